@@ -73,6 +73,93 @@ bitflags! {
     }
 }
 
+bitflags! {
+    /**
+    Flags used to specify why a GPU's clocks are being held below their
+    maximum.
+
+    This replaces `ThrottleReasons`, following NVML's renaming of "clocks
+    throttle reasons" to "clocks event reasons"; the bits are the same.
+    */
+    // Checked against local
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+    pub struct ClocksEventReasons: u64 {
+        /// Nothing is running on the GPU.
+        ///
+        /// This limiter may be removed in a future release.
+        const GPU_IDLE                    = nvmlClocksEventReasonGpuIdle as u64;
+        /// GPU clocks are limited by the current applications clocks setting.
+        const APPLICATIONS_CLOCKS_SETTING = nvmlClocksEventReasonApplicationsClocksSetting as u64;
+        /// Software power scaling algorithm is reducing clocks.
+        const SW_POWER_CAP                = nvmlClocksEventReasonSwPowerCap as u64;
+        /**
+        Hardware slowdown (reducing the core clocks by a factor of 2 or more)
+        is engaged.
+
+        This is an indicator of:
+
+        * Temperature being too high
+        * External Power Brake Assertion being triggered (e.g. by the system power supply)
+        * Power draw being too high and Fast Trigger protection reducing the clocks
+
+        This may also be reported during powerstate or clock change, behavior that may be
+        removed in a later release.
+        */
+        // NVML only defines this under its old name
+        const HW_SLOWDOWN                 = nvmlClocksThrottleReasonHwSlowdown as u64;
+        /**
+        This GPU is being held at lower clocks by another GPU in its sync boost
+        group.
+
+        All GPUs in a sync boost group will boost to the minimum possible clocks
+        across the entire group. Look at the clocks event reasons for other GPUs
+        in the system to find out why this GPU is being held at lower clocks.
+        */
+        const SYNC_BOOST                  = nvmlClocksEventReasonSyncBoost as u64;
+        /**
+        Software thermal slowdown.
+
+        This is an indicator of one or more of the following:
+
+        * The current GPU temperature is above the max GPU operating temperature
+        * The current memory temperature is above the max memory operating temperature
+        */
+        const SW_THERMAL_SLOWDOWN         = nvmlClocksEventReasonSwThermalSlowdown as u64;
+        /**
+        Hardware thermal slowdown is engaged, reducing core clocks by 2x or more.
+
+        This indicates that the temperature of the GPU is too high.
+        */
+        // NVML only defines this under its old name
+        const HW_THERMAL_SLOWDOWN         = nvmlClocksThrottleReasonHwThermalSlowdown as u64;
+        /**
+        Hardware power brake slowdown is engaged, reducing core clocks by 2x or more.
+
+        This indicates that an external power brake assertion is being triggered,
+        such as by the system power supply.
+        */
+        // NVML only defines this under its old name
+        const HW_POWER_BRAKE_SLOWDOWN     = nvmlClocksThrottleReasonHwPowerBrakeSlowdown as u64;
+        /// GPU clocks are limited by the current setting of display clocks.
+        const DISPLAY_CLOCK_SETTING       = nvmlClocksEventReasonDisplayClockSetting as u64;
+        /// Clocks are as high as possible.
+        const NONE                        = nvmlClocksEventReasonNone as u64;
+    }
+}
+
+impl From<ThrottleReasons> for ClocksEventReasons {
+    fn from(reasons: ThrottleReasons) -> Self {
+        Self::from_bits_truncate(reasons.bits())
+    }
+}
+
+impl From<ClocksEventReasons> for ThrottleReasons {
+    fn from(reasons: ClocksEventReasons) -> Self {
+        Self::from_bits_truncate(reasons.bits())
+    }
+}
+
 bitflags! {
     /// Flags that specify info about a frame capture session
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -88,3 +175,59 @@ bitflags! {
         const CAPTURE_WITH_WAIT_TIMEOUT   = NVML_NVFBC_SESSION_FLAG_CAPTURE_WITH_WAIT_TIMEOUT;
     }
 }
+
+bitflags! {
+    /// The VM states in which a vGPU can be migrated to a physical GPU.
+    ///
+    /// If no flags are set the vGPU cannot be migrated.
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+    pub struct VgpuVmCompatibility: u32 {
+        /// The VM must be powered off.
+        const COLD      = nvmlVgpuVmCompatibility_enum_NVML_VGPU_VM_COMPATIBILITY_COLD;
+        /// The VM must be hibernated.
+        const HIBERNATE = nvmlVgpuVmCompatibility_enum_NVML_VGPU_VM_COMPATIBILITY_HIBERNATE;
+        /// The VM must be sleeping.
+        const SLEEP     = nvmlVgpuVmCompatibility_enum_NVML_VGPU_VM_COMPATIBILITY_SLEEP;
+        /// The VM can be running.
+        const LIVE      = nvmlVgpuVmCompatibility_enum_NVML_VGPU_VM_COMPATIBILITY_LIVE;
+    }
+}
+
+bitflags! {
+    /// What limits the compatibility of a vGPU with a physical GPU.
+    ///
+    /// If no flags are set compatibility is not limited.
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+    pub struct VgpuCompatibilityLimit: u32 {
+        /// Limited by the host driver.
+        const HOST_DRIVER  = nvmlVgpuPgpuCompatibilityLimitCode_enum_NVML_VGPU_COMPATIBILITY_LIMIT_HOST_DRIVER;
+        /// Limited by the guest driver.
+        const GUEST_DRIVER = nvmlVgpuPgpuCompatibilityLimitCode_enum_NVML_VGPU_COMPATIBILITY_LIMIT_GUEST_DRIVER;
+        /// Limited by the physical GPU.
+        const GPU          = nvmlVgpuPgpuCompatibilityLimitCode_enum_NVML_VGPU_COMPATIBILITY_LIMIT_GPU;
+        /// Limited by something else.
+        const OTHER        = nvmlVgpuPgpuCompatibilityLimitCode_enum_NVML_VGPU_COMPATIBILITY_LIMIT_OTHER;
+    }
+}
+
+bitflags! {
+    /// What a cooler cools.
+    ///
+    /// Used in [`crate::struct_wrappers::device::CoolerInfo`].
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+    pub struct CoolerTarget: u32 {
+        /// The cooler cools nothing.
+        const NONE         = nvmlCoolerTarget_enum_NVML_THERMAL_COOLER_TARGET_NONE;
+        /// The cooler cools the GPU.
+        const GPU          = nvmlCoolerTarget_enum_NVML_THERMAL_COOLER_TARGET_GPU;
+        /// The cooler cools the memory.
+        const MEMORY       = nvmlCoolerTarget_enum_NVML_THERMAL_COOLER_TARGET_MEMORY;
+        /// The cooler cools the power supply.
+        const POWER_SUPPLY = nvmlCoolerTarget_enum_NVML_THERMAL_COOLER_TARGET_POWER_SUPPLY;
+        /// The cooler cools the GPU, memory and power supply.
+        const GPU_RELATED  = nvmlCoolerTarget_enum_NVML_THERMAL_COOLER_TARGET_GPU_RELATED;
+    }
+}
@@ -3,7 +3,7 @@ use crate::EventSet;
 use crate::NvLink;
 use crate::Nvml;
 
-use crate::bitmasks::device::ThrottleReasons;
+use crate::bitmasks::device::{ClocksEventReasons, ThrottleReasons};
 #[cfg(target_os = "linux")]
 use crate::bitmasks::event::EventTypes;
 #[cfg(target_os = "windows")]
@@ -13,24 +13,36 @@ use crate::enum_wrappers::{bool_from_state, device::*, state_from_bool};
 
 use crate::enums::device::{
     BusType, DeviceArchitecture, FanControlPolicy, GpuLockedClocksSetting, PcieLinkMaxSpeed,
-    PowerSource,
+    PowerScope, PowerSmoothingProfileParam, PowerSource, ThermalTarget,
 };
+use crate::enums::nv_link::NvLinkBwMode;
 #[cfg(target_os = "linux")]
 use crate::error::NvmlErrorWithSource;
-use crate::error::{nvml_sym, nvml_try, Bits, NvmlError};
+use crate::error::{nvml_sym, nvml_try, nvml_try_blob, nvml_try_list, Bits, NvmlError};
+
+use crate::high_level::attestation::AttestationEvidence;
+use crate::high_level::clocks::{ApplicationClocksGuard, LockedClocksGuard};
+use crate::high_level::compare::{DeviceComparison, DeviceProperties};
+use crate::high_level::ecc::EccModeGuard;
+use crate::high_level::health::HealthReport;
+use crate::high_level::power::PowerReadings;
+use crate::high_level::support::SupportMatrix;
 
 use crate::ffi::bindings::*;
 
 use crate::struct_wrappers::device::*;
 use crate::structs::device::*;
 
-use crate::vgpu::VgpuType;
+#[cfg(target_os = "linux")]
+use crate::vgpu::VgpuInstance;
+use crate::vgpu::{VgpuType, VgpuTypeId};
 
 #[cfg(target_os = "linux")]
 use std::convert::TryInto;
 #[cfg(target_os = "linux")]
 use std::os::raw::c_ulong;
 use std::{
+    collections::BTreeMap,
     convert::TryFrom,
     ffi::CStr,
     mem,
@@ -79,6 +91,17 @@ unsafe impl Sync for Device<'_> {}
 
 assert_impl_all!(Device: Send, Sync);
 
+/// The C struct passed to the calls that request and clear workload power
+/// profiles.
+fn requested_profiles(profiles: &PowerProfileMask) -> nvmlWorkloadPowerProfileRequestedProfiles_t {
+    nvmlWorkloadPowerProfileRequestedProfiles_t {
+        // Implements NVML_STRUCT_VERSION(WorkloadPowerProfileRequestedProfiles, 1), as detailed in nvml.h
+        version: (mem::size_of::<nvmlWorkloadPowerProfileRequestedProfiles_v1_t>()
+            | (1_usize << 24_usize)) as u32,
+        requestedProfilesMask: profiles.as_c(),
+    }
+}
+
 impl<'nvml> Device<'nvml> {
     /**
     Create a new `Device` wrapper.
@@ -334,6 +357,32 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets the NUMA node of this `Device`.
+
+    This only applies to platforms where GPU memory is exposed as a NUMA node
+    (e.g. coherently attached GPUs such as Grace Hopper). Use
+    `.memory_affinity()` to find the CPU NUMA nodes closest to other GPUs.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` is not a NUMA node
+    * `Unknown`, on any unexpected error
+    */
+    #[doc(alias = "nvmlDeviceGetNumaNodeId")]
+    pub fn numa_node_id(&self) -> Result<u32, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetNumaNodeId.as_ref())?;
+
+        unsafe {
+            let mut node: c_uint = mem::zeroed();
+            nvml_try(sym(self.device, &mut node))?;
+
+            Ok(node)
+        }
+    }
+
     /**
     Gets the board ID for this `Device`, from 0-N.
 
@@ -589,6 +638,10 @@ impl<'nvml> Device<'nvml> {
     with an active context). Graphics applications (OpenGL, DirectX) won't be listed by this
     function.
 
+    When called on a MIG device handle, only the processes running on that MIG
+    device are returned. Called on the parent `Device`, processes from every MIG
+    device are returned with their `gpu_instance_id` and `compute_instance_id` set.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
@@ -873,7 +926,6 @@ impl<'nvml> Device<'nvml> {
     * `InvalidArg`, if device is invalid or counters is NULL
     * `NotSupported`, if the device does not support this feature
     * `GpuLost`, if the target GPU has fallen off the bus or is otherwise inaccessible
-    * `ArgumentVersionMismatch`, if the provided version is invalid/unsupported
     * `Unknown`, on any unexpected error
     */
     #[doc(alias = "nvmlDeviceSetConfComputeSettings")]
@@ -898,7 +950,6 @@ impl<'nvml> Device<'nvml> {
     * `InvalidArg`, if device is invalid or counters is NULL
     * `NotSupported`, if the device does not support this feature
     * `GpuLost`, if the target GPU has fallen off the bus or is otherwise inaccessible
-    * `ArgumentVersionMismatch`, if the provided version is invalid/unsupported
     * `Unknown`, on any unexpected error
     */
     #[doc(alias = "nvmlSystemGetConfComputeSettings")]
@@ -923,7 +974,6 @@ impl<'nvml> Device<'nvml> {
     * `InvalidArg`, if device is invalid or counters is NULL
     * `NotSupported`, if the device does not support this feature
     * `GpuLost`, if the target GPU has fallen off the bus or is otherwise inaccessible
-    * `ArgumentVersionMismatch`, if the provided version is invalid/unsupported
     * `Unknown`, on any unexpected error
     */
     #[doc(alias = "nvmlSystemGetConfComputeSettings")]
@@ -942,54 +992,29 @@ impl<'nvml> Device<'nvml> {
 
     /**
     Gets the confidential compute capabilities for this `Device`.
+
+    These are system-wide; this is the same as
+    `Nvml.confidential_compute_capabilities()`.
+
     # Errors
     * `Uninitialized`, if the library has not been successfully initialized
     * `InvalidArg`, if device is invalid or memory is NULL
     * `NotSupported`, if this query is not supported by the device
+    * `UnexpectedVariant`, for which you can read the docs for
     */
     pub fn get_confidential_compute_capabilities(
         &self,
     ) -> Result<ConfidentialComputeCapabilities, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlSystemGetConfComputeCapabilities.as_ref())?;
-
-        unsafe {
-            let mut capabilities: nvmlConfComputeSystemCaps_t = mem::zeroed();
-            nvml_try(sym(&mut capabilities))?;
-
-            let cpu_caps = match capabilities.cpuCaps {
-                NVML_CC_SYSTEM_CPU_CAPS_NONE => ConfidentialComputeCpuCapabilities::None,
-                NVML_CC_SYSTEM_CPU_CAPS_AMD_SEV => ConfidentialComputeCpuCapabilities::AmdSev,
-                NVML_CC_SYSTEM_CPU_CAPS_INTEL_TDX => ConfidentialComputeCpuCapabilities::IntelTdx,
-                _ => return Err(NvmlError::Unknown),
-            };
-
-            let gpus_caps = match capabilities.gpusCaps {
-                NVML_CC_SYSTEM_GPUS_CC_CAPABLE => ConfidentialComputeGpuCapabilities::Capable,
-                NVML_CC_SYSTEM_GPUS_CC_NOT_CAPABLE => {
-                    ConfidentialComputeGpuCapabilities::NotCapable
-                }
-                _ => return Err(NvmlError::Unknown),
-            };
-
-            Ok(ConfidentialComputeCapabilities {
-                cpu_caps,
-                gpus_caps,
-            })
-        }
+        self.nvml.confidential_compute_capabilities()
     }
 
     /**
     Fetches the confidential compute attestation report for this [`Device`].
 
-    This method retrieves a comprehensive attestation report from the device, which includes:
-    - A 32-byte nonce
-    - The attestation report size (as big-endian bytes)
-    - The attestation report data (up to 8192 bytes)
-    - A flag indicating if CEC attestation is present (as big-endian bytes)
-    - The CEC attestation report size (as big-endian bytes)
-    - The CEC attestation report data (up to 4096 bytes)
-
-    The returned vector contains all these components concatenated together in the order listed above.
+    `nonce` is included in the signed report, so that a verifier can check
+    that the report is fresh. The attestation report, and the CEC
+    attestation report if the device has one, are returned without the
+    unused tail of NVML's fixed-size buffers.
 
     # Errors
 
@@ -997,8 +1022,12 @@ impl<'nvml> Device<'nvml> {
     * `InvalidArg`, if device is invalid or memory is NULL
     * `NotSupported`, if this query is not supported by the device
     * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Hopper and newer fully supported devices.
     */
-    #[doc(alias = "nvmlDeviceGetAttestationReport")]
+    #[doc(alias = "nvmlDeviceGetConfComputeGpuAttestationReport")]
     pub fn confidential_compute_gpu_attestation_report(
         &self,
         nonce: [u8; NVML_CC_GPU_CEC_NONCE_SIZE as usize],
@@ -1016,20 +1045,17 @@ impl<'nvml> Device<'nvml> {
 
             nvml_try(sym(self.device, &mut report))?;
 
-            let is_cec_attestation_report_present = report.isCecAttestationReportPresent == 1;
-            Ok(ConfidentialComputeGpuAttestationReport {
-                attestation_report_size: report.attestationReportSize,
-                attestation_report: report.attestationReport.to_vec(),
-                is_cec_attestation_report_present,
-                cec_attestation_report_size: report.cecAttestationReportSize,
-                cec_attestation_report: report.cecAttestationReport.to_vec(),
-            })
+            Ok(ConfidentialComputeGpuAttestationReport::from(report))
         }
     }
 
     /**
     Gets the confidential compute GPU certificate for this `Device`.
 
+    The certificate chain and attestation certificate chain are returned
+    without the unused tail of NVML's fixed-size buffers, ready to be handed
+    to an attestation verifier.
+
     # Errors
 
     * `Uninitialized` if the library has not been successfully initialized
@@ -1037,6 +1063,7 @@ impl<'nvml> Device<'nvml> {
     * `NotSupported` if this query is not supported by the device
     * `Unknown` on any unexpected error
     */
+    #[doc(alias = "nvmlDeviceGetConfComputeGpuCertificate")]
     pub fn confidential_compute_gpu_certificate(
         &self,
     ) -> Result<ConfidentialComputeGpuCertificate, NvmlError> {
@@ -1051,15 +1078,78 @@ impl<'nvml> Device<'nvml> {
             let mut certificate_chain: nvmlConfComputeGpuCertificate_t = mem::zeroed();
             nvml_try(sym(self.device, &mut certificate_chain))?;
 
-            Ok(ConfidentialComputeGpuCertificate {
-                cert_chain_size: certificate_chain.certChainSize,
-                attestation_cert_chain_size: certificate_chain.attestationCertChainSize,
-                cert_chain: certificate_chain.certChain.to_vec(),
-                attestation_cert_chain: certificate_chain.attestationCertChain.to_vec(),
-            })
+            Ok(ConfidentialComputeGpuCertificate::from(certificate_chain))
+        }
+    }
+
+    /**
+    Gets the sizes of the protected and unprotected memory of this `Device`
+    under confidential compute.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this query is not supported by the device
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere or newer fully supported devices.
+
+    # Platform Support
+
+    Supports Linux and Windows TCC.
+    */
+    #[doc(alias = "nvmlDeviceGetConfComputeMemSizeInfo")]
+    pub fn conf_compute_mem_size_info(&self) -> Result<ConfComputeMemSizeInfo, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetConfComputeMemSizeInfo.as_ref())?;
+
+        unsafe {
+            let mut info: nvmlConfComputeMemSizeInfo_t = mem::zeroed();
+            nvml_try(sym(self.device, &mut info))?;
+
+            Ok(info.into())
         }
     }
 
+    /**
+    Sets the size of the unprotected memory of this `Device` under
+    confidential compute, in KiB. The rest of the memory is protected.
+
+    See `.conf_compute_mem_size_info()` for the current split.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this query is not supported by the device
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere or newer fully supported devices.
+
+    # Platform Support
+
+    Supports Linux and Windows TCC.
+    */
+    #[doc(alias = "nvmlDeviceSetConfComputeUnprotectedMemSize")]
+    pub fn set_conf_compute_unprotected_mem_size(
+        &mut self,
+        size_kib: u64,
+    ) -> Result<(), NvmlError> {
+        let sym = nvml_sym(
+            self.nvml
+                .lib
+                .nvmlDeviceSetConfComputeUnprotectedMemSize
+                .as_ref(),
+        )?;
+
+        unsafe { nvml_try(sym(self.device, size_kib)) }
+    }
+
     /**
     Gets the current PCIe link generation.
 
@@ -1435,6 +1525,8 @@ impl<'nvml> Device<'nvml> {
     Changing ECC modes requires a reboot. The "pending" ECC mode refers to the target
     mode following the next reboot.
 
+    See `EccModeState::is_change_pending()` to check whether the two differ.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
@@ -1695,7 +1787,9 @@ impl<'nvml> Device<'nvml> {
     }
 
     /**
-    Sets the GPU clock frequency offset value.
+    Gets the range the GPU clock frequency offset value can be set to.
+
+    Returns a (min, max) tuple, in MHz.
 
     # Errors
 
@@ -1703,6 +1797,41 @@ impl<'nvml> Device<'nvml> {
     * `InvalidArg`, if this `Device` is invalid
     * `NotSupported`, if this `Device` does not support this feature
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports all discrete products with unlocked overclocking capabilities.
+    */
+    // Checked against local
+    // Tested
+    #[doc(alias = "nvmlDeviceGetGpcClkMinMaxVfOffset")]
+    pub fn gpc_clock_min_max_vf_offset(&self) -> Result<(i32, i32), NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetGpcClkMinMaxVfOffset.as_ref())?;
+
+        unsafe {
+            let mut min: c_int = mem::zeroed();
+            let mut max: c_int = mem::zeroed();
+            nvml_try(sym(self.device, &mut min, &mut max))?;
+
+            Ok((min, max))
+        }
+    }
+
+    /**
+    Sets the GPU clock frequency offset value.
+
+    The offset is checked against the range returned by
+    `.gpc_clock_min_max_vf_offset()` before it is applied, if that range can be
+    read.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid or `offset` is out of range
+    * `NotSupported`, if this `Device` does not support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
     * `UnexpectedVariant`, for which you can read the docs for
     * `Unknown`, on any unexpected error
 
@@ -1712,8 +1841,14 @@ impl<'nvml> Device<'nvml> {
     */
     // Checked against local
     // Tested (no-run)
-    #[doc(alias = "nvmlDeviceGetGpcClkVfOffset")]
+    #[doc(alias = "nvmlDeviceSetGpcClkVfOffset")]
     pub fn set_gpc_clock_vf_offset(&self, offset: i32) -> Result<(), NvmlError> {
+        if let Ok((min, max)) = self.gpc_clock_min_max_vf_offset() {
+            if offset < min || offset > max {
+                return Err(NvmlError::InvalidArg);
+            }
+        }
+
         let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetGpcClkVfOffset.as_ref())?;
 
         unsafe { nvml_try(sym(self.device, offset)) }
@@ -1737,7 +1872,7 @@ impl<'nvml> Device<'nvml> {
     */
     // Checked against local
     // Tested (no-run)
-    #[doc(alias = "nvmlDeviceGetGpcMemClkVfOffset")]
+    #[doc(alias = "nvmlDeviceGetMemClkVfOffset")]
     pub fn mem_clock_vf_offset(&self) -> Result<i32, NvmlError> {
         let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetMemClkVfOffset.as_ref())?;
 
@@ -1750,7 +1885,9 @@ impl<'nvml> Device<'nvml> {
     }
 
     /**
-    Sets the memory clock frequency offset value.
+    Gets the range the memory clock frequency offset value can be set to.
+
+    Returns a (min, max) tuple, in MHz.
 
     # Errors
 
@@ -1758,6 +1895,41 @@ impl<'nvml> Device<'nvml> {
     * `InvalidArg`, if this `Device` is invalid
     * `NotSupported`, if this `Device` does not support this feature
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports all discrete products with unlocked overclocking capabilities.
+    */
+    // Checked against local
+    // Tested
+    #[doc(alias = "nvmlDeviceGetMemClkMinMaxVfOffset")]
+    pub fn mem_clock_min_max_vf_offset(&self) -> Result<(i32, i32), NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetMemClkMinMaxVfOffset.as_ref())?;
+
+        unsafe {
+            let mut min: c_int = mem::zeroed();
+            let mut max: c_int = mem::zeroed();
+            nvml_try(sym(self.device, &mut min, &mut max))?;
+
+            Ok((min, max))
+        }
+    }
+
+    /**
+    Sets the memory clock frequency offset value.
+
+    The offset is checked against the range returned by
+    `.mem_clock_min_max_vf_offset()` before it is applied, if that range can be
+    read.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid or `offset` is out of range
+    * `NotSupported`, if this `Device` does not support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
     * `UnexpectedVariant`, for which you can read the docs for
     * `Unknown`, on any unexpected error
 
@@ -1767,8 +1939,14 @@ impl<'nvml> Device<'nvml> {
     */
     // Checked against local
     // Tested (no-run)
-    #[doc(alias = "nvmlDeviceSetGpcMemClkVfOffset")]
+    #[doc(alias = "nvmlDeviceSetMemClkVfOffset")]
     pub fn set_mem_clock_vf_offset(&self, offset: i32) -> Result<(), NvmlError> {
+        if let Ok((min, max)) = self.mem_clock_min_max_vf_offset() {
+            if offset < min || offset > max {
+                return Err(NvmlError::InvalidArg);
+            }
+        }
+
         let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetMemClkVfOffset.as_ref())?;
 
         unsafe { nvml_try(sym(self.device, offset)) }
@@ -1809,6 +1987,42 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets the intended operating speed of the specified fan as a percentage of
+    the maximum fan speed (100%).
+
+    This is the speed the fan is being driven to, by the fan control policy or
+    by [`Self::set_fan_speed()`]; compare it with [`Self::fan_speed()`] to see
+    whether the fan has reached it.
+
+    You can determine valid fan indices using [`Self::num_fans()`].
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid or `fan_idx` is invalid
+    * `NotSupported`, if this `Device` does not have a fan
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports all discrete products with dedicated fans.
+    */
+    // Checked against local
+    // Tested
+    #[doc(alias = "nvmlDeviceGetTargetFanSpeed")]
+    pub fn target_fan_speed(&self, fan_idx: u32) -> Result<u32, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetTargetFanSpeed.as_ref())?;
+
+        unsafe {
+            let mut speed: c_uint = mem::zeroed();
+            nvml_try(sym(self.device, fan_idx, &mut speed))?;
+
+            Ok(speed)
+        }
+    }
+
     /**
     Retrieves the intended operating speed in rotations per minute (RPM) of the
     device's specified fan.
@@ -1851,16 +2065,55 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets the control signal type and target of the cooler at `cooler_idx`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid or `cooler_idx` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `IncorrectBits`, if NVML returns an unknown cooler target
+    * `UnexpectedVariant`, for which you can read the docs for (this includes
+      `NVML_ERROR_ARGUMENT_VERSION_MISMATCH`, if the struct version is not
+      supported by the driver)
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    For all discrete products with dedicated fans.
+    */
+    // Checked against local
+    // Tested
+    #[doc(alias = "nvmlDeviceGetCoolerInfo")]
+    pub fn cooler_info(&self, cooler_idx: u32) -> Result<CoolerInfo, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetCoolerInfo.as_ref())?;
+
+        unsafe {
+            let mut info: nvmlCoolerInfo_t = mem::zeroed();
+            // Implements NVML_STRUCT_VERSION(CoolerInfo, 1), as detailed in nvml.h
+            info.version = (mem::size_of::<nvmlCoolerInfo_v1_t>() | (1_usize << 24_usize)) as u32;
+            info.index = cooler_idx;
+
+            nvml_try(sym(self.device, &mut info))?;
+
+            CoolerInfo::try_from(info)
+        }
+    }
+
     /**
     Retrieves the min and max fan speed that user can set for the GPU fan.
 
-    Returns a (min, max) tuple.
+    Returns a (min, max) tuple, in percent of the maximum fan speed. This is
+    the range [`Self::set_fan_speed()`] accepts.
 
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
     * `InvalidArg`, if this `Device` is invalid
     * `NotSupported`, if this `Device` does not have fans
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
     * `Unknown`, on any unexpected error
 
     # Device Support
@@ -1946,12 +2199,16 @@ impl<'nvml> Device<'nvml> {
     WARNING: This function changes the fan control policy to manual. It means that YOU have to monitor the temperature and adjust the fan speed accordingly.
     If you set the fan speed too low you can burn your GPU! Use [`Device::set_default_fan_speed`] to restore default control policy.
 
+    `speed` is a percentage of the maximum fan speed. It is checked against
+    the range returned by [`Self::min_max_fan_speed()`] before it is applied,
+    if that range can be read.
+
     You can determine valid fan indices using [`Self::num_fans()`].
 
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
-    * `InvalidArg`, if this `Device` is invalid or `fan_idx` is invalid
+    * `InvalidArg`, if this `Device` is invalid, `fan_idx` is invalid or `speed` is out of range
     * `NotSupported`, if this `Device` does not have a fan
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
     * `UnexpectedVariant`, for which you can read the docs for
@@ -1961,8 +2218,16 @@ impl<'nvml> Device<'nvml> {
 
     Supports Maxwell or newer fully supported discrete devices with fans.
      */
+    // Checked against local
+    // Tested (no-run)
     #[doc(alias = "nvmlDeviceSetFanSpeed_v2")]
     pub fn set_fan_speed(&mut self, fan_idx: u32, speed: u32) -> Result<(), NvmlError> {
+        if let Ok((min, max)) = self.min_max_fan_speed() {
+            if speed < min || speed > max {
+                return Err(NvmlError::InvalidArg);
+            }
+        }
+
         let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetFanSpeed_v2.as_ref())?;
 
         unsafe { nvml_try(sym(self.device, fan_idx, speed)) }
@@ -1986,6 +2251,8 @@ impl<'nvml> Device<'nvml> {
 
     Supports Maxwell or newer fully supported discrete devices with fans.
      */
+    // Checked against local
+    // Tested (no-run)
     #[doc(alias = "nvmlDeviceSetDefaultFanSpeed_v2")]
     pub fn set_default_fan_speed(&mut self, fan_idx: u32) -> Result<(), NvmlError> {
         let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetDefaultFanSpeed_v2.as_ref())?;
@@ -1996,6 +2263,9 @@ impl<'nvml> Device<'nvml> {
     /**
     Gets the number of fans on this [`Device`].
 
+    Valid fan indices for the per-fan methods, such as [`Self::fan_speed()`],
+    range from 0 up to this count.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
@@ -2007,6 +2277,8 @@ impl<'nvml> Device<'nvml> {
 
     Supports all discrete products with dedicated fans.
     */
+    // Checked against local
+    // Tested
     #[doc(alias = "nvmlDeviceGetNumFans")]
     pub fn num_fans(&self) -> Result<u32, NvmlError> {
         let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetNumFans.as_ref())?;
@@ -2264,48 +2536,290 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
-    fn process_utilization_stats_count(
-        &self,
-        last_seen_timestamp: u64,
-    ) -> Result<c_uint, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetProcessUtilization.as_ref())?;
-
-        unsafe {
-            let mut count: c_uint = 0;
-
-            match sym(
-                self.device,
-                ptr::null_mut(),
-                &mut count,
-                last_seen_timestamp,
-            ) {
-                // Despite being undocumented, this appears to be the correct behavior
-                nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => Ok(count),
-                other => nvml_try(other).map(|_| 0),
-            }
-        }
-    }
-
     /**
-    Gets the NVML index of this `Device`.
+    Gets utilization stats for the vGPU instances running on this `Device`.
 
-    Keep in mind that the order in which NVML enumerates devices has no guarantees of
-    consistency between reboots. Also, the NVML index may not correlate with other APIs,
-    such as the CUDA device index.
+    Passing `None` as the `last_seen_timestamp` will target all samples that
+    the driver has buffered; passing a timestamp retrieved from a previous
+    query will target samples taken since that timestamp.
+
+    Returns an empty `Vec` if there are no samples (e.g. no vGPU instances are
+    running).
 
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
     * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `InsufficientSize`, if the list kept growing while it was being fetched
+    * `UnexpectedVariant`, for which you can read the docs for
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
-    */
-    // Checked against local
-    // Tested
-    #[doc(alias = "nvmlDeviceGetIndex")]
-    pub fn index(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetIndex.as_ref())?;
+    * `Unknown`, on any unexpected error
 
-        unsafe {
+    # Device Support
+
+    Supports Maxwell or newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetVgpuUtilization")]
+    pub fn vgpu_utilization_stats<T>(
+        &self,
+        last_seen_timestamp: T,
+    ) -> Result<Vec<VgpuUtilizationSample>, NvmlError>
+    where
+        T: Into<Option<u64>>,
+    {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetVgpuUtilization.as_ref())?;
+
+        let last_seen_timestamp = last_seen_timestamp.into().unwrap_or(0);
+        let mut val_type: nvmlValueType_t = 0;
+
+        let samples = nvml_try_list(unsafe { mem::zeroed() }, |count, samples| unsafe {
+            sym(
+                self.device,
+                last_seen_timestamp,
+                &mut val_type,
+                count,
+                samples,
+            )
+        });
+
+        let samples = match samples {
+            Ok(samples) if !samples.is_empty() => samples,
+            // NVML reports that there are no samples as `NotFound`
+            Ok(_) | Err(NvmlError::NotFound) => return Ok(vec![]),
+            Err(e) => return Err(e),
+        };
+
+        let val_type = SampleValueType::try_from(val_type)?;
+
+        Ok(samples
+            .into_iter()
+            .map(|s| VgpuUtilizationSample::from_tag_and_struct(&val_type, s))
+            .collect())
+    }
+
+    /**
+    Gets utilization info for every active vGPU instance on this `Device` in
+    a single call.
+
+    This is the versioned successor of `.vgpu_utilization_stats()` and also
+    reports JPEG and optical flow accelerator utilization.
+
+    Passing `None` as the `last_seen_timestamp` will target all samples that
+    the driver has buffered; passing a timestamp retrieved from a previous
+    query will target samples taken since that timestamp.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `InsufficientSize`, if the list kept growing while it was being fetched
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell or newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetVgpuInstancesUtilizationInfo")]
+    pub fn vgpu_instances_utilization_info<T>(
+        &self,
+        last_seen_timestamp: T,
+    ) -> Result<Vec<VgpuUtilizationInfo>, NvmlError>
+    where
+        T: Into<Option<u64>>,
+    {
+        let sym = nvml_sym(
+            self.nvml
+                .lib
+                .nvmlDeviceGetVgpuInstancesUtilizationInfo
+                .as_ref(),
+        )?;
+
+        let mut info: nvmlVgpuInstancesUtilizationInfo_t = unsafe { mem::zeroed() };
+        // Implements NVML_STRUCT_VERSION(VgpuInstancesUtilizationInfo, 1), as detailed in nvml.h
+        info.version = (mem::size_of::<nvmlVgpuInstancesUtilizationInfo_v1_t>()
+            | (1_usize << 24_usize)) as u32;
+        info.lastSeenTimeStamp = last_seen_timestamp.into().unwrap_or(0);
+
+        // The count and buffer live in the struct rather than being passed
+        // directly, so they're copied in and out around each call
+        let samples = nvml_try_list(unsafe { mem::zeroed() }, |count, samples| unsafe {
+            info.vgpuInstanceCount = *count;
+            info.vgpuUtilArray = samples;
+
+            let res = sym(self.device, &mut info);
+            *count = info.vgpuInstanceCount;
+
+            res
+        })?;
+
+        if samples.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let val_type = SampleValueType::try_from(info.sampleValType)?;
+
+        Ok(samples
+            .into_iter()
+            .map(|s| VgpuUtilizationInfo::from_tag_and_struct(&val_type, s))
+            .collect())
+    }
+
+    /**
+    Gets utilization stats for the processes running in the vGPU guests on
+    this `Device` that used the GPU during the sample period.
+
+    Passing `None` as the `last_seen_timestamp` will target all samples that
+    the driver has buffered; passing a timestamp retrieved from a previous
+    query will target samples taken since that timestamp.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `InsufficientSize`, if the list kept growing while it was being fetched
+    * `Utf8Error`, if a process name obtained from the C function is not valid
+      Utf8
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell or newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetVgpuProcessUtilization")]
+    pub fn vgpu_process_utilization_stats<T>(
+        &self,
+        last_seen_timestamp: T,
+    ) -> Result<Vec<VgpuProcessUtilizationSample>, NvmlError>
+    where
+        T: Into<Option<u64>>,
+    {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetVgpuProcessUtilization.as_ref())?;
+
+        let last_seen_timestamp = last_seen_timestamp.into().unwrap_or(0);
+
+        let samples = nvml_try_list(unsafe { mem::zeroed() }, |count, samples| unsafe {
+            sym(self.device, last_seen_timestamp, count, samples)
+        })?;
+
+        samples
+            .into_iter()
+            .map(VgpuProcessUtilizationSample::try_from)
+            .collect()
+    }
+
+    /**
+    Gets utilization info for every process running in the vGPU guests on
+    this `Device` in a single call.
+
+    This is the versioned successor of `.vgpu_process_utilization_stats()`
+    and also reports JPEG and optical flow accelerator utilization.
+
+    Passing `None` as the `last_seen_timestamp` will target all samples that
+    the driver has buffered; passing a timestamp retrieved from a previous
+    query will target samples taken since that timestamp.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `InsufficientSize`, if the list kept growing while it was being fetched
+    * `Utf8Error`, if a process name obtained from the C function is not valid
+      Utf8
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell or newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetVgpuProcessesUtilizationInfo")]
+    pub fn vgpu_processes_utilization_info<T>(
+        &self,
+        last_seen_timestamp: T,
+    ) -> Result<Vec<VgpuProcessUtilizationInfo>, NvmlError>
+    where
+        T: Into<Option<u64>>,
+    {
+        let sym = nvml_sym(
+            self.nvml
+                .lib
+                .nvmlDeviceGetVgpuProcessesUtilizationInfo
+                .as_ref(),
+        )?;
+
+        let mut info: nvmlVgpuProcessesUtilizationInfo_t = unsafe { mem::zeroed() };
+        // Implements NVML_STRUCT_VERSION(VgpuProcessesUtilizationInfo, 1), as detailed in nvml.h
+        info.version = (mem::size_of::<nvmlVgpuProcessesUtilizationInfo_v1_t>()
+            | (1_usize << 24_usize)) as u32;
+        info.lastSeenTimeStamp = last_seen_timestamp.into().unwrap_or(0);
+
+        // The count and buffer live in the struct rather than being passed
+        // directly, so they're copied in and out around each call
+        let samples = nvml_try_list(unsafe { mem::zeroed() }, |count, samples| unsafe {
+            info.vgpuProcessCount = *count;
+            info.vgpuProcUtilArray = samples;
+
+            let res = sym(self.device, &mut info);
+            *count = info.vgpuProcessCount;
+
+            res
+        })?;
+
+        samples
+            .into_iter()
+            .map(VgpuProcessUtilizationInfo::try_from)
+            .collect()
+    }
+
+    fn process_utilization_stats_count(
+        &self,
+        last_seen_timestamp: u64,
+    ) -> Result<c_uint, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetProcessUtilization.as_ref())?;
+
+        unsafe {
+            let mut count: c_uint = 0;
+
+            match sym(
+                self.device,
+                ptr::null_mut(),
+                &mut count,
+                last_seen_timestamp,
+            ) {
+                // Despite being undocumented, this appears to be the correct behavior
+                nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => Ok(count),
+                other => nvml_try(other).map(|_| 0),
+            }
+        }
+    }
+
+    /**
+    Gets the NVML index of this `Device`.
+
+    Keep in mind that the order in which NVML enumerates devices has no guarantees of
+    consistency between reboots. Also, the NVML index may not correlate with other APIs,
+    such as the CUDA device index.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    */
+    // Checked against local
+    // Tested
+    #[doc(alias = "nvmlDeviceGetIndex")]
+    pub fn index(&self) -> Result<u32, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetIndex.as_ref())?;
+
+        unsafe {
             let mut index: c_uint = mem::zeroed();
             nvml_try(sym(self.device, &mut index))?;
 
@@ -2579,6 +3093,49 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets every memory error counter for this `Device`: corrected and
+    uncorrected errors, volatile and aggregate, for every memory location.
+
+    This makes one `.memory_error_counter()` call per counter. Counters the
+    device doesn't support are `None`.
+
+    # Errors
+
+    Any error other than `NotSupported` returned by `.memory_error_counter()`.
+
+    # Device Support
+
+    Supports Fermi and newer fully supported devices. Only applicable to
+    devices with ECC. Requires ECC mode to be enabled.
+    */
+    pub fn all_memory_error_counters(&self) -> Result<MemoryErrorCounters, NvmlError> {
+        let mut counters = MemoryErrorCounters::default();
+
+        for (error_type, counts) in [
+            (MemoryError::Corrected, &mut counters.corrected),
+            (MemoryError::Uncorrected, &mut counters.uncorrected),
+        ] {
+            for (counter_type, counts) in [
+                (EccCounter::Volatile, &mut counts.volatile),
+                (EccCounter::Aggregate, &mut counts.aggregate),
+            ] {
+                for location in MemoryLocationCounts::LOCATIONS {
+                    let count = match self.memory_error_counter(error_type, counter_type, location)
+                    {
+                        Ok(count) => Some(count),
+                        Err(NvmlError::NotSupported) => None,
+                        Err(e) => return Err(e),
+                    };
+
+                    counts.set(location, count);
+                }
+            }
+        }
+
+        Ok(counters)
+    }
+
     /**
     Gets the amount of used, free and total memory available on this `Device`, in bytes.
 
@@ -2592,6 +3149,14 @@ impl<'nvml> Device<'nvml> {
     of used memory is equal to the sum of memory allocated by all active channels on
     this `Device`.
 
+    When called on a MIG device handle, the amounts reported are those of the
+    MIG device's memory slice.
+
+    Older drivers don't provide `nvmlDeviceGetMemoryInfo_v2`; on those this
+    falls back to `nvmlDeviceGetMemoryInfo`, which doesn't report reserved
+    memory. The returned `MemoryInfo` then has a `version` of 1 and a
+    `reserved` of 0.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
@@ -2602,20 +3167,55 @@ impl<'nvml> Device<'nvml> {
     // Checked against local
     // Tested
     #[doc(alias = "nvmlDeviceGetMemoryInfo")]
+    #[doc(alias = "nvmlDeviceGetMemoryInfo_v2")]
     pub fn memory_info(&self) -> Result<MemoryInfo, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetMemoryInfo_v2.as_ref())?;
+        let sym = match self.nvml.lib.nvmlDeviceGetMemoryInfo_v2.as_ref() {
+            Ok(sym) => sym,
+            Err(_) => return self.memory_info_v1(),
+        };
 
         unsafe {
             let mut info: nvmlMemory_v2_t = mem::zeroed();
 
             // Implements NVML_STRUCT_VERSION(Memory, 2), as detailed in nvml.h (https://github.com/NVIDIA/nvidia-settings/issues/78)
             info.version = (std::mem::size_of::<nvmlMemory_v2_t>() | (2_usize << 24_usize)) as u32;
+
+            match nvml_try(sym(self.device, &mut info)) {
+                Ok(()) => Ok(info.into()),
+                // Drivers that export the symbol without implementing it
+                Err(NvmlError::FunctionNotFound) => self.memory_info_v1(),
+                Err(e) => Err(e),
+            }
+        }
+    }
+
+    // Helper for the above function, for drivers without the v2 query.
+    fn memory_info_v1(&self) -> Result<MemoryInfo, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetMemoryInfo.as_ref())?;
+
+        unsafe {
+            let mut info: nvmlMemory_t = mem::zeroed();
             nvml_try(sym(self.device, &mut info))?;
 
             Ok(info.into())
         }
     }
 
+    /**
+    Gets the percentage of this `Device`'s memory that is used.
+
+    This is `MemoryInfo::used_percent()`: used memory relative to the memory
+    that can be allocated, leaving out what the driver reserves, which is how
+    nvidia-smi reports it.
+
+    # Errors
+
+    Any error returned by `Device::memory_info()`.
+    */
+    pub fn memory_usage_percent(&self) -> Result<f64, NvmlError> {
+        Ok(self.memory_info()?.used_percent())
+    }
+
     /**
     Gets the minor number for this `Device`.
 
@@ -2725,6 +3325,7 @@ impl<'nvml> Device<'nvml> {
                 placements.as_mut_ptr(),
                 &mut count,
             ))?;
+            placements.set_len((count as usize).min(placements.capacity()));
 
             Ok(placements
                 .into_iter()
@@ -2733,6 +3334,72 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Get information about the GPU instance profile `profile` (one of the
+    `NVML_GPU_INSTANCE_PROFILE_*` constants).
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` or `profile` is invalid
+    * `NotSupported`, if this `Device` does not support this profile or MIG
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetGpuInstanceProfileInfo")]
+    pub fn gpu_instance_profile_info(
+        &self,
+        profile: u32,
+    ) -> Result<GpuInstanceProfileInfo, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetGpuInstanceProfileInfo.as_ref())?;
+
+        unsafe {
+            let mut info: nvmlGpuInstanceProfileInfo_t = mem::zeroed();
+            nvml_try(sym(self.device, profile, &mut info))?;
+
+            Ok(info.into())
+        }
+    }
+
+    /**
+    Get the number of additional GPU instances of profile `profile` that can be
+    created on this `Device`, given the instances that already exist.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` or `profile` is invalid
+    * `NotSupported`, if this `Device` does not support MIG or MIG mode is disabled
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetGpuInstanceRemainingCapacity")]
+    pub fn gpu_instance_remaining_capacity(&self, profile: u32) -> Result<u32, NvmlError> {
+        let sym = nvml_sym(
+            self.nvml
+                .lib
+                .nvmlDeviceGetGpuInstanceRemainingCapacity
+                .as_ref(),
+        )?;
+
+        unsafe {
+            let mut count: c_uint = 0;
+            nvml_try(sym(self.device, profile, &mut count))?;
+
+            Ok(count)
+        }
+    }
+
     /**
     Checks if the `Device`supports multi partitioned GPU feature and if enabled.
     Not to confuse with `is_multi_gpu_board`, MIG is a single GPU
@@ -2879,32 +3546,112 @@ impl<'nvml> Device<'nvml> {
     }
 
     /**
-    The name of this `Device`, e.g. "Tesla C2070".
+    Gets the GPU instance ID of this MIG `Device`.
 
-    The name is an alphanumeric string that denotes a particular product.
+    This is the value reported in `ProcessInfo::gpu_instance_id` for processes
+    running on this MIG device.
 
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
     * `InvalidArg`, if this `Device` is invalid
-    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
-    * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    * `NotSupported`, if this `Device` is not a MIG device handle
     * `Unknown`, on any unexpected error
     */
-    // Checked against local
-    // Tested
-    #[doc(alias = "nvmlDeviceGetName")]
-    pub fn name(&self) -> Result<String, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetName.as_ref())?;
+    #[doc(alias = "nvmlDeviceGetGpuInstanceId")]
+    pub fn mig_gpu_instance_id(&self) -> Result<u32, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetGpuInstanceId.as_ref())?;
 
         unsafe {
-            let mut name_vec = vec![0; NVML_DEVICE_NAME_V2_BUFFER_SIZE as usize];
+            let mut id: c_uint = 0;
+            nvml_try(sym(self.device, &mut id))?;
 
-            nvml_try(sym(
-                self.device,
-                name_vec.as_mut_ptr(),
-                NVML_DEVICE_NAME_V2_BUFFER_SIZE,
-            ))?;
+            Ok(id)
+        }
+    }
+
+    /**
+    Gets the compute instance ID of this MIG `Device`.
+
+    This is the value reported in `ProcessInfo::compute_instance_id` for
+    processes running on this MIG device.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` is not a MIG device handle
+    * `Unknown`, on any unexpected error
+    */
+    #[doc(alias = "nvmlDeviceGetComputeInstanceId")]
+    pub fn mig_compute_instance_id(&self) -> Result<u32, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetComputeInstanceId.as_ref())?;
+
+        unsafe {
+            let mut id: c_uint = 0;
+            nvml_try(sym(self.device, &mut id))?;
+
+            Ok(id)
+        }
+    }
+
+    /**
+    Gets handles to every MIG device that currently exists on this parent
+    `Device`.
+
+    MIG device indices are sparse; indices with no MIG device behind them are
+    skipped.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support MIG
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    */
+    pub fn mig_devices(&self) -> Result<Vec<Device<'nvml>>, NvmlError> {
+        let count = self.mig_device_count()?;
+        let mut devices = Vec::with_capacity(count as usize);
+
+        for index in 0..count {
+            match self.mig_device_by_index(index) {
+                Ok(device) => devices.push(device),
+                Err(NvmlError::NotFound) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(devices)
+    }
+
+    /**
+    The name of this `Device`, e.g. "Tesla C2070".
+
+    The name is an alphanumeric string that denotes a particular product.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    * `Unknown`, on any unexpected error
+    */
+    // Checked against local
+    // Tested
+    #[doc(alias = "nvmlDeviceGetName")]
+    pub fn name(&self) -> Result<String, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetName.as_ref())?;
+
+        unsafe {
+            let mut name_vec = vec![0; NVML_DEVICE_NAME_V2_BUFFER_SIZE as usize];
+
+            nvml_try(sym(
+                self.device,
+                name_vec.as_mut_ptr(),
+                NVML_DEVICE_NAME_V2_BUFFER_SIZE,
+            ))?;
 
             let name_raw = CStr::from_ptr(name_vec.as_ptr());
             Ok(name_raw.to_str()?.into())
@@ -3277,6 +4024,9 @@ impl<'nvml> Device<'nvml> {
     /**
     Gets the list of retired pages filtered by `cause`, including pages pending retirement.
 
+    Each `RetiredPage` holds the time the page was retired alongside its
+    address, which can be used to correlate retirements with other events.
+
     **I cannot verify that this method will work because the call within is not supported
     on my dev machine**. Please **verify for yourself** that it works before you use it.
     If you are able to test it on your machine, please let me know if it works; if it
@@ -3380,6 +4130,85 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets the number of rows remapped due to correctable and uncorrectable
+    memory errors, and whether remappings are pending or have failed.
+
+    Rows that are pending remapping are included in the counts. Remappings
+    take effect after the GPU is reset.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    // Checked against local
+    // Tested (no-run)
+    #[doc(alias = "nvmlDeviceGetRemappedRows")]
+    pub fn remapped_rows(&self) -> Result<RemappedRows, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetRemappedRows.as_ref())?;
+
+        unsafe {
+            let mut correctable: c_uint = 0;
+            let mut uncorrectable: c_uint = 0;
+            let mut pending: c_uint = 0;
+            let mut failure_occurred: c_uint = 0;
+
+            nvml_try(sym(
+                self.device,
+                &mut correctable,
+                &mut uncorrectable,
+                &mut pending,
+                &mut failure_occurred,
+            ))?;
+
+            Ok(RemappedRows {
+                correctable,
+                uncorrectable,
+                pending: pending != 0,
+                failure_occurred: failure_occurred != 0,
+            })
+        }
+    }
+
+    /**
+    Gets the row remapper histogram, which counts memory banks by how many
+    spare rows they have left for remapping.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    // Checked against local
+    // Tested (no-run)
+    #[doc(alias = "nvmlDeviceGetRowRemapperHistogram")]
+    pub fn row_remapper_histogram(&self) -> Result<RowRemapperHistogram, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetRowRemapperHistogram.as_ref())?;
+
+        unsafe {
+            let mut values: nvmlRowRemapperHistogramValues_t = mem::zeroed();
+
+            nvml_try(sym(self.device, &mut values))?;
+
+            Ok(values.into())
+        }
+    }
+
     /**
     Gets recent samples for this `Device`.
 
@@ -3473,6 +4302,33 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Describe the sample buffer NVML keeps for `sample_type` on this `Device`.
+
+    The buffer depth and sampling period are derived from the samples
+    currently in the buffer, so they are only meaningful once the driver has
+    been collecting samples for a while. Use this to pick a poll interval for
+    `.samples()` that neither misses samples nor returns none.
+
+    # Errors
+
+    Same as `.samples()`, except that `NotFound` (no samples in the buffer)
+    results in an empty `SamplingInfo` instead.
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetSamples")]
+    pub fn sampling_info(&self, sample_type: Sampling) -> Result<SamplingInfo, NvmlError> {
+        let samples = match self.samples(sample_type, None) {
+            Err(NvmlError::NotFound) => vec![],
+            other => other?,
+        };
+
+        Ok(SamplingInfo::from_samples(sample_type, &samples))
+    }
+
     // Helper for the above function. Returns # of samples that can be queried.
     fn samples_count(&self, sample_type: &Sampling, timestamp: u64) -> Result<c_uint, NvmlError> {
         let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetSamples.as_ref())?;
@@ -3554,6 +4410,63 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Get values for the given slice of `FieldId`s, each paired with a scope ID.
+
+    Some fields are reported per-scope, e.g. per NvLink link for
+    `NVML_FI_DEV_NVLINK_THROUGHPUT_DATA_TX`; the scope of each requested value
+    is given along with its `FieldId`. Refer to the documentation of each field
+    ID constant in the NVML header for what its scope ID means (a scope ID of
+    `u32::MAX` often requests an aggregate across all scopes).
+
+    Otherwise behaves like `field_values_for()`, which uses a scope ID of 0.
+
+    # Errors
+
+    ## Outer `Result`
+
+    * `InvalidArg`, if `id_slice` has a length of zero
+
+    ## Inner `Result`
+
+    * `UnexpectedVariant`, check that error's docs for more info
+
+    # Device Support
+
+    Device support varies per `FieldId` that you pass in.
+    */
+    #[doc(alias = "nvmlDeviceGetFieldValues")]
+    pub fn scoped_field_values_for(
+        &self,
+        id_slice: &[(FieldId, u32)],
+    ) -> Result<Vec<Result<FieldValueSample, NvmlError>>, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetFieldValues.as_ref())?;
+
+        unsafe {
+            let values_count = id_slice.len();
+            let mut field_values: Vec<nvmlFieldValue_t> = Vec::with_capacity(values_count);
+
+            for (id, scope_id) in id_slice.iter() {
+                let mut raw: nvmlFieldValue_t = mem::zeroed();
+                raw.fieldId = id.0;
+                raw.scopeId = *scope_id;
+
+                field_values.push(raw);
+            }
+
+            nvml_try(sym(
+                self.device,
+                values_count as i32,
+                field_values.as_mut_ptr(),
+            ))?;
+
+            Ok(field_values
+                .into_iter()
+                .map(FieldValueSample::try_from)
+                .collect())
+        }
+    }
+
     /**
     Gets the globally unique board serial number associated with this `Device`'s board
     as an alphanumeric string.
@@ -3649,6 +4562,7 @@ impl<'nvml> Device<'nvml> {
     // Checked against local.
     // Tested
     #[doc(alias = "nvmlDeviceGetCurrentClocksThrottleReasons")]
+    #[deprecated(note = "use `.current_clocks_event_reasons()`.")]
     pub fn current_throttle_reasons(&self) -> Result<ThrottleReasons, NvmlError> {
         Ok(ThrottleReasons::from_bits_truncate(
             self.current_throttle_reasons_raw()?,
@@ -3676,6 +4590,7 @@ impl<'nvml> Device<'nvml> {
     */
     // Checked against local.
     // Tested
+    #[deprecated(note = "use `.current_clocks_event_reasons_strict()`.")]
     pub fn current_throttle_reasons_strict(&self) -> Result<ThrottleReasons, NvmlError> {
         let reasons = self.current_throttle_reasons_raw()?;
 
@@ -3726,6 +4641,7 @@ impl<'nvml> Device<'nvml> {
     // Checked against local
     // Tested
     #[doc(alias = "nvmlDeviceGetSupportedClocksThrottleReasons")]
+    #[deprecated(note = "use `.supported_clocks_event_reasons()`.")]
     pub fn supported_throttle_reasons(&self) -> Result<ThrottleReasons, NvmlError> {
         Ok(ThrottleReasons::from_bits_truncate(
             self.supported_throttle_reasons_raw()?,
@@ -3756,6 +4672,7 @@ impl<'nvml> Device<'nvml> {
     */
     // Checked against local
     // Tested
+    #[deprecated(note = "use `.supported_clocks_event_reasons_strict()`.")]
     pub fn supported_throttle_reasons_strict(&self) -> Result<ThrottleReasons, NvmlError> {
         let reasons = self.supported_throttle_reasons_raw()?;
 
@@ -3780,96 +4697,255 @@ impl<'nvml> Device<'nvml> {
     }
 
     /**
-    Gets a `Vec` of possible graphics clocks that can be used as an arg for
-    `set_applications_clocks()`.
+    Gets the reasons this `Device`'s clocks are currently held below their
+    maximum.
+
+    Note that multiple reasons can be affecting clocks at once.
+
+    The returned bitmask is created via the `ClocksEventReasons::from_bits_truncate`
+    method, meaning that any bits that don't correspond to flags present in this
+    version of the wrapper will be dropped.
 
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
-    * `NotFound`, if the specified `for_mem_clock` is not a supported frequency
     * `InvalidArg`, if this `Device` is invalid
-    * `NotSupported`, if this `Device` doesn't support this feature
+    * `NotSupported`, if this `Device` does not support this feature
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
     * `Unknown`, on any unexpected error
 
     # Device Support
 
-    Supports Kepler and newer fully supported devices.
+    Supports all _fully supported_ devices.
     */
     // Checked against local
     // Tested
-    #[doc(alias = "nvmlDeviceGetSupportedGraphicsClocks")]
-    pub fn supported_graphics_clocks(&self, for_mem_clock: u32) -> Result<Vec<u32>, NvmlError> {
-        match self.supported_graphics_clocks_manual(for_mem_clock, 128) {
-            Err(NvmlError::InsufficientSize(Some(s))) =>
-            // `s` is the required size for the call; make the call a second time
-            {
-                self.supported_graphics_clocks_manual(for_mem_clock, s)
-            }
-            value => value,
-        }
-    }
-
-    // Removes code duplication in the above function.
-    fn supported_graphics_clocks_manual(
-        &self,
-        for_mem_clock: u32,
-        size: usize,
-    ) -> Result<Vec<u32>, NvmlError> {
-        let mut items: Vec<c_uint> = vec![0; size];
-        let mut count = size as c_uint;
-
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetSupportedGraphicsClocks.as_ref())?;
-
-        unsafe {
-            match sym(self.device, for_mem_clock, &mut count, items.as_mut_ptr()) {
-                // `count` is now the size that is required. Return it in the error.
-                nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => {
-                    return Err(NvmlError::InsufficientSize(Some(count as usize)))
-                }
-                value => nvml_try(value)?,
-            }
-        }
-
-        items.truncate(count as usize);
-        Ok(items)
+    #[doc(alias = "nvmlDeviceGetCurrentClocksEventReasons")]
+    pub fn current_clocks_event_reasons(&self) -> Result<ClocksEventReasons, NvmlError> {
+        Ok(ClocksEventReasons::from_bits_truncate(
+            self.current_clocks_event_reasons_raw()?,
+        ))
     }
 
     /**
-    Gets a `Vec` of possible memory clocks that can be used as an arg for
-    `set_applications_clocks()`.
+    Gets the reasons this `Device`'s clocks are currently held below their
+    maximum, erroring if any bits correspond to non-present flags.
+
+    Note that multiple reasons can be affecting clocks at once.
 
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
+    * `IncorrectBits`, if NVML returns any bits that do not correspond to flags in
+      `ClocksEventReasons`
     * `InvalidArg`, if this `Device` is invalid
-    * `NotSupported`, if this `Device` doesn't support this feature
+    * `NotSupported`, if this `Device` does not support this feature
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
     * `Unknown`, on any unexpected error
 
     # Device Support
 
-    Supports Kepler and newer fully supported devices.
+    Supports all _fully supported_ devices.
     */
     // Checked against local
     // Tested
-    #[doc(alias = "nvmlDeviceGetSupportedMemoryClocks")]
-    pub fn supported_memory_clocks(&self) -> Result<Vec<u32>, NvmlError> {
-        match self.supported_memory_clocks_manual(16) {
-            Err(NvmlError::InsufficientSize(Some(s))) => {
-                // `s` is the required size for the call; make the call a second time
-                self.supported_memory_clocks_manual(s)
-            }
-            value => value,
-        }
-    }
+    pub fn current_clocks_event_reasons_strict(&self) -> Result<ClocksEventReasons, NvmlError> {
+        let reasons = self.current_clocks_event_reasons_raw()?;
 
-    // Removes code duplication in the above function.
-    fn supported_memory_clocks_manual(&self, size: usize) -> Result<Vec<u32>, NvmlError> {
-        let mut items: Vec<c_uint> = vec![0; size];
-        let mut count = size as c_uint;
+        ClocksEventReasons::from_bits(reasons).ok_or(NvmlError::IncorrectBits(Bits::U64(reasons)))
+    }
 
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetSupportedMemoryClocks.as_ref())?;
+    // Helper for the above methods.
+    fn current_clocks_event_reasons_raw(&self) -> Result<c_ulonglong, NvmlError> {
+        let sym = nvml_sym(
+            self.nvml
+                .lib
+                .nvmlDeviceGetCurrentClocksEventReasons
+                .as_ref(),
+        )?;
+
+        unsafe {
+            let mut reasons: c_ulonglong = mem::zeroed();
+
+            nvml_try(sym(self.device, &mut reasons))?;
+
+            Ok(reasons)
+        }
+    }
+
+    /**
+    Gets a bitmask of the supported clocks event reasons.
+
+    These reasons can be returned by `.current_clocks_event_reasons()`.
+
+    The returned bitmask is created via the `ClocksEventReasons::from_bits_truncate`
+    method, meaning that any bits that don't correspond to flags present in this
+    version of the wrapper will be dropped.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports all _fully supported_ devices.
+
+    # Environment Support
+
+    This method is not supported on virtual machines running vGPUs.
+    */
+    // Checked against local
+    // Tested
+    #[doc(alias = "nvmlDeviceGetSupportedClocksEventReasons")]
+    pub fn supported_clocks_event_reasons(&self) -> Result<ClocksEventReasons, NvmlError> {
+        Ok(ClocksEventReasons::from_bits_truncate(
+            self.supported_clocks_event_reasons_raw()?,
+        ))
+    }
+
+    /**
+    Gets a bitmask of the supported clocks event reasons, erroring if any bits
+    correspond to non-present flags.
+
+    These reasons can be returned by `.current_clocks_event_reasons()`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `IncorrectBits`, if NVML returns any bits that do not correspond to flags in
+      `ClocksEventReasons`
+    * `InvalidArg`, if this `Device` is invalid
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports all _fully supported_ devices.
+
+    # Environment Support
+
+    This method is not supported on virtual machines running vGPUs.
+    */
+    // Checked against local
+    // Tested
+    pub fn supported_clocks_event_reasons_strict(&self) -> Result<ClocksEventReasons, NvmlError> {
+        let reasons = self.supported_clocks_event_reasons_raw()?;
+
+        ClocksEventReasons::from_bits(reasons).ok_or(NvmlError::IncorrectBits(Bits::U64(reasons)))
+    }
+
+    // Helper for the above methods.
+    fn supported_clocks_event_reasons_raw(&self) -> Result<c_ulonglong, NvmlError> {
+        let sym = nvml_sym(
+            self.nvml
+                .lib
+                .nvmlDeviceGetSupportedClocksEventReasons
+                .as_ref(),
+        )?;
+
+        unsafe {
+            let mut reasons: c_ulonglong = mem::zeroed();
+
+            nvml_try(sym(self.device, &mut reasons))?;
+
+            Ok(reasons)
+        }
+    }
+
+    /**
+    Gets a `Vec` of possible graphics clocks that can be used as an arg for
+    `set_applications_clocks()`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `NotFound`, if the specified `for_mem_clock` is not a supported frequency
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    // Checked against local
+    // Tested
+    #[doc(alias = "nvmlDeviceGetSupportedGraphicsClocks")]
+    pub fn supported_graphics_clocks(&self, for_mem_clock: u32) -> Result<Vec<u32>, NvmlError> {
+        match self.supported_graphics_clocks_manual(for_mem_clock, 128) {
+            Err(NvmlError::InsufficientSize(Some(s))) =>
+            // `s` is the required size for the call; make the call a second time
+            {
+                self.supported_graphics_clocks_manual(for_mem_clock, s)
+            }
+            value => value,
+        }
+    }
+
+    // Removes code duplication in the above function.
+    fn supported_graphics_clocks_manual(
+        &self,
+        for_mem_clock: u32,
+        size: usize,
+    ) -> Result<Vec<u32>, NvmlError> {
+        let mut items: Vec<c_uint> = vec![0; size];
+        let mut count = size as c_uint;
+
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetSupportedGraphicsClocks.as_ref())?;
+
+        unsafe {
+            match sym(self.device, for_mem_clock, &mut count, items.as_mut_ptr()) {
+                // `count` is now the size that is required. Return it in the error.
+                nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => {
+                    return Err(NvmlError::InsufficientSize(Some(count as usize)))
+                }
+                value => nvml_try(value)?,
+            }
+        }
+
+        items.truncate(count as usize);
+        Ok(items)
+    }
+
+    /**
+    Gets a `Vec` of possible memory clocks that can be used as an arg for
+    `set_applications_clocks()`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    // Checked against local
+    // Tested
+    #[doc(alias = "nvmlDeviceGetSupportedMemoryClocks")]
+    pub fn supported_memory_clocks(&self) -> Result<Vec<u32>, NvmlError> {
+        match self.supported_memory_clocks_manual(16) {
+            Err(NvmlError::InsufficientSize(Some(s))) => {
+                // `s` is the required size for the call; make the call a second time
+                self.supported_memory_clocks_manual(s)
+            }
+            value => value,
+        }
+    }
+
+    // Removes code duplication in the above function.
+    fn supported_memory_clocks_manual(&self, size: usize) -> Result<Vec<u32>, NvmlError> {
+        let mut items: Vec<c_uint> = vec![0; size];
+        let mut count = size as c_uint;
+
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetSupportedMemoryClocks.as_ref())?;
 
         unsafe {
             match sym(self.device, &mut count, items.as_mut_ptr()) {
@@ -3911,6 +4987,116 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets the current temperature reading for the given sensor, in °C, through
+    the versioned temperature API.
+
+    Unlike `.temperature()`, the reading is signed.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid or `sensor` is invalid (shouldn't occur?)
+    * `NotSupported`, if this `Device` does not have the specified sensor
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for (this includes
+      `NVML_ERROR_ARGUMENT_VERSION_MISMATCH`, if the struct version is not
+      supported by the driver)
+    * `Unknown`, on any unexpected error
+    */
+    // Checked against local
+    // Tested
+    #[doc(alias = "nvmlDeviceGetTemperatureV")]
+    pub fn temperature_v(&self, sensor: TemperatureSensor) -> Result<i32, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetTemperatureV.as_ref())?;
+
+        unsafe {
+            let mut temp: nvmlTemperature_t = mem::zeroed();
+            // Implements NVML_STRUCT_VERSION(Temperature, 1), as detailed in nvml.h
+            temp.version = (mem::size_of::<nvmlTemperature_v1_t>() | (1_usize << 24_usize)) as u32;
+            temp.sensorType = sensor.as_c();
+
+            nvml_try(sym(self.device, &mut temp))?;
+
+            Ok(temp.temperature)
+        }
+    }
+
+    /**
+    Gets how far this `Device` is from its slowdown temperature, in °C.
+
+    The margin is negative once the slowdown threshold has been passed.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for (this includes
+      `NVML_ERROR_ARGUMENT_VERSION_MISMATCH`, if the struct version is not
+      supported by the driver)
+    * `Unknown`, on any unexpected error
+    */
+    // Checked against local
+    // Tested
+    #[doc(alias = "nvmlDeviceGetMarginTemperature")]
+    pub fn margin_temperature(&self) -> Result<i32, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetMarginTemperature.as_ref())?;
+
+        unsafe {
+            let mut margin: nvmlMarginTemperature_t = mem::zeroed();
+            // Implements NVML_STRUCT_VERSION(MarginTemperature, 1), as detailed in nvml.h
+            margin.version =
+                (mem::size_of::<nvmlMarginTemperature_v1_t>() | (1_usize << 24_usize)) as u32;
+
+            nvml_try(sym(self.device, &mut margin))?;
+
+            Ok(margin.marginTemperature)
+        }
+    }
+
+    /**
+    Gets the thermal sensors of this `Device` along with their current and
+    default temperatures.
+
+    Pass `None` as `sensor_index` to get every sensor of this `Device`, or
+    the index of a single sensor. Boards have up to
+    `NVML_MAX_THERMAL_SENSORS_PER_GPU` sensors.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` or `sensor_index` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+    */
+    // Checked against local
+    // Tested
+    #[doc(alias = "nvmlDeviceGetThermalSettings")]
+    pub fn thermal_settings(
+        &self,
+        sensor_index: Option<u32>,
+    ) -> Result<Vec<ThermalSensor>, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetThermalSettings.as_ref())?;
+        let sensor_index = sensor_index.unwrap_or(ThermalTarget::All.as_c() as u32);
+
+        unsafe {
+            let mut settings: nvmlGpuThermalSettings_t = mem::zeroed();
+
+            nvml_try(sym(self.device, sensor_index, &mut settings))?;
+
+            let count = (settings.count as usize).min(settings.sensor.len());
+
+            settings.sensor[..count]
+                .iter()
+                .map(|&sensor| ThermalSensor::try_from(sensor))
+                .collect()
+        }
+    }
+
     /**
     Gets the temperature threshold for this `Device` and the specified `threshold_type`, in °C.
 
@@ -3948,10 +5134,15 @@ impl<'nvml> Device<'nvml> {
     Set the temperature threshold for this `Device` and the specified `threshold_type` and
     with the given temperature.
 
+    Setting `TemperatureThreshold::AcousticCurr` checks `temp` against the
+    `AcousticMin` and `AcousticMax` thresholds before it is applied, if they
+    can be read.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
-    * `InvalidArg`, if this `Device` is invalid or `threshold_type` is invalid (shouldn't occur?)
+    * `InvalidArg`, if this `Device` is invalid, `threshold_type` is invalid (shouldn't occur?)
+      or `temp` is outside of the acoustic range, if that range can be read
     * `NotSupported`, if this `Device` does not have a temperature sensor or is unsupported
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
     * `Unknown`, on any unexpected error
@@ -3968,6 +5159,19 @@ impl<'nvml> Device<'nvml> {
         threshold_type: TemperatureThreshold,
         temp: i32,
     ) -> Result<(), NvmlError> {
+        if threshold_type == TemperatureThreshold::AcousticCurr {
+            if let (Ok(min), Ok(max)) = (
+                self.temperature_threshold(TemperatureThreshold::AcousticMin),
+                self.temperature_threshold(TemperatureThreshold::AcousticMax),
+            ) {
+                let temp = i64::from(temp);
+
+                if temp < i64::from(min) || temp > i64::from(max) {
+                    return Err(NvmlError::InvalidArg);
+                }
+            }
+        }
+
         let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetTemperatureThreshold.as_ref())?;
 
         unsafe {
@@ -4181,6 +5385,9 @@ impl<'nvml> Device<'nvml> {
     and memory utilization readings. This is caused by the ECC memory scrubbing
     mechanism that is performed during driver initialization.
 
+    MIG device handles do not support this call; it will return `NotSupported`
+    for them.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
@@ -4331,6 +5538,9 @@ impl<'nvml> Device<'nvml> {
     /**
     Gets the status for a given p2p capability index between this [`Device`] and another given [`Device`].
 
+    Use this to check whether e.g. peer reads over NvLink are possible before
+    enabling peer access in CUDA.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
@@ -4349,7 +5559,7 @@ impl<'nvml> Device<'nvml> {
             let mut status: nvmlGpuP2PStatus_t = mem::zeroed();
             let device2 = device2.device;
 
-            nvml_try(sym(self.device, device2, p2p_index as u32, &mut status))?;
+            nvml_try(sym(self.device, device2, p2p_index.as_c(), &mut status))?;
 
             status
         };
@@ -4522,6 +5732,8 @@ impl<'nvml> Device<'nvml> {
     /**
     Checks if this `Device` and the passed-in device are on the same physical board.
 
+    This detects dual-GPU boards, e.g. when pairing GPUs for NvLink bridges.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
@@ -4533,6 +5745,7 @@ impl<'nvml> Device<'nvml> {
     // Checked against local
     // Tested
     #[doc(alias = "nvmlDeviceOnSameBoard")]
+    #[doc(alias = "on_same_board")]
     pub fn is_on_same_board_as(&self, other_device: &Device) -> Result<bool, NvmlError> {
         let sym = nvml_sym(self.nvml.lib.nvmlDeviceOnSameBoard.as_ref())?;
 
@@ -4550,24 +5763,119 @@ impl<'nvml> Device<'nvml> {
     }
 
     /**
-    Resets the application clock to the default value.
-
-    This is the applications clock that will be used after a system reboot or a driver
-    reload. The default value is a constant, but the current value be changed with
-    `.set_applications_clocks()`.
+    Compares the static properties of this `Device` (architecture, memory size,
+    maximum clocks, active NvLink links and VBIOS version) with those of
+    `other`.
 
-    On Pascal and newer hardware, if clocks were previously locked with
-    `.set_applications_clocks()`, this call will unlock clocks. This returns clocks
-    to their default behavior of automatically boosting above base clocks as
-    thermal limits allow.
+    Useful for detecting nodes whose GPUs were expected to be identical but
+    are not. Properties a device does not support are compared as absent.
 
     # Errors
 
-    * `Uninitialized`, if the library has not been successfully initialized
-    * `InvalidArg`, if the `Device` is invalid
-    * `NotSupported`, if this `Device` does not support this feature
-    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
-    * `Unknown`, on any unexpected error
+    Any error other than `NotSupported` returned while reading either device's
+    properties; see `DeviceProperties::of()`.
+    */
+    pub fn compare(&self, other: &Device) -> Result<DeviceComparison, NvmlError> {
+        Ok(DeviceComparison::new(
+            DeviceProperties::of(self)?,
+            DeviceProperties::of(other)?,
+        ))
+    }
+
+    /**
+    Probe a fixed list of read-only calls on this `Device` and record whether
+    each is supported.
+
+    The result is keyed by `Device` method name and can be attached to bug
+    reports or used as a capability cache; see `high_level::support`.
+
+    Probing makes a few dozen NVML calls, so avoid doing it in hot paths.
+    */
+    pub fn support_matrix(&self) -> SupportMatrix {
+        SupportMatrix::probe(self)
+    }
+
+    /**
+    Read the instantaneous and averaged GPU power and the module power of
+    this `Device` in one request; see `high_level::power`.
+
+    # Errors
+
+    Any error returned by `PowerReadings::read()`.
+    */
+    pub fn power_readings(&self) -> Result<PowerReadings, NvmlError> {
+        PowerReadings::read(self)
+    }
+
+    /**
+    Run the usual health checks on this `Device` (ECC errors, retired pages,
+    remapped rows, clocks event reasons, temperature and fans) and summarize
+    them; see `high_level::health`.
+
+    # Errors
+
+    Any error returned by `HealthReport::read()`.
+    */
+    pub fn health_report(&self) -> Result<HealthReport, NvmlError> {
+        HealthReport::read(self)
+    }
+
+    /**
+    Like `.health_report()`, but also including the critical XID errors
+    waiting in `events`.
+
+    # Errors
+
+    Any error returned by `HealthReport::read_with_events()`.
+
+    # Platform Support
+
+    Only supports Linux.
+    */
+    #[cfg(target_os = "linux")]
+    pub fn health_report_with_events(&self, events: &EventSet) -> Result<HealthReport, NvmlError> {
+        HealthReport::read_with_events(self, events)
+    }
+
+    /**
+    Gather the evidence a remote verifier needs to attest this `Device`,
+    requesting the attestation report with `nonce`; see
+    `high_level::attestation`.
+
+    # Errors
+
+    Any error returned by `AttestationEvidence::collect()`.
+
+    # Device Support
+
+    Supports Hopper and newer fully supported devices.
+    */
+    pub fn attestation_evidence(
+        &self,
+        nonce: [u8; NVML_CC_GPU_CEC_NONCE_SIZE as usize],
+    ) -> Result<AttestationEvidence, NvmlError> {
+        AttestationEvidence::collect(self, nonce)
+    }
+
+    /**
+    Resets the application clock to the default value.
+
+    This is the applications clock that will be used after a system reboot or a driver
+    reload. The default value is a constant, but the current value be changed with
+    `.set_applications_clocks()`.
+
+    On Pascal and newer hardware, if clocks were previously locked with
+    `.set_applications_clocks()`, this call will unlock clocks. This returns clocks
+    to their default behavior of automatically boosting above base clocks as
+    thermal limits allow.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
 
     # Device Support
 
@@ -5134,6 +6442,23 @@ impl<'nvml> Device<'nvml> {
         unsafe { nvml_try(sym(self.device, mem_clock, graphics_clock)) }
     }
 
+    /**
+    Set this `Device`'s applications clocks until the returned guard is
+    dropped, when the previous applications clocks are restored; see
+    `high_level::clocks`.
+
+    # Errors
+
+    Any error returned by `ApplicationClocksGuard::apply()`.
+    */
+    pub fn set_applications_clocks_scoped(
+        &mut self,
+        mem_clock: u32,
+        graphics_clock: u32,
+    ) -> Result<ApplicationClocksGuard<'_, 'nvml>, NvmlError> {
+        ApplicationClocksGuard::apply(self, mem_clock, graphics_clock)
+    }
+
     /**
     Sets the compute mode for this `Device`.
 
@@ -5354,6 +6679,23 @@ impl<'nvml> Device<'nvml> {
         unsafe { nvml_try(sym(self.device)) }
     }
 
+    /**
+    Lock this [`Device`]'s GPU clocks to the range from `min_clock_mhz` to
+    `max_clock_mhz` until the returned guard is dropped; see
+    `high_level::clocks`.
+
+    # Errors
+
+    Any error returned by `LockedClocksGuard::lock()`.
+    */
+    pub fn lock_clocks_scoped(
+        &mut self,
+        min_clock_mhz: u32,
+        max_clock_mhz: u32,
+    ) -> Result<LockedClocksGuard<'_, 'nvml>, NvmlError> {
+        LockedClocksGuard::lock(self, min_clock_mhz, max_clock_mhz)
+    }
+
     /**
     Set whether or not ECC mode is enabled for this `Device`.
 
@@ -5384,6 +6726,22 @@ impl<'nvml> Device<'nvml> {
         unsafe { nvml_try(sym(self.device, state_from_bool(enabled))) }
     }
 
+    /**
+    Set whether or not ECC mode is enabled for this `Device` until the
+    returned guard is dropped, when the previous pending mode is restored;
+    see `high_level::ecc`.
+
+    Like `.set_ecc()`, the change only takes effect after the next reboot
+    or GPU reset. Dropping the guard before that cancels it.
+
+    # Errors
+
+    Any error returned by `EccModeGuard::set()`.
+    */
+    pub fn set_ecc_scoped(&mut self, enabled: bool) -> Result<EccModeGuard<'_, 'nvml>, NvmlError> {
+        EccModeGuard::set(self, enabled)
+    }
+
     /**
     Sets the GPU operation mode for this `Device`.
 
@@ -5492,203 +6850,549 @@ impl<'nvml> Device<'nvml> {
     }
 
     /**
-    Retrieve min, max and current clock offset of some clock domain for a given PState
+    Sets the power limit for the given scope of this `Device`, in milliwatts.
+
+    With `PowerScope::Gpu` this is the same as `.set_power_management_limit()`.
+    `PowerScope::Module` caps the whole module instead (e.g. the GPU and CPU of
+    a Grace Hopper superchip), separately from the GPU-only limit.
+
+    This limit is not persistent across reboots or driver unloads. Enable
+    persistent mode to prevent the driver from unloading when no application
+    is using this `Device`.
+
+    Requires root/admin permissions. See `.power_management_limit_constraints()`
+    to check the allowed range of values.
 
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
-    * `InvalidArg`,  If device, type or pstate are invalid or both minClockOffsetMHz and maxClockOffsetMHz are NULL
-    * `ArgumentVersionMismatch`, if the provided version is invalid/unsupported
+    * `InvalidArg`, if the `Device` is invalid, or `scope` or `limit` is
+      invalid
     * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
 
     # Device Support
 
-    Supports Maxwell and newer fully supported devices.
+    Supports Kepler and newer fully supported devices. Scopes other than
+    `PowerScope::Gpu` are only supported by devices that are part of a
+    module.
     */
-    // Checked against local
-    // Tested
-    #[doc(alias = "nvmlDeviceGetClockOffsets")]
-    pub fn clock_offset(
-        &self,
-        clock_type: Clock,
-        power_state: PerformanceState,
-    ) -> Result<ClockOffset, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetClockOffsets.as_ref())?;
+    #[doc(alias = "nvmlDeviceSetPowerManagementLimit_v2")]
+    pub fn set_power_management_limit_v2(
+        &mut self,
+        scope: PowerScope,
+        limit: u32,
+    ) -> Result<(), NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetPowerManagementLimit_v2.as_ref())?;
 
         unsafe {
-            // Implements NVML_STRUCT_VERSION(ClockOffset, 1), as detailed in nvml.h
-            let version =
-                (std::mem::size_of::<nvmlClockOffset_v1_t>() | (1_usize << 24_usize)) as u32;
-
-            let mut clock_offset = nvmlClockOffset_v1_t {
-                version,
-                type_: clock_type.as_c(),
-                pstate: power_state.as_c(),
-                clockOffsetMHz: mem::zeroed(),
-                minClockOffsetMHz: mem::zeroed(),
-                maxClockOffsetMHz: mem::zeroed(),
+            let mut value = nvmlPowerValue_v2_t {
+                // Implements NVML_STRUCT_VERSION(PowerValue, 2), as detailed in nvml.h
+                version: (mem::size_of::<nvmlPowerValue_v2_t>() | (2_usize << 24_usize)) as u32,
+                powerScope: scope.as_c(),
+                powerValueMw: limit,
             };
-            nvml_try(sym(self.device, &mut clock_offset))?;
-            ClockOffset::try_from(clock_offset)
+
+            nvml_try(sym(self.device, &mut value))
         }
     }
 
     /**
-    Control current clock offset of some clock domain for a given PState
+    Activates one of the preset power smoothing profiles of this `Device`.
+
+    Power smoothing limits how fast the power draw of the GPU changes, to
+    spare the datacenter power infrastructure from sudden swings.
 
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` or `profile_id` is invalid
     * `NoPermission`, if the user doesn't have permission to perform this operation
-    * `InvalidArg`,  If device, type or pstate are invalid or both clockOffsetMHz is out of allowed range
-    * `ArgumentVersionMismatch`, if the provided version is invalid/unsupported
+    * `NotSupported`, if this `Device` does not support this feature
+    * `Unknown`, on any unexpected error
 
     # Device Support
 
-    Supports Maxwell and newer fully supported devices.
+    Supports Blackwell and newer fully supported devices.
     */
-    // Checked against local
-    // Tested (no-run)
-    #[doc(alias = "nvmlDeviceSetClockOffsets")]
-    pub fn set_clock_offset(
+    #[doc(alias = "nvmlDevicePowerSmoothingActivatePresetProfile")]
+    pub fn activate_power_smoothing_preset_profile(
         &mut self,
-        clock_type: Clock,
-        power_state: PerformanceState,
-        offset: i32,
+        profile_id: u32,
     ) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetClockOffsets.as_ref())?;
-
-        unsafe {
-            // Implements NVML_STRUCT_VERSION(ClockOffset, 1), as detailed in nvml.h
-            let version =
-                (std::mem::size_of::<nvmlClockOffset_v1_t>() | (1_usize << 24_usize)) as u32;
+        let sym = nvml_sym(
+            self.nvml
+                .lib
+                .nvmlDevicePowerSmoothingActivatePresetProfile
+                .as_ref(),
+        )?;
 
-            let mut clock_offset = nvmlClockOffset_v1_t {
-                version,
-                type_: clock_type.as_c(),
-                pstate: power_state.as_c(),
-                clockOffsetMHz: offset,
-                minClockOffsetMHz: 0,
-                maxClockOffsetMHz: 0,
-            };
-            nvml_try(sym(self.device, &mut clock_offset))?;
-            Ok(())
+        // Only the profile ID is read
+        let mut profile = PowerSmoothingProfile {
+            profile_id,
+            param: PowerSmoothingProfileParam::PercentTmpFloor,
+            value: 0.0,
         }
+        .as_c();
+
+        unsafe { nvml_try(sym(self.device, &mut profile)) }
     }
 
     /**
-    Get all supported Performance States (P-States) for the device.
-    The number of elements in the returned list will never exceed [`NVML_MAX_GPU_PERF_PSTATES`]`.
+    Updates the value of a parameter of one of the preset power smoothing
+    profiles of this `Device`.
 
     # Errors
 
-    * `InsufficientSize`, if the the container supplied was not large enough to hold the resulting list
     * `Uninitialized`, if the library has not been successfully initialized
-    * `InvalidArg`,  if device or pstates is invalid
-    * `NotSupported`, if the device does not support performance state readings
+    * `InvalidArg`, if this `Device` is invalid, or the profile, parameter or
+      value in `profile` is invalid
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `NotSupported`, if this `Device` does not support this feature
     * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Blackwell and newer fully supported devices.
     */
-    // Checked against local
-    // Tested
-    #[doc(alias = "nvmlDeviceGetSupportedPerformanceStates")]
-    pub fn supported_performance_states(&self) -> Result<Vec<PerformanceState>, NvmlError> {
+    #[doc(alias = "nvmlDevicePowerSmoothingUpdatePresetProfileParam")]
+    pub fn update_power_smoothing_preset_profile_param(
+        &mut self,
+        profile: &PowerSmoothingProfile,
+    ) -> Result<(), NvmlError> {
         let sym = nvml_sym(
             self.nvml
                 .lib
-                .nvmlDeviceGetSupportedPerformanceStates
+                .nvmlDevicePowerSmoothingUpdatePresetProfileParam
                 .as_ref(),
         )?;
 
-        unsafe {
-            let mut pstates =
-                [PerformanceState::Unknown.as_c(); NVML_MAX_GPU_PERF_PSTATES as usize];
-            // The array size passed to `nvmlDeviceGetSupportedPerformanceStates` must be in bytes, not array length
-            let byte_size = mem::size_of_val(&pstates);
-
-            nvml_try(sym(self.device, pstates.as_mut_ptr(), byte_size as u32))?;
-
-            pstates
-                .into_iter()
-                .take_while(|pstate| *pstate != PerformanceState::Unknown.as_c())
-                .map(PerformanceState::try_from)
-                .collect()
-        }
+        unsafe { nvml_try(sym(self.device, &mut profile.as_c())) }
     }
 
     /**
-    Retrieve min and max clocks of some clock domain for a given PState.
-
-    Returns a (min, max) tuple.
+    Enables or disables power smoothing on this `Device`.
 
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
-    * `InvalidArg`, if device, type or pstate are invalid or both minClockMHz and maxClockMHz are NULL
-    * `NotSupported`, if the device does not support this feature
+    * `InvalidArg`, if this `Device` is invalid
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `NotSupported`, if this `Device` does not support this feature
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Blackwell and newer fully supported devices.
     */
-    // Checked against local
-    // Tested
-    #[doc(alias = "nvmlDeviceGetMinMaxClockOfPState")]
-    pub fn min_max_clock_of_pstate(
-        &self,
-        clock_type: Clock,
-        pstate: PerformanceState,
-    ) -> Result<(u32, u32), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetMinMaxClockOfPState.as_ref())?;
+    #[doc(alias = "nvmlDevicePowerSmoothingSetState")]
+    pub fn set_power_smoothing_state(&mut self, enabled: bool) -> Result<(), NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDevicePowerSmoothingSetState.as_ref())?;
 
         unsafe {
-            let mut min: u32 = mem::zeroed();
-            let mut max: u32 = mem::zeroed();
-
-            nvml_try(sym(
-                self.device,
-                clock_type.as_c(),
-                pstate.as_c(),
-                &mut min,
-                &mut max,
-            ))?;
+            let mut state = nvmlPowerSmoothingState_t {
+                // Implements NVML_STRUCT_VERSION(PowerSmoothingState, 1), as detailed in nvml.h
+                version: (mem::size_of::<nvmlPowerSmoothingState_v1_t>() | (1_usize << 24_usize))
+                    as u32,
+                state: state_from_bool(enabled),
+            };
 
-            Ok((min, max))
+            nvml_try(sym(self.device, &mut state))
         }
     }
 
-    // Event handling methods
-
     /**
-    Starts recording the given `EventTypes` for this `Device` and adding them
-    to the specified `EventSet`.
-
-    Use `.supported_event_types()` to find out which events you can register for
-    this `Device`.
-
-    **Unfortunately, due to the way `error-chain` works, there is no way to
-    return the set if it is still valid after an error has occured with the
-    register call.** The set that you passed in will be freed if any error
-    occurs and will not be returned to you. This is not desired behavior
-    and I will fix it as soon as it is possible to do so.
-
-    All events that occurred before this call was made will not be recorded.
+    Gets the workload power profiles this `Device` supports.
 
-    ECC events are only available on `Device`s with ECC enabled. Power capping events
-    are only available on `Device`s with power management enabled.
+    Profiles tune the power and clocks of the GPU for a kind of workload;
+    see `.set_requested_workload_power_profiles()`.
 
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
-    * `InvalidArg`, if `events` is invalid (shouldn't occur?)
-    * `NotSupported`, if the platform does not support this feature or some of the
-      requested event types.
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
-    * `Unknown`, on any unexpected error. **If this error is returned, the `set` you
-      passed in has had its resources freed and will not be returned to you**. NVIDIA's
-      docs say that this error means that the set is in an invalid state.
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
 
     # Device Support
 
-    Supports Fermi and newer fully supported devices.
-
-    # Platform Support
-
+    Supports Blackwell and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceWorkloadPowerProfileGetProfilesInfo")]
+    pub fn workload_power_profiles(&self) -> Result<Vec<WorkloadPowerProfileInfo>, NvmlError> {
+        let sym = nvml_sym(
+            self.nvml
+                .lib
+                .nvmlDeviceWorkloadPowerProfileGetProfilesInfo
+                .as_ref(),
+        )?;
+
+        unsafe {
+            let mut info: nvmlWorkloadPowerProfileProfilesInfo_t = mem::zeroed();
+            // Implements NVML_STRUCT_VERSION(WorkloadPowerProfileProfilesInfo, 1), as detailed in nvml.h
+            info.version = (mem::size_of::<nvmlWorkloadPowerProfileProfilesInfo_v1_t>()
+                | (1_usize << 24_usize)) as u32;
+
+            nvml_try(sym(self.device, &mut info))?;
+
+            let supported = PowerProfileMask::from(info.perfProfilesMask);
+            let mut profiles: Vec<WorkloadPowerProfileInfo> = vec![];
+
+            // Keep one entry per supported profile, whether the driver lays
+            // the entries out by profile ID or one after the other
+            for entry in info.perfProfile.iter() {
+                if supported.contains_id(entry.profileId)
+                    && !profiles.iter().any(|p| p.profile_id == entry.profileId)
+                {
+                    profiles.push((*entry).into());
+                }
+            }
+
+            Ok(profiles)
+        }
+    }
+
+    /**
+    Gets the workload power profiles this `Device` supports, those that have
+    been requested and those currently in effect.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Blackwell and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceWorkloadPowerProfileGetCurrentProfiles")]
+    pub fn current_workload_power_profiles(
+        &self,
+    ) -> Result<WorkloadPowerProfileCurrentProfiles, NvmlError> {
+        let sym = nvml_sym(
+            self.nvml
+                .lib
+                .nvmlDeviceWorkloadPowerProfileGetCurrentProfiles
+                .as_ref(),
+        )?;
+
+        unsafe {
+            let mut profiles: nvmlWorkloadPowerProfileCurrentProfiles_t = mem::zeroed();
+            // Implements NVML_STRUCT_VERSION(WorkloadPowerProfileCurrentProfiles, 1), as detailed in nvml.h
+            profiles.version = (mem::size_of::<nvmlWorkloadPowerProfileCurrentProfiles_v1_t>()
+                | (1_usize << 24_usize)) as u32;
+
+            nvml_try(sym(self.device, &mut profiles))?;
+
+            Ok(profiles.into())
+        }
+    }
+
+    /**
+    Requests the given workload power profiles for this `Device`, in addition
+    to those already requested.
+
+    Only profiles this `Device` supports may be requested (see
+    `.workload_power_profiles()`), and not together with profiles they
+    conflict with.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Blackwell and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceWorkloadPowerProfileSetRequestedProfiles")]
+    pub fn set_requested_workload_power_profiles(
+        &mut self,
+        profiles: &PowerProfileMask,
+    ) -> Result<(), NvmlError> {
+        let sym = nvml_sym(
+            self.nvml
+                .lib
+                .nvmlDeviceWorkloadPowerProfileSetRequestedProfiles
+                .as_ref(),
+        )?;
+
+        unsafe { nvml_try(sym(self.device, &mut requested_profiles(profiles))) }
+    }
+
+    /**
+    Withdraws the request for the given workload power profiles on this
+    `Device`. Other requested profiles stay requested.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Blackwell and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceWorkloadPowerProfileClearRequestedProfiles")]
+    pub fn clear_requested_workload_power_profiles(
+        &mut self,
+        profiles: &PowerProfileMask,
+    ) -> Result<(), NvmlError> {
+        let sym = nvml_sym(
+            self.nvml
+                .lib
+                .nvmlDeviceWorkloadPowerProfileClearRequestedProfiles
+                .as_ref(),
+        )?;
+
+        unsafe { nvml_try(sym(self.device, &mut requested_profiles(profiles))) }
+    }
+
+    /**
+    Retrieve min, max and current clock offset of some clock domain for a given PState
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device`, `clock_type` or `power_state` are invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for (this includes
+      `NVML_ERROR_ARGUMENT_VERSION_MISMATCH`, if the struct version is not
+      supported by the driver)
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell and newer fully supported devices.
+    */
+    // Checked against local
+    // Tested
+    #[doc(alias = "nvmlDeviceGetClockOffsets")]
+    pub fn clock_offset(
+        &self,
+        clock_type: Clock,
+        power_state: PerformanceState,
+    ) -> Result<ClockOffset, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetClockOffsets.as_ref())?;
+
+        unsafe {
+            // Implements NVML_STRUCT_VERSION(ClockOffset, 1), as detailed in nvml.h
+            let version =
+                (std::mem::size_of::<nvmlClockOffset_v1_t>() | (1_usize << 24_usize)) as u32;
+
+            let mut clock_offset = nvmlClockOffset_v1_t {
+                version,
+                type_: clock_type.as_c(),
+                pstate: power_state.as_c(),
+                clockOffsetMHz: mem::zeroed(),
+                minClockOffsetMHz: mem::zeroed(),
+                maxClockOffsetMHz: mem::zeroed(),
+            };
+            nvml_try(sym(self.device, &mut clock_offset))?;
+            ClockOffset::try_from(clock_offset)
+        }
+    }
+
+    /**
+    Control current clock offset of some clock domain for a given PState
+
+    This is a shorthand for `.set_clock_offsets()`.
+
+    # Errors
+
+    Any error returned by `.set_clock_offsets()`.
+
+    # Device Support
+
+    Supports Maxwell and newer fully supported devices.
+    */
+    // Checked against local
+    // Tested (no-run)
+    pub fn set_clock_offset(
+        &mut self,
+        clock_type: Clock,
+        power_state: PerformanceState,
+        offset: i32,
+    ) -> Result<(), NvmlError> {
+        self.set_clock_offsets(&ClockOffset {
+            // Implements NVML_STRUCT_VERSION(ClockOffset, 1), as detailed in nvml.h
+            version: (mem::size_of::<nvmlClockOffset_v1_t>() | (1_usize << 24_usize)) as u32,
+            clock_type,
+            state: power_state,
+            clock_offset_mhz: offset,
+            min_clock_offset_mhz: 0,
+            max_clock_offset_mhz: 0,
+        })
+    }
+
+    /**
+    Apply `offset.clock_offset_mhz` to the clock domain and PState given by
+    `offset`.
+
+    A `ClockOffset` returned by `.clock_offset()` can be modified and passed
+    back in. Its min and max are ignored by NVML; check the new offset against
+    them beforehand to avoid an `InvalidArg` error.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `InvalidArg`, if this `Device`, the clock type or the PState are invalid, or
+      the offset is out of the allowed range
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for (this includes
+      `NVML_ERROR_ARGUMENT_VERSION_MISMATCH`, if the struct version is not
+      supported by the driver)
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell and newer fully supported devices.
+    */
+    // Checked against local
+    // Tested (no-run)
+    #[doc(alias = "nvmlDeviceSetClockOffsets")]
+    pub fn set_clock_offsets(&mut self, offset: &ClockOffset) -> Result<(), NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetClockOffsets.as_ref())?;
+
+        unsafe { nvml_try(sym(self.device, &mut offset.as_c())) }
+    }
+
+    /**
+    Get all supported Performance States (P-States) for the device.
+    The number of elements in the returned list will never exceed [`NVML_MAX_GPU_PERF_PSTATES`]`.
+
+    # Errors
+
+    * `InsufficientSize`, if the the container supplied was not large enough to hold the resulting list
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`,  if device or pstates is invalid
+    * `NotSupported`, if the device does not support performance state readings
+    * `Unknown`, on any unexpected error
+    */
+    // Checked against local
+    // Tested
+    #[doc(alias = "nvmlDeviceGetSupportedPerformanceStates")]
+    pub fn supported_performance_states(&self) -> Result<Vec<PerformanceState>, NvmlError> {
+        let sym = nvml_sym(
+            self.nvml
+                .lib
+                .nvmlDeviceGetSupportedPerformanceStates
+                .as_ref(),
+        )?;
+
+        unsafe {
+            let mut pstates =
+                [PerformanceState::Unknown.as_c(); NVML_MAX_GPU_PERF_PSTATES as usize];
+            // The array size passed to `nvmlDeviceGetSupportedPerformanceStates` must be in bytes, not array length
+            let byte_size = mem::size_of_val(&pstates);
+
+            nvml_try(sym(self.device, pstates.as_mut_ptr(), byte_size as u32))?;
+
+            pstates
+                .into_iter()
+                .take_while(|pstate| *pstate != PerformanceState::Unknown.as_c())
+                .map(PerformanceState::try_from)
+                .collect()
+        }
+    }
+
+    /**
+    Retrieve min and max clocks of some clock domain for a given PState.
+
+    Returns a (min, max) tuple, in MHz. Use `.supported_performance_states()`
+    to find out which PStates are valid for this `Device`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device`, `clock_type` or `pstate` are invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    */
+    // Checked against local
+    // Tested
+    #[doc(alias = "nvmlDeviceGetMinMaxClockOfPState")]
+    pub fn min_max_clock_of_pstate(
+        &self,
+        clock_type: Clock,
+        pstate: PerformanceState,
+    ) -> Result<(u32, u32), NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetMinMaxClockOfPState.as_ref())?;
+
+        unsafe {
+            let mut min: u32 = mem::zeroed();
+            let mut max: u32 = mem::zeroed();
+
+            nvml_try(sym(
+                self.device,
+                clock_type.as_c(),
+                pstate.as_c(),
+                &mut min,
+                &mut max,
+            ))?;
+
+            Ok((min, max))
+        }
+    }
+
+    // Event handling methods
+
+    /**
+    Starts recording the given `EventTypes` for this `Device` and adding them
+    to the specified `EventSet`.
+
+    Use `.supported_event_types()` to find out which events you can register for
+    this `Device`.
+
+    **Unfortunately, due to the way `error-chain` works, there is no way to
+    return the set if it is still valid after an error has occured with the
+    register call.** The set that you passed in will be freed if any error
+    occurs and will not be returned to you. This is not desired behavior
+    and I will fix it as soon as it is possible to do so.
+
+    All events that occurred before this call was made will not be recorded.
+
+    ECC events are only available on `Device`s with ECC enabled. Power capping events
+    are only available on `Device`s with power management enabled.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if `events` is invalid (shouldn't occur?)
+    * `NotSupported`, if the platform does not support this feature or some of the
+      requested event types.
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error. **If this error is returned, the `set` you
+      passed in has had its resources freed and will not be returned to you**. NVIDIA's
+      docs say that this error means that the set is in an invalid state.
+
+    # Device Support
+
+    Supports Fermi and newer fully supported devices.
+
+    # Platform Support
+
     Only supports Linux.
 
     # Examples
@@ -6010,15 +7714,20 @@ impl<'nvml> Device<'nvml> {
     /**
     Get the list of performance modes for `Device`
 
-    Originally, NVML returns as a list in a form of a single string separated by comma.
+    NVML returns these as a single string, which is parsed into a
+    `PerformanceMode` per mode.
 
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
-    * `NotSupported`, if the platform does not support this feature or some of the
-      requested event types.
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
-    * `Unknown`, on any unexpected error. **If this error is returned, the `set` you
+    * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    * `UnexpectedVariant`, for which you can read the docs for (this includes
+      `NVML_ERROR_ARGUMENT_VERSION_MISMATCH`, if the struct version is not
+      supported by the driver)
+    * `Unknown`, on any unexpected error
 
     # Platform Support
 
@@ -6028,32 +7737,71 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[cfg(target_os = "linux")]
     #[doc(alias = "nvmlDeviceGetPerformanceModes")]
-    pub fn performance_modes(&self) -> Result<(Vec<String>, u32), NvmlError> {
+    pub fn performance_modes(&self) -> Result<Vec<PerformanceMode>, NvmlError> {
         let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetPerformanceModes.as_ref())?;
 
         unsafe {
             let mut pmodes: nvmlDevicePerfModes_t = mem::zeroed();
+            // Implements NVML_STRUCT_VERSION(DevicePerfModes, 1), as detailed in nvml.h
+            pmodes.version =
+                (mem::size_of::<nvmlDevicePerfModes_v1_t>() | (1_usize << 24_usize)) as u32;
 
             nvml_try(sym(self.device, &mut pmodes))?;
 
             let modes_str = CStr::from_ptr(pmodes.str_.as_ptr());
-            let modes = modes_str.to_str()?;
-            Ok((
-                modes.split(';').map(str::to_string).collect(),
-                pmodes.version,
-            ))
+
+            Ok(PerformanceMode::parse_list(modes_str.to_str()?))
         }
     }
 
     /**
-    Gets the active vGPU instances for `Device`
+    Gets this `Device`'s current clock frequencies in MHz, keyed by the name
+    NVML gives each clock domain (e.g. `nvclock`, `memclock` or
+    `memTransferRate`).
 
-    A list as Vec of vGPU handles is returned to be used with nvmlVgpuInstance* calls.
+    This reads every domain in one call, unlike `.clock_info()`, which queries
+    a single `Clock` type.
 
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    * `UnexpectedVariant`, for which you can read the docs for (this includes
+      `NVML_ERROR_ARGUMENT_VERSION_MISMATCH`, if the struct version is not
+      supported by the driver)
+    * `Unknown`, on any unexpected error
+    */
+    // Checked against local
+    // Tested
+    #[doc(alias = "nvmlDeviceGetCurrentClockFreqs")]
+    pub fn current_clock_freqs(&self) -> Result<BTreeMap<String, u32>, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetCurrentClockFreqs.as_ref())?;
+
+        unsafe {
+            let mut freqs: nvmlDeviceCurrentClockFreqs_t = mem::zeroed();
+            // Implements NVML_STRUCT_VERSION(DeviceCurrentClockFreqs, 1), as detailed in nvml.h
+            freqs.version =
+                (mem::size_of::<nvmlDeviceCurrentClockFreqs_v1_t>() | (1_usize << 24_usize)) as u32;
+
+            nvml_try(sym(self.device, &mut freqs))?;
+
+            let freqs_str = CStr::from_ptr(freqs.str_.as_ptr());
+
+            Ok(parse_clock_pairs(freqs_str.to_str()?))
+        }
+    }
+
+    /**
+    Gets the active vGPU instances for `Device`
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `InsufficientSize`, if the list kept growing while it was being fetched
     * `Unknown`, on any unexpected error
     * `NotSupported`, if the platform does not support this feature
 
@@ -6065,18 +7813,17 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[cfg(target_os = "linux")]
     #[doc(alias = "nvmlDeviceGetActiveVgpus")]
-    pub fn active_vgpus(&self) -> Result<Vec<nvmlVgpuInstance_t>, NvmlError> {
+    pub fn active_vgpus(&self) -> Result<Vec<VgpuInstance<'_>>, NvmlError> {
         let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetActiveVgpus.as_ref())?;
 
-        unsafe {
-            let mut count: u32 = 0;
-
-            nvml_try(sym(self.device, std::ptr::null_mut(), &mut count))?;
-            let mut arr: Vec<nvmlVgpuInstance_t> = vec![0; count as usize];
-            nvml_try(sym(self.device, arr.as_mut_ptr(), &mut count))?;
+        let instances = nvml_try_list(0, |count, instances| unsafe {
+            sym(self.device, instances, count)
+        })?;
 
-            Ok(arr)
-        }
+        Ok(instances
+            .into_iter()
+            .map(|instance| VgpuInstance::new(self, instance))
+            .collect())
     }
 
     /**
@@ -6086,6 +7833,7 @@ impl<'nvml> Device<'nvml> {
 
     * `Uninitialized`, if the library has not been successfully initialized
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `InsufficientSize`, if the list kept growing while it was being fetched
     * `Unknown`, on any unexpected error
     * `NotSupported`, if the platform does not support this feature
 
@@ -6100,15 +7848,7 @@ impl<'nvml> Device<'nvml> {
     ) -> Result<Vec<u32>, NvmlError> {
         let sym = nvml_sym(self.nvml.lib.nvmlVgpuInstanceGetAccountingPids.as_ref())?;
 
-        unsafe {
-            let mut count: u32 = 0;
-
-            nvml_try(sym(instance, &mut count, std::ptr::null_mut()))?;
-            let mut pids: Vec<u32> = vec![0; count as usize];
-            nvml_try(sym(instance, &mut count, pids.as_mut_ptr()))?;
-
-            Ok(pids)
-        }
+        nvml_try_list(0, |count, pids| unsafe { sym(instance, count, pids) })
     }
 
     /**
@@ -6173,6 +7913,36 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Sets the virtualization mode of this `Device`.
+
+    Changing the mode usually requires a GPU reset or reboot to take full
+    effect.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid or `mode` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    #[cfg(target_os = "linux")]
+    #[doc(alias = "nvmlDeviceSetVirtualizationMode")]
+    pub fn set_virtualization_mode(
+        &mut self,
+        mode: GpuVirtualizationMode,
+    ) -> Result<(), NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetVirtualizationMode.as_ref())?;
+
+        unsafe { nvml_try(sym(self.device, mode.as_c())) }
+    }
+
     /**
     Removes this `Device` from the view of both NVML and the NVIDIA kernel driver.
 
@@ -6331,6 +8101,39 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets information about this `Device`'s membership of an NVLink fabric
+    (e.g. an NVSwitch-connected cluster): its cluster UUID, clique ID and
+    registration state and health.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Hopper or newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetGpuFabricInfoV")]
+    pub fn gpu_fabric_info(&self) -> Result<GpuFabricInfo, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetGpuFabricInfoV.as_ref())?;
+
+        unsafe {
+            let mut info: nvmlGpuFabricInfoV_t = mem::zeroed();
+            // Implements NVML_STRUCT_VERSION(GpuFabricInfo, 2), as detailed in nvml.h
+            info.version =
+                (mem::size_of::<nvmlGpuFabricInfo_v2_t>() | (2_usize << 24_usize)) as u32;
+
+            nvml_try(sym(self.device, &mut info))?;
+
+            GpuFabricInfo::try_from(info)
+        }
+    }
+
     // NvLink
 
     /**
@@ -6343,46 +8146,173 @@ impl<'nvml> Device<'nvml> {
         NvLink { device: self, link }
     }
 
-    // vGPU
+    /**
+    Gets the NvLink bandwidth modes this `Device` supports.
 
-    /// Obtain a list of vGPU type (profiles) supported by the device, if any.
-    pub fn vgpu_supported_types(&self) -> Result<Vec<VgpuType>, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetSupportedVgpus.as_ref())?;
-        let mut ids = vec![];
+    Unlike `NvLink.bw_mode()`, which works on the system-wide mode, this is
+    per device.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Blackwell or newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetNvlinkSupportedBwModes")]
+    pub fn nvlink_supported_bw_modes(&self) -> Result<Vec<u8>, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetNvlinkSupportedBwModes.as_ref())?;
 
         unsafe {
-            let mut count: c_uint = 0;
+            let mut modes: nvmlNvlinkSupportedBwModes_t = mem::zeroed();
+            // Implements NVML_STRUCT_VERSION(NvlinkSupportedBwModes, 1), as detailed in nvml.h
+            modes.version =
+                (mem::size_of::<nvmlNvlinkSupportedBwModes_v1_t>() | (1_usize << 24_usize)) as u32;
 
-            match nvml_try(sym(self.device, &mut count, ids.as_mut_ptr())) {
-                Ok(()) | Err(NvmlError::InsufficientSize(_)) => {}
-                Err(err) => return Err(err),
-            }
+            nvml_try(sym(self.device, &mut modes))?;
 
-            ids.resize(count as usize, 0);
-            nvml_try(sym(self.device, &mut count, ids.as_mut_ptr()))?;
+            Ok(modes
+                .bwModes
+                .iter()
+                .take(modes.totalBwModes as usize)
+                .copied()
+                .collect())
         }
-
-        Ok(ids.into_iter().map(|id| VgpuType::new(self, id)).collect())
     }
 
-    /// Obtain a list of vGPU type (profiles) creatable on the device, if any.
-    pub fn vgpu_creatable_types(&self) -> Result<Vec<VgpuType>, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetCreatableVgpus.as_ref())?;
-        let mut ids = vec![];
+    /**
+    Gets the NvLink bandwidth mode of this `Device`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Blackwell or newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetNvlinkBwMode")]
+    pub fn nvlink_bw_mode(&self) -> Result<NvLinkBwMode, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetNvlinkBwMode.as_ref())?;
 
         unsafe {
-            let mut count: c_uint = 0;
+            let mut mode: nvmlNvlinkGetBwMode_t = mem::zeroed();
+            // Implements NVML_STRUCT_VERSION(NvlinkGetBwMode, 1), as detailed in nvml.h
+            mode.version =
+                (mem::size_of::<nvmlNvlinkGetBwMode_v1_t>() | (1_usize << 24_usize)) as u32;
 
-            match nvml_try(sym(self.device, &mut count, ids.as_mut_ptr())) {
-                Ok(()) | Err(NvmlError::InsufficientSize(_)) => {}
-                Err(err) => return Err(err),
-            }
+            nvml_try(sym(self.device, &mut mode))?;
 
-            ids.resize(count as usize, 0);
-            nvml_try(sym(self.device, &mut count, ids.as_mut_ptr()))?;
+            Ok(match mode.bIsBest {
+                0 => NvLinkBwMode::Mode(mode.bwMode),
+                _ => NvLinkBwMode::Best,
+            })
         }
+    }
+
+    /**
+    Sets the NvLink bandwidth mode of this `Device`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid or `mode` is not supported
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `InUse`, if the device is in use and the mode cannot be changed
+    * `Unknown`, on any unexpected error
 
-        Ok(ids.into_iter().map(|id| VgpuType::new(self, id)).collect())
+    # Device Support
+
+    Supports Blackwell or newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceSetNvlinkBwMode")]
+    pub fn set_nvlink_bw_mode(&mut self, mode: NvLinkBwMode) -> Result<(), NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetNvlinkBwMode.as_ref())?;
+
+        let (set_best, bw_mode) = match mode {
+            NvLinkBwMode::Best => (1, 0),
+            NvLinkBwMode::Mode(m) => (0, m),
+        };
+
+        let mut mode = nvmlNvlinkSetBwMode_t {
+            // Implements NVML_STRUCT_VERSION(NvlinkSetBwMode, 1), as detailed in nvml.h
+            version: (mem::size_of::<nvmlNvlinkSetBwMode_v1_t>() | (1_usize << 24_usize)) as u32,
+            bSetBest: set_best,
+            bwMode: bw_mode,
+        };
+
+        unsafe { nvml_try(sym(self.device, &mut mode)) }
+    }
+
+    // vGPU
+
+    /**
+    Gets the IDs of the vGPU types (profiles) supported by this `Device`, if any.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support vGPU
+    * `InsufficientSize`, if the list kept growing while it was being fetched
+    * `Unknown`, on any unexpected error
+    */
+    #[doc(alias = "nvmlDeviceGetSupportedVgpus")]
+    pub fn supported_vgpus(&self) -> Result<Vec<VgpuTypeId>, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetSupportedVgpus.as_ref())?;
+
+        let ids = nvml_try_list(0, |count, ids| unsafe { sym(self.device, count, ids) })?;
+
+        Ok(ids.into_iter().map(VgpuTypeId).collect())
+    }
+
+    /**
+    Gets the IDs of the vGPU types (profiles) that can currently be created on
+    this `Device`, if any.
+
+    The list shrinks as vGPU instances are created and capacity is used up.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support vGPU
+    * `InsufficientSize`, if the list kept growing while it was being fetched
+    * `Unknown`, on any unexpected error
+    */
+    #[doc(alias = "nvmlDeviceGetCreatableVgpus")]
+    pub fn creatable_vgpus(&self) -> Result<Vec<VgpuTypeId>, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetCreatableVgpus.as_ref())?;
+
+        let ids = nvml_try_list(0, |count, ids| unsafe { sym(self.device, count, ids) })?;
+
+        Ok(ids.into_iter().map(VgpuTypeId).collect())
+    }
+
+    /// Obtain a list of vGPU type (profiles) supported by the device, if any.
+    pub fn vgpu_supported_types(&self) -> Result<Vec<VgpuType>, NvmlError> {
+        Ok(self
+            .supported_vgpus()?
+            .into_iter()
+            .map(|id| VgpuType::new(self, id.0))
+            .collect())
+    }
+
+    /// Obtain a list of vGPU type (profiles) creatable on the device, if any.
+    pub fn vgpu_creatable_types(&self) -> Result<Vec<VgpuType>, NvmlError> {
+        Ok(self
+            .creatable_vgpus()?
+            .into_iter()
+            .map(|id| VgpuType::new(self, id.0))
+            .collect())
     }
 
     /// Obtain a list of vGPU scheduler capabilities supported by the device, if any.
@@ -6440,7 +8370,20 @@ impl<'nvml> Device<'nvml> {
         unsafe { nvml_try(sym(self.device, &mut scheduler_state.as_c())) }
     }
 
-    /// Check if the GPU is on vGPU host mode
+    /**
+    Gets whether this `Device` runs vGPUs on SR-IOV virtual functions.
+
+    Only meaningful when the device is in `GpuVirtualizationMode::HostVgpu`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+    */
+    #[doc(alias = "nvmlDeviceGetHostVgpuMode")]
     pub fn vgpu_host_mode(&self) -> Result<HostVgpuMode, NvmlError> {
         let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetHostVgpuMode.as_ref())?;
 
@@ -6453,7 +8396,175 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
-    /// Query the given vGPU capability
+    /**
+    Check whether vGPU heterogeneous mode is enabled on this `Device`.
+
+    In heterogeneous mode, vGPU instances of different types (sizes) can run on
+    the device at the same time.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Ada or newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetVgpuHeterogeneousMode")]
+    pub fn vgpu_heterogeneous_mode(&self) -> Result<bool, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetVgpuHeterogeneousMode.as_ref())?;
+
+        unsafe {
+            let mut mode: nvmlVgpuHeterogeneousMode_t = mem::zeroed();
+            // Implements NVML_STRUCT_VERSION(VgpuHeterogeneousMode, 1), as detailed in nvml.h
+            mode.version = (std::mem::size_of::<nvmlVgpuHeterogeneousMode_v1_t>()
+                | (1_usize << 24_usize)) as u32;
+
+            nvml_try(sym(self.device, &mut mode))?;
+
+            Ok(mode.mode == nvmlEnableState_enum_NVML_FEATURE_ENABLED)
+        }
+    }
+
+    /**
+    Enable or disable vGPU heterogeneous mode on this `Device`.
+
+    The mode can only be changed while no vGPU instances are running on the
+    device.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `InUse`, if vGPU instances are running on the device
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Ada or newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceSetVgpuHeterogeneousMode")]
+    pub fn set_vgpu_heterogeneous_mode(&mut self, enabled: bool) -> Result<(), NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetVgpuHeterogeneousMode.as_ref())?;
+
+        let mode = nvmlVgpuHeterogeneousMode_t {
+            // Implements NVML_STRUCT_VERSION(VgpuHeterogeneousMode, 1), as detailed in nvml.h
+            version: (std::mem::size_of::<nvmlVgpuHeterogeneousMode_v1_t>() | (1_usize << 24_usize))
+                as u32,
+            mode: if enabled {
+                nvmlEnableState_enum_NVML_FEATURE_ENABLED
+            } else {
+                nvmlEnableState_enum_NVML_FEATURE_DISABLED
+            },
+        };
+
+        unsafe { nvml_try(sym(self.device, &mode)) }
+    }
+
+    /**
+    Obtain the vGPU metadata of this physical GPU, for checking the
+    compatibility of a vGPU instance with it with `Nvml.vgpu_compatibility()`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support vGPU
+    * `InsufficientSize`, if the metadata kept growing while it was being
+      fetched
+    * `Unknown`, on any unexpected error
+    */
+    #[doc(alias = "nvmlDeviceGetVgpuMetadata")]
+    pub fn vgpu_metadata(&self) -> Result<PgpuMetadata, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetVgpuMetadata.as_ref())?;
+
+        let bytes = nvml_try_blob(|metadata, size| unsafe { sym(self.device, metadata, size) })?;
+
+        PgpuMetadata::from_bytes(bytes)
+    }
+
+    /**
+    Obtain the vGPU metadata of this physical GPU as a string, e.g. to store
+    alongside `.vgpu_metadata()` in migration compatibility records.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support vGPU
+    * `InsufficientSize`, if the string kept growing while it was being fetched
+    * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    * `Unknown`, on any unexpected error
+    */
+    #[doc(alias = "nvmlDeviceGetPgpuMetadataString")]
+    pub fn pgpu_metadata_string(&self) -> Result<String, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetPgpuMetadataString.as_ref())?;
+
+        let buffer = nvml_try_list(0, |size, buffer| unsafe { sym(self.device, buffer, size) })?;
+        let bytes: Vec<u8> = buffer
+            .into_iter()
+            .map(|c| c as u8)
+            .take_while(|c| *c != 0)
+            .collect();
+
+        Ok(String::from_utf8(bytes).map_err(|e| e.utf8_error())?)
+    }
+
+    /**
+    Gets the GRID (NVIDIA vGPU software) licensable features of this `Device`
+    and whether each is licensed.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support GRID licensing
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Utf8Error`, if a string obtained from the C function is not valid Utf8
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell or newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetGridLicensableFeatures_v4")]
+    pub fn grid_licensable_features(&self) -> Result<GridLicensableFeatures, NvmlError> {
+        let sym = nvml_sym(
+            self.nvml
+                .lib
+                .nvmlDeviceGetGridLicensableFeatures_v4
+                .as_ref(),
+        )?;
+
+        unsafe {
+            let mut features: nvmlGridLicensableFeatures_t = mem::zeroed();
+
+            nvml_try(sym(self.device, &mut features))?;
+
+            GridLicensableFeatures::try_from(features)
+        }
+    }
+
+    /**
+    Query the given vGPU capability of this `Device`.
+
+    For toggleable capabilities, the value is non-zero if the capability is
+    enabled; for others (e.g. `VgpuCapability::ReadDeviceBufferBw`), it is the
+    value of the capability.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `Unknown`, on any unexpected error
+    */
+    #[doc(alias = "nvmlDeviceGetVgpuCapabilities")]
     pub fn vgpu_capabilities(&self, cap: VgpuCapability) -> Result<u32, NvmlError> {
         let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetVgpuCapabilities.as_ref())?;
 
@@ -6465,6 +8576,18 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Enable or disable the given vGPU capability of this `Device`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid or `cap` cannot be toggled
+    * `NotSupported`, if this `Device` does not support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `Unknown`, on any unexpected error
+    */
+    #[doc(alias = "nvmlDeviceSetVgpuCapabilities")]
     pub fn vgpu_set_capabilities(
         &self,
         cap: VgpuCapability,
@@ -6491,9 +8614,11 @@ mod test {
     #[cfg(target_os = "windows")]
     use crate::bitmasks::Behavior;
     use crate::enum_wrappers::device::*;
-    use crate::enums::device::GpuLockedClocksSetting;
+    use crate::enums::device::{GpuLockedClocksSetting, PowerScope, PowerSmoothingProfileParam};
+    use crate::enums::nv_link::NvLinkBwMode;
     use crate::error::*;
-    use crate::structs::device::FieldId;
+    use crate::struct_wrappers::device::PowerProfileMask;
+    use crate::structs::device::{FieldId, PowerSmoothingProfile};
     use crate::sys_exports::field_id::*;
     use crate::test_utils::*;
 
@@ -6554,6 +8679,46 @@ mod test {
         test_with_device(3, &nvml, |device| device.memory_affinity(64, 0))
     }
 
+    #[test]
+    fn conf_compute_mem_size_info() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.conf_compute_mem_size_info())
+    }
+
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn set_conf_compute_unprotected_mem_size() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        let info = device.conf_compute_mem_size_info().expect("mem size info");
+        device
+            .set_conf_compute_unprotected_mem_size(info.unprotected_mem_size_kib)
+            .expect("set unprotected mem size")
+    }
+
+    #[test]
+    fn confidential_compute_gpu_attestation_report() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.confidential_compute_gpu_attestation_report([0; 32])
+        })
+    }
+
+    #[test]
+    fn confidential_compute_gpu_certificate() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.confidential_compute_gpu_certificate()
+        })
+    }
+
+    #[test]
+    fn numa_node_id() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.numa_node_id())
+    }
+
     #[test]
     fn board_id() {
         let nvml = nvml();
@@ -6752,12 +8917,36 @@ mod test {
         test_with_device(3, &nvml, |device| device.enforced_power_limit())
     }
 
+    #[test]
+    fn gpc_clock_min_max_vf_offset() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.gpc_clock_min_max_vf_offset())
+    }
+
+    #[test]
+    fn mem_clock_min_max_vf_offset() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.mem_clock_min_max_vf_offset())
+    }
+
     #[test]
     fn fan_speed() {
         let nvml = nvml();
         test_with_device(3, &nvml, |device| device.fan_speed(0))
     }
 
+    #[test]
+    fn target_fan_speed() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.target_fan_speed(0))
+    }
+
+    #[test]
+    fn cooler_info() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.cooler_info(0))
+    }
+
     #[test]
     fn fan_speed_rpm() {
         let nvml = nvml();
@@ -6874,12 +9063,24 @@ mod test {
         })
     }
 
+    #[test]
+    fn all_memory_error_counters() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.all_memory_error_counters())
+    }
+
     #[test]
     fn memory_info() {
         let nvml = nvml();
         test_with_device(3, &nvml, |device| device.memory_info())
     }
 
+    #[test]
+    fn memory_usage_percent() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.memory_usage_percent())
+    }
+
     #[cfg(target_os = "linux")]
     #[test]
     fn minor_number() {
@@ -6900,6 +9101,18 @@ mod test {
         test_with_device(3, &nvml, |device| device.possible_placements(0))
     }
 
+    #[test]
+    fn gpu_instance_profile_info() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.gpu_instance_profile_info(0))
+    }
+
+    #[test]
+    fn gpu_instance_remaining_capacity() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.gpu_instance_remaining_capacity(0))
+    }
+
     #[test]
     fn mig_mode() {
         let nvml = nvml();
@@ -6939,6 +9152,45 @@ mod test {
         test(3, || device.mig_parent_device())
     }
 
+    #[test]
+    fn mig_gpu_instance_id() {
+        let nvml = nvml();
+        let device = device(&nvml);
+        let mig = device.mig_device_by_index(0).expect("mig device");
+        test(3, || mig.mig_gpu_instance_id())
+    }
+
+    #[test]
+    fn mig_compute_instance_id() {
+        let nvml = nvml();
+        let device = device(&nvml);
+        let mig = device.mig_device_by_index(0).expect("mig device");
+        test(3, || mig.mig_compute_instance_id())
+    }
+
+    #[test]
+    fn mig_devices() {
+        let nvml = nvml();
+        let device = device(&nvml);
+        test(3, || device.mig_devices())
+    }
+
+    #[test]
+    fn mig_device_memory_info() {
+        let nvml = nvml();
+        let device = device(&nvml);
+        let mig = device.mig_device_by_index(0).expect("mig device");
+        test(3, || mig.memory_info())
+    }
+
+    #[test]
+    fn mig_device_running_compute_processes() {
+        let nvml = nvml();
+        let device = device(&nvml);
+        let mig = device.mig_device_by_index(0).expect("mig device");
+        test(3, || mig.running_compute_processes())
+    }
+
     #[test]
     fn name() {
         let nvml = nvml();
@@ -7060,6 +9312,18 @@ mod test {
         test_with_device(3, &nvml, |device| device.are_pages_pending_retired())
     }
 
+    #[test]
+    fn remapped_rows() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.remapped_rows())
+    }
+
+    #[test]
+    fn row_remapper_histogram() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.row_remapper_histogram())
+    }
+
     #[test]
     #[ignore = "my machine does not support this call"]
     fn samples() {
@@ -7070,6 +9334,13 @@ mod test {
         })
     }
 
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn sampling_info() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.sampling_info(Sampling::Power))
+    }
+
     #[test]
     fn field_values_for() {
         let nvml = nvml();
@@ -7117,6 +9388,18 @@ mod test {
         })
     }
 
+    #[test]
+    fn scoped_field_values_for() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.scoped_field_values_for(&[
+                (FieldId(NVML_FI_DEV_NVLINK_THROUGHPUT_DATA_TX), 0),
+                (FieldId(NVML_FI_DEV_NVLINK_THROUGHPUT_DATA_RX), 0),
+                (FieldId(NVML_FI_DEV_NVLINK_THROUGHPUT_DATA_TX), u32::MAX),
+            ])
+        })
+    }
+
     // Passing an empty slice should return an `InvalidArg` error
     #[should_panic(expected = "InvalidArg")]
     #[test]
@@ -7140,28 +9423,64 @@ mod test {
     }
 
     #[test]
-    fn current_throttle_reasons() {
+    fn current_throttle_reasons() {
+        let nvml = nvml();
+
+        #[allow(deprecated)]
+        test_with_device(3, &nvml, |device| device.current_throttle_reasons())
+    }
+
+    #[test]
+    fn current_throttle_reasons_strict() {
+        let nvml = nvml();
+
+        #[allow(deprecated)]
+        test_with_device(3, &nvml, |device| device.current_throttle_reasons_strict())
+    }
+
+    #[test]
+    fn supported_throttle_reasons() {
+        let nvml = nvml();
+
+        #[allow(deprecated)]
+        test_with_device(3, &nvml, |device| device.supported_throttle_reasons())
+    }
+
+    #[test]
+    fn supported_throttle_reasons_strict() {
+        let nvml = nvml();
+
+        #[allow(deprecated)]
+        test_with_device(3, &nvml, |device| {
+            device.supported_throttle_reasons_strict()
+        })
+    }
+
+    #[test]
+    fn current_clocks_event_reasons() {
         let nvml = nvml();
-        test_with_device(3, &nvml, |device| device.current_throttle_reasons())
+        test_with_device(3, &nvml, |device| device.current_clocks_event_reasons())
     }
 
     #[test]
-    fn current_throttle_reasons_strict() {
+    fn current_clocks_event_reasons_strict() {
         let nvml = nvml();
-        test_with_device(3, &nvml, |device| device.current_throttle_reasons_strict())
+        test_with_device(3, &nvml, |device| {
+            device.current_clocks_event_reasons_strict()
+        })
     }
 
     #[test]
-    fn supported_throttle_reasons() {
+    fn supported_clocks_event_reasons() {
         let nvml = nvml();
-        test_with_device(3, &nvml, |device| device.supported_throttle_reasons())
+        test_with_device(3, &nvml, |device| device.supported_clocks_event_reasons())
     }
 
     #[test]
-    fn supported_throttle_reasons_strict() {
+    fn supported_clocks_event_reasons_strict() {
         let nvml = nvml();
         test_with_device(3, &nvml, |device| {
-            device.supported_throttle_reasons_strict()
+            device.supported_clocks_event_reasons_strict()
         })
     }
 
@@ -7196,6 +9515,26 @@ mod test {
         })
     }
 
+    #[test]
+    fn temperature_v() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.temperature_v(TemperatureSensor::Gpu)
+        })
+    }
+
+    #[test]
+    fn margin_temperature() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.margin_temperature())
+    }
+
+    #[test]
+    fn thermal_settings() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.thermal_settings(None))
+    }
+
     #[test]
     fn temperature_threshold() {
         let nvml = nvml();
@@ -7207,6 +9546,16 @@ mod test {
         })
     }
 
+    #[test]
+    fn acoustic_temperature_threshold() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.temperature_threshold(TemperatureThreshold::AcousticMin)?;
+            device.temperature_threshold(TemperatureThreshold::AcousticCurr)?;
+            device.temperature_threshold(TemperatureThreshold::AcousticMax)
+        })
+    }
+
     #[test]
     fn set_temperature_threshold() {
         let nvml = nvml();
@@ -7326,6 +9675,28 @@ mod test {
         device1.is_on_same_board_as(&device2).expect("bool");
     }
 
+    #[ignore = "my machine does not support this call"]
+    #[test]
+    fn p2p_status() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.p2p_status(device, P2pCapabilitiesIndex::Read)
+        })
+    }
+
+    #[test]
+    fn compare() {
+        let nvml = nvml();
+        let device = device(&nvml);
+        test(3, || device.compare(&device))
+    }
+
+    #[test]
+    fn support_matrix() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| Ok(device.support_matrix()))
+    }
+
     // This modifies device state, so we don't want to actually run the test
     #[allow(dead_code)]
     fn reset_applications_clocks() {
@@ -7454,6 +9825,19 @@ mod test {
         device.set_applications_clocks(32, 32).expect("set to true")
     }
 
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn set_applications_clocks_scoped() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        let guard = device
+            .set_applications_clocks_scoped(32, 32)
+            .expect("set to 32");
+
+        guard.restore().expect("clocks restored")
+    }
+
     // This modifies device state, so we don't want to actually run the test
     #[allow(dead_code)]
     fn set_compute_mode() {
@@ -7477,6 +9861,18 @@ mod test {
             .expect("set to wdm")
     }
 
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn set_fan_speed() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        let (_, max) = device.min_max_fan_speed().expect("fan speed range");
+
+        device.set_fan_speed(0, max).expect("set to max");
+        device.set_default_fan_speed(0).expect("set to default")
+    }
+
     // This modifies device state, so we don't want to actually run the test
     #[allow(dead_code)]
     fn set_gpu_locked_clocks() {
@@ -7520,6 +9916,20 @@ mod test {
         device.reset_mem_locked_clocks().expect("clocks reset")
     }
 
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn lock_clocks_scoped() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        let mut guard = device
+            .lock_clocks_scoped(1048, 1139)
+            .expect("set to a range");
+        guard.lock_mem(1048, 1139).expect("set to a range");
+
+        guard.restore().expect("clocks reset")
+    }
+
     // This modifies device state, so we don't want to actually run the test
     #[allow(dead_code)]
     fn set_ecc() {
@@ -7529,6 +9939,17 @@ mod test {
         device.set_ecc(true).expect("set to true")
     }
 
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn set_ecc_scoped() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        let guard = device.set_ecc_scoped(true).expect("set to true");
+
+        guard.restore().expect("pending mode restored")
+    }
+
     // This modifies device state, so we don't want to actually run the test
     #[allow(dead_code)]
     fn set_gpu_op_mode() {
@@ -7561,6 +9982,64 @@ mod test {
             .expect("set to true")
     }
 
+    #[test]
+    fn workload_power_profiles() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.workload_power_profiles())
+    }
+
+    #[test]
+    fn current_workload_power_profiles() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.current_workload_power_profiles())
+    }
+
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn set_requested_workload_power_profiles() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        let profiles: PowerProfileMask = [PowerProfileType::MaxQ].iter().copied().collect();
+
+        device
+            .set_requested_workload_power_profiles(&profiles)
+            .expect("request profiles");
+        device
+            .clear_requested_workload_power_profiles(&profiles)
+            .expect("clear profiles")
+    }
+
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn power_smoothing() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        device
+            .update_power_smoothing_preset_profile_param(&PowerSmoothingProfile {
+                profile_id: 0,
+                param: PowerSmoothingProfileParam::RampUpRate,
+                value: 1000.0,
+            })
+            .expect("update profile param");
+        device
+            .activate_power_smoothing_preset_profile(0)
+            .expect("activate profile");
+        device.set_power_smoothing_state(true).expect("set to true")
+    }
+
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn set_power_management_limit_v2() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        device
+            .set_power_management_limit_v2(PowerScope::Module, 900000)
+            .expect("set module power limit")
+    }
+
     // This modifies device state, so we don't want to actually run the test
     #[allow(dead_code)]
     fn set_clock_offset() {
@@ -7572,6 +10051,20 @@ mod test {
             .expect("set to true")
     }
 
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn set_clock_offsets() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        let mut offset = device
+            .clock_offset(Clock::Graphics, PerformanceState::Zero)
+            .expect("clock offset");
+        offset.clock_offset_mhz = offset.min_clock_offset_mhz.max(-100);
+
+        device.set_clock_offsets(&offset).expect("set clock offset")
+    }
+
     #[cfg(target_os = "linux")]
     #[allow(unused_variables)]
     #[test]
@@ -7606,6 +10099,29 @@ mod test {
         test_with_device(3, &nvml, |device| device.supported_event_types_strict())
     }
 
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    #[cfg(target_os = "linux")]
+    fn set_drain() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        device.set_drain(true, None).expect("set drain");
+        device.set_drain(false, None).expect("unset drain");
+    }
+
+    // This removes the device, so we don't want to actually run the test
+    #[allow(dead_code)]
+    #[cfg(target_os = "linux")]
+    fn remove() {
+        let nvml = nvml();
+        let device = device(&nvml);
+
+        let (res, device) = device.remove(None, DetachGpuState::Remove, PcieLinkState::ShutDown);
+        res.expect("removed");
+        assert!(device.is_none());
+    }
+
     #[cfg(target_os = "linux")]
     #[test]
     fn is_drain_enabled() {
@@ -7620,11 +10136,18 @@ mod test {
         test_with_device(3, &nvml, |device| device.performance_modes())
     }
 
+    #[test]
+    fn current_clock_freqs() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.current_clock_freqs())
+    }
+
     #[cfg(target_os = "linux")]
     #[test]
     fn active_vgpus() {
         let nvml = nvml();
-        test_with_device(3, &nvml, |device| device.active_vgpus())
+        let device = device(&nvml);
+        test(3, || device.active_vgpus())
     }
 
     #[test]
@@ -7639,6 +10162,147 @@ mod test {
         test_with_device(3, &nvml, |device| device.vgpu_accounting_instance(0, 0))
     }
 
+    #[test]
+    fn supported_vgpus() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.supported_vgpus())
+    }
+
+    #[test]
+    fn creatable_vgpus() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.creatable_vgpus())
+    }
+
+    #[test]
+    fn gpu_fabric_info() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.gpu_fabric_info())
+    }
+
+    #[test]
+    fn nvlink_supported_bw_modes() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.nvlink_supported_bw_modes())
+    }
+
+    #[test]
+    fn nvlink_bw_mode() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.nvlink_bw_mode())
+    }
+
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn set_nvlink_bw_mode() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        device
+            .set_nvlink_bw_mode(NvLinkBwMode::Best)
+            .expect("set NvLink bandwidth mode")
+    }
+
+    #[test]
+    fn vgpu_capabilities() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.vgpu_capabilities(VgpuCapability::FractionalMultiVgpu)
+        })
+    }
+
+    #[test]
+    fn vgpu_type_has_capability() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            for vgpu_type in device.vgpu_supported_types()? {
+                let raw = vgpu_type.capabilities(VgpuTypeCapability::GpuDirect.as_c())?;
+                assert_eq!(
+                    vgpu_type.has_capability(VgpuTypeCapability::GpuDirect)?,
+                    raw
+                );
+            }
+
+            Ok(())
+        })
+    }
+
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn vgpu_set_capabilities() {
+        let nvml = nvml();
+        let device = device(&nvml);
+
+        device
+            .vgpu_set_capabilities(VgpuCapability::FractionalMultiVgpu, true)
+            .expect("set vGPU capability")
+    }
+
+    #[test]
+    fn grid_licensable_features() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.grid_licensable_features())
+    }
+
+    #[test]
+    fn vgpu_metadata() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.vgpu_metadata())
+    }
+
+    #[test]
+    fn vgpu_utilization_stats() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.vgpu_utilization_stats(None))
+    }
+
+    #[test]
+    fn vgpu_instances_utilization_info() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.vgpu_instances_utilization_info(None)
+        })
+    }
+
+    #[test]
+    fn vgpu_processes_utilization_info() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.vgpu_processes_utilization_info(None)
+        })
+    }
+
+    #[test]
+    fn vgpu_process_utilization_stats() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.vgpu_process_utilization_stats(None)
+        })
+    }
+
+    #[test]
+    fn vgpu_heterogeneous_mode() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.vgpu_heterogeneous_mode())
+    }
+
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn set_vgpu_heterogeneous_mode() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        device
+            .set_vgpu_heterogeneous_mode(true)
+            .expect("set heterogeneous mode")
+    }
+
+    #[test]
+    fn pgpu_metadata_string() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.pgpu_metadata_string())
+    }
+
     #[cfg(target_os = "linux")]
     #[test]
     fn virtualization_mode() {
@@ -7646,6 +10310,24 @@ mod test {
         test_with_device(3, &nvml, |device| device.virtualization_mode())
     }
 
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    #[cfg(target_os = "linux")]
+    fn set_virtualization_mode() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        device
+            .set_virtualization_mode(GpuVirtualizationMode::HostVgpu)
+            .expect("set virtualization mode")
+    }
+
+    #[test]
+    fn vgpu_host_mode() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.vgpu_host_mode())
+    }
+
     #[cfg(target_os = "linux")]
     #[test]
     fn device_attributes() {
@@ -420,6 +420,21 @@ pub enum TemperatureThreshold {
     /// GPU temperature at which the GPU can be throttled below the base clock.
     #[wrap(c_variant = "NVML_TEMPERATURE_THRESHOLD_GPU_MAX")]
     GpuMax,
+    /// Lowest temperature the acoustic (target) threshold can be set to.
+    #[wrap(c_variant = "NVML_TEMPERATURE_THRESHOLD_ACOUSTIC_MIN")]
+    AcousticMin,
+    /// Temperature the GPU targets, trading performance for lower fan noise.
+    ///
+    /// This is the threshold that can be set, between `AcousticMin` and
+    /// `AcousticMax`.
+    #[wrap(c_variant = "NVML_TEMPERATURE_THRESHOLD_ACOUSTIC_CURR")]
+    AcousticCurr,
+    /// Highest temperature the acoustic (target) threshold can be set to.
+    #[wrap(c_variant = "NVML_TEMPERATURE_THRESHOLD_ACOUSTIC_MAX")]
+    AcousticMax,
+    /// Current temperature threshold of GPU Performance Scaling.
+    #[wrap(c_variant = "NVML_TEMPERATURE_THRESHOLD_GPS_CURR")]
+    GpsCurr,
 }
 
 /// Level relationships within a system between two GPUs.
@@ -507,19 +522,26 @@ pub enum ComputeMode {
 }
 
 /// P2P capability index status.
+///
+/// Returned from `Device.p2p_status()`.
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Copy, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[wrap(c_enum = "nvmlGpuP2PStatus_enum")]
 pub enum P2pStatus {
+    /// The capability is supported between the two devices.
     #[wrap(c_variant = "NVML_P2P_STATUS_OK")]
     Ok,
+    /// The chipset does not support P2P.
     #[wrap(c_variant = "NVML_P2P_STATUS_CHIPSET_NOT_SUPPORED")]
     ChipsetNotSupported,
+    /// One of the devices does not support P2P.
     #[wrap(c_variant = "NVML_P2P_STATUS_GPU_NOT_SUPPORTED")]
     GpuNotSupported,
+    /// The I/O hub topology between the devices does not support P2P.
     #[wrap(c_variant = "NVML_P2P_STATUS_IOH_TOPOLOGY_NOT_SUPPORTED")]
     IohTopologyNotSupported,
+    /// P2P was disabled through a registry key.
     #[wrap(c_variant = "NVML_P2P_STATUS_DISABLED_BY_REGKEY")]
     DisabledByRegkey,
     #[wrap(c_variant = "NVML_P2P_STATUS_NOT_SUPPORTED")]
@@ -528,19 +550,26 @@ pub enum P2pStatus {
     Unknown,
 }
 
+/// P2P capabilities that can be queried with `Device.p2p_status()`.
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Copy, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[wrap(c_enum = "nvmlGpuP2PCapsIndex_enum")]
 pub enum P2pCapabilitiesIndex {
+    /// Reading from the other device's memory.
     #[wrap(c_variant = "NVML_P2P_CAPS_INDEX_READ")]
     Read,
+    /// Writing to the other device's memory.
     #[wrap(c_variant = "NVML_P2P_CAPS_INDEX_WRITE")]
     Write,
+    /// P2P over NvLink.
     #[wrap(c_variant = "NVML_P2P_CAPS_INDEX_NVLINK")]
     NvLink,
+    /// Atomic operations on the other device's memory.
     #[wrap(c_variant = "NVML_P2P_CAPS_INDEX_ATOMICS")]
     Atomics,
+    /// P2P over PCIe.
+    #[doc(alias = "NVML_P2P_CAPS_INDEX_PCI")]
     #[wrap(c_variant = "NVML_P2P_CAPS_INDEX_PROP")]
     Prop,
     #[wrap(c_variant = "NVML_P2P_CAPS_INDEX_UNKNOWN")]
@@ -665,6 +694,97 @@ pub enum HostVgpuMode {
     Sriov,
 }
 
+/// The kind of identifier a `VgpuInstance`'s VM ID is.
+#[derive(EnumWrapper, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[wrap(c_enum = "nvmlVgpuVmIdType")]
+pub enum VgpuVmIdType {
+    /// The VM ID is a domain ID
+    #[wrap(c_variant = "NVML_VGPU_VM_ID_DOMAIN_ID")]
+    DomainId,
+    /// The VM ID is a UUID
+    #[wrap(c_variant = "NVML_VGPU_VM_ID_UUID")]
+    Uuid,
+}
+
+/// Features that can be licensed through GRID (NVIDIA vGPU software licensing).
+#[derive(EnumWrapper, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[wrap(c_enum = "nvmlGridLicenseFeatureCode_t")]
+pub enum GridLicenseFeatureCode {
+    #[wrap(c_variant = "NVML_GRID_LICENSE_FEATURE_CODE_UNKNOWN")]
+    Unknown,
+    /// Virtual GPU
+    #[wrap(c_variant = "NVML_GRID_LICENSE_FEATURE_CODE_VGPU")]
+    Vgpu,
+    /// NVIDIA RTX Virtual Workstation (formerly Quadro Virtual Workstation)
+    #[doc(alias = "NVML_GRID_LICENSE_FEATURE_CODE_VWORKSTATION")]
+    #[wrap(c_variant = "NVML_GRID_LICENSE_FEATURE_CODE_NVIDIA_RTX")]
+    NvidiaRtx,
+    /// Gaming
+    #[wrap(c_variant = "NVML_GRID_LICENSE_FEATURE_CODE_GAMING")]
+    Gaming,
+    /// Compute
+    #[wrap(c_variant = "NVML_GRID_LICENSE_FEATURE_CODE_COMPUTE")]
+    Compute,
+}
+
+/// Whether the guest driver of a vGPU instance has reported information.
+#[derive(EnumWrapper, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[wrap(c_enum = "nvmlVgpuGuestInfoState_enum")]
+pub enum VgpuGuestInfoState {
+    /// The guest driver is not loaded, or has not reported information yet.
+    #[wrap(c_variant = "NVML_VGPU_INSTANCE_GUEST_INFO_STATE_UNINITIALIZED")]
+    Uninitialized,
+    /// The guest driver has reported information.
+    #[wrap(c_variant = "NVML_VGPU_INSTANCE_GUEST_INFO_STATE_INITIALIZED")]
+    Initialized,
+}
+
+/// Capabilities of a vGPU type (profile).
+///
+/// Used with `VgpuType::has_capability()`.
+#[derive(EnumWrapper, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[wrap(c_enum = "nvmlVgpuCapability_enum")]
+pub enum VgpuTypeCapability {
+    /// P2P over NVLink is supported
+    #[wrap(c_variant = "NVML_VGPU_CAP_NVLINK_P2P")]
+    NvlinkP2p,
+    /// GPUDirect capability is supported
+    #[wrap(c_variant = "NVML_VGPU_CAP_GPUDIRECT")]
+    GpuDirect,
+    /// vGPU profile cannot be mixed with other vGPU profiles in the same VM
+    #[wrap(c_variant = "NVML_VGPU_CAP_MULTI_VGPU_EXCLUSIVE")]
+    MultiVgpuExclusive,
+    /// vGPU profile cannot run on a GPU alongside other profiles of a different type
+    #[wrap(c_variant = "NVML_VGPU_CAP_EXCLUSIVE_TYPE")]
+    ExclusiveType,
+    /// vGPU profile cannot run on a GPU alongside other profiles of a different size
+    #[wrap(c_variant = "NVML_VGPU_CAP_EXCLUSIVE_SIZE")]
+    ExclusiveSize,
+}
+
+/// vGPU capabilities of the host driver.
+///
+/// Used with `Nvml::vgpu_driver_capabilities()`.
+#[derive(EnumWrapper, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[wrap(c_enum = "nvmlVgpuDriverCapability_enum")]
+pub enum VgpuDriverCapability {
+    /// vGPU profiles of different types can run on the same device (and the
+    /// same VM can use several of them)
+    #[wrap(c_variant = "NVML_VGPU_DRIVER_CAP_HETEROGENEOUS_MULTI_VGPU")]
+    HeterogeneousMultiVgpu,
+    /// The host driver can be updated while vGPUs are running
+    #[wrap(c_variant = "NVML_VGPU_DRIVER_CAP_WARM_UPDATE")]
+    WarmUpdate,
+}
+
+/// vGPU capabilities of a physical device.
+///
+/// Used with `Device::vgpu_capabilities()` and `Device::vgpu_set_capabilities()`.
 #[derive(EnumWrapper, Debug, Clone, Copy, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[wrap(c_enum = "nvmlDeviceVgpuCapability_enum")]
@@ -684,4 +804,79 @@ pub enum VgpuCapability {
     /// GPU write device buffer expected bandwidth (Mb/s)
     #[wrap(c_variant = "NVML_DEVICE_VGPU_CAP_WRITE_DEVICE_BUFFER_BW")]
     WriteDeviceBufferBw,
+    /// Device streaming
+    #[wrap(c_variant = "NVML_DEVICE_VGPU_CAP_DEVICE_STREAMING")]
+    DeviceStreaming,
+    /// Mini quarter GPU vGPU profiles
+    #[wrap(c_variant = "NVML_DEVICE_VGPU_CAP_MINI_QUARTER_GPU")]
+    MiniQuarterGpu,
+    /// Compute media engine vGPU profiles
+    #[wrap(c_variant = "NVML_DEVICE_VGPU_CAP_COMPUTE_MEDIA_ENGINE_GPU")]
+    ComputeMediaEngineGpu,
+    /// Warm updates of the host driver while vGPUs are running
+    #[wrap(c_variant = "NVML_DEVICE_VGPU_CAP_WARM_UPDATE")]
+    WarmUpdate,
+    /// Homogeneous placement of vGPUs of the same type
+    #[wrap(c_variant = "NVML_DEVICE_VGPU_CAP_HOMOGENEOUS_PLACEMENTS")]
+    HomogeneousPlacements,
+}
+
+/// Workload power profiles, which tune the power and clocks of a GPU for a
+/// kind of workload.
+///
+/// Used with [`crate::struct_wrappers::device::PowerProfileMask`].
+#[derive(EnumWrapper, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[wrap(c_enum = "nvmlPowerProfileType_t")]
+pub enum PowerProfileType {
+    /// Maximum performance.
+    #[wrap(c_variant = "NVML_POWER_PROFILE_MAX_P")]
+    MaxP,
+    /// Maximum performance per watt.
+    #[wrap(c_variant = "NVML_POWER_PROFILE_MAX_Q")]
+    MaxQ,
+    #[wrap(c_variant = "NVML_POWER_PROFILE_COMPUTE")]
+    Compute,
+    #[wrap(c_variant = "NVML_POWER_PROFILE_MEMORY_BOUND")]
+    MemoryBound,
+    #[wrap(c_variant = "NVML_POWER_PROFILE_NETWORK")]
+    Network,
+    #[wrap(c_variant = "NVML_POWER_PROFILE_BALANCED")]
+    Balanced,
+    #[wrap(c_variant = "NVML_POWER_PROFILE_LLM_INFERENCE")]
+    LlmInference,
+    #[wrap(c_variant = "NVML_POWER_PROFILE_LLM_TRAINING")]
+    LlmTraining,
+    #[wrap(c_variant = "NVML_POWER_PROFILE_RBM")]
+    Rbm,
+    #[wrap(c_variant = "NVML_POWER_PROFILE_DCPCIE")]
+    DcPcie,
+    #[wrap(c_variant = "NVML_POWER_PROFILE_HMMA_SPARSE")]
+    HmmaSparse,
+    #[wrap(c_variant = "NVML_POWER_PROFILE_HMMA_DENSE")]
+    HmmaDense,
+    #[wrap(c_variant = "NVML_POWER_PROFILE_SYNC_BALANCED")]
+    SyncBalanced,
+    #[wrap(c_variant = "NVML_POWER_PROFILE_HPC")]
+    Hpc,
+    #[wrap(c_variant = "NVML_POWER_PROFILE_MIG")]
+    Mig,
+}
+
+/// The kind of control signal a cooler takes.
+///
+/// Used in [`crate::struct_wrappers::device::CoolerInfo`].
+#[derive(EnumWrapper, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[wrap(c_enum = "nvmlCoolerControl_enum")]
+pub enum CoolerControl {
+    /// The cooler has no control signal.
+    #[wrap(c_variant = "NVML_THERMAL_COOLER_SIGNAL_NONE")]
+    None,
+    /// The cooler can only be switched on or off.
+    #[wrap(c_variant = "NVML_THERMAL_COOLER_SIGNAL_TOGGLE")]
+    Toggle,
+    /// The cooler's speed can be varied.
+    #[wrap(c_variant = "NVML_THERMAL_COOLER_SIGNAL_VARIABLE")]
+    Variable,
 }
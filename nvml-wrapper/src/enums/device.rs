@@ -77,6 +77,18 @@ impl SampleValue {
             }
         }
     }
+
+    /// The sample value as an `f64`, regardless of its underlying type.
+    ///
+    /// Large 64-bit integer values may lose precision.
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            SampleValue::F64(v) => v,
+            SampleValue::U32(v) => v as f64,
+            SampleValue::U64(v) => v as f64,
+            SampleValue::I64(v) => v as f64,
+        }
+    }
 }
 
 /// Represents different types of sample values.
@@ -381,3 +393,496 @@ impl TryFrom<nvmlFanControlPolicy_t> for FanControlPolicy {
         }
     }
 }
+
+/// vGPU scheduler policy.
+///
+/// Used in [`crate::struct_wrappers::device::VgpuSchedulerCapabilities`],
+/// [`crate::struct_wrappers::device::VgpuSchedulerLog`],
+/// [`crate::struct_wrappers::device::VgpuSchedulerGetState`] and
+/// [`crate::struct_wrappers::device::VgpuSchedulerSetState`].
+// TODO: technically this is an "enum wrapper" but the type on the C side isn't
+// an enum
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VgpuSchedulerPolicy {
+    Unknown,
+    BestEffort,
+    EqualShare,
+    FixedShare,
+    /// A policy this crate doesn't know about, with its raw value.
+    Other(u32),
+}
+
+impl VgpuSchedulerPolicy {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> c_uint {
+        match *self {
+            Self::Unknown => NVML_VGPU_SCHEDULER_POLICY_UNKNOWN,
+            Self::BestEffort => NVML_VGPU_SCHEDULER_POLICY_BEST_EFFORT,
+            Self::EqualShare => NVML_VGPU_SCHEDULER_POLICY_EQUAL_SHARE,
+            Self::FixedShare => NVML_VGPU_SCHEDULER_POLICY_FIXED_SHARE,
+            Self::Other(data) => data,
+        }
+    }
+}
+
+impl From<c_uint> for VgpuSchedulerPolicy {
+    fn from(data: c_uint) -> Self {
+        match data {
+            NVML_VGPU_SCHEDULER_POLICY_UNKNOWN => Self::Unknown,
+            NVML_VGPU_SCHEDULER_POLICY_BEST_EFFORT => Self::BestEffort,
+            NVML_VGPU_SCHEDULER_POLICY_EQUAL_SHARE => Self::EqualShare,
+            NVML_VGPU_SCHEDULER_POLICY_FIXED_SHARE => Self::FixedShare,
+            _ => Self::Other(data),
+        }
+    }
+}
+
+/// vGPU scheduler Adaptive Round Robin (ARR) mode.
+// TODO: technically this is an "enum wrapper" but the type on the C side isn't
+// an enum
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VgpuSchedulerArrMode {
+    /// The driver default.
+    Default,
+    Disabled,
+    Enabled,
+    /// A mode this crate doesn't know about, with its raw value.
+    Other(u32),
+}
+
+impl VgpuSchedulerArrMode {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> c_uint {
+        match *self {
+            Self::Default => NVML_VGPU_SCHEDULER_ARR_DEFAULT,
+            Self::Disabled => NVML_VGPU_SCHEDULER_ARR_DISABLE,
+            Self::Enabled => NVML_VGPU_SCHEDULER_ARR_ENABLE,
+            Self::Other(data) => data,
+        }
+    }
+}
+
+impl From<c_uint> for VgpuSchedulerArrMode {
+    fn from(data: c_uint) -> Self {
+        match data {
+            NVML_VGPU_SCHEDULER_ARR_DEFAULT => Self::Default,
+            NVML_VGPU_SCHEDULER_ARR_DISABLE => Self::Disabled,
+            NVML_VGPU_SCHEDULER_ARR_ENABLE => Self::Enabled,
+            _ => Self::Other(data),
+        }
+    }
+}
+
+/// The state of a GPU's registration with the NVLink fabric.
+///
+/// Used in [`crate::struct_wrappers::device::GpuFabricInfo`].
+// TODO: technically this is an "enum wrapper" but the type on the C side isn't
+// an enum
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GpuFabricState {
+    /// The GPU is not connected to a fabric.
+    NotSupported,
+    NotStarted,
+    InProgress,
+    Completed,
+}
+
+impl GpuFabricState {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> c_uint {
+        match *self {
+            Self::NotSupported => NVML_GPU_FABRIC_STATE_NOT_SUPPORTED,
+            Self::NotStarted => NVML_GPU_FABRIC_STATE_NOT_STARTED,
+            Self::InProgress => NVML_GPU_FABRIC_STATE_IN_PROGRESS,
+            Self::Completed => NVML_GPU_FABRIC_STATE_COMPLETED,
+        }
+    }
+}
+
+impl TryFrom<c_uint> for GpuFabricState {
+    type Error = NvmlError;
+
+    fn try_from(data: c_uint) -> Result<Self, Self::Error> {
+        match data {
+            NVML_GPU_FABRIC_STATE_NOT_SUPPORTED => Ok(Self::NotSupported),
+            NVML_GPU_FABRIC_STATE_NOT_STARTED => Ok(Self::NotStarted),
+            NVML_GPU_FABRIC_STATE_IN_PROGRESS => Ok(Self::InProgress),
+            NVML_GPU_FABRIC_STATE_COMPLETED => Ok(Self::Completed),
+            _ => Err(NvmlError::UnexpectedVariant(data)),
+        }
+    }
+}
+
+/// The licensing state of a vGPU instance.
+///
+/// Used in [`crate::struct_wrappers::device::VgpuLicenseInfo`].
+// TODO: technically this is an "enum wrapper" but the type on the C side isn't
+// an enum
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VgpuLicenseState {
+    Unknown,
+    Uninitialized,
+    /// Unlicensed, with features not yet restricted.
+    UnlicensedUnrestricted,
+    /// Unlicensed, with features restricted.
+    UnlicensedRestricted,
+    Unlicensed,
+    Licensed,
+}
+
+impl VgpuLicenseState {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> c_uint {
+        match *self {
+            Self::Unknown => NVML_GRID_LICENSE_STATE_UNKNOWN,
+            Self::Uninitialized => NVML_GRID_LICENSE_STATE_UNINITIALIZED,
+            Self::UnlicensedUnrestricted => NVML_GRID_LICENSE_STATE_UNLICENSED_UNRESTRICTED,
+            Self::UnlicensedRestricted => NVML_GRID_LICENSE_STATE_UNLICENSED_RESTRICTED,
+            Self::Unlicensed => NVML_GRID_LICENSE_STATE_UNLICENSED,
+            Self::Licensed => NVML_GRID_LICENSE_STATE_LICENSED,
+        }
+    }
+}
+
+impl TryFrom<c_uint> for VgpuLicenseState {
+    type Error = NvmlError;
+
+    fn try_from(data: c_uint) -> Result<Self, Self::Error> {
+        match data {
+            NVML_GRID_LICENSE_STATE_UNKNOWN => Ok(Self::Unknown),
+            NVML_GRID_LICENSE_STATE_UNINITIALIZED => Ok(Self::Uninitialized),
+            NVML_GRID_LICENSE_STATE_UNLICENSED_UNRESTRICTED => Ok(Self::UnlicensedUnrestricted),
+            NVML_GRID_LICENSE_STATE_UNLICENSED_RESTRICTED => Ok(Self::UnlicensedRestricted),
+            NVML_GRID_LICENSE_STATE_UNLICENSED => Ok(Self::Unlicensed),
+            NVML_GRID_LICENSE_STATE_LICENSED => Ok(Self::Licensed),
+            _ => Err(NvmlError::UnexpectedVariant(data)),
+        }
+    }
+}
+
+/// The status of a license expiry date.
+///
+/// Used in [`crate::struct_wrappers::device::VgpuLicenseExpiry`].
+// TODO: technically this is an "enum wrapper" but the type on the C side isn't
+// an enum
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LicenseExpiryStatus {
+    NotAvailable,
+    Invalid,
+    /// The expiry date is valid.
+    Valid,
+    NotApplicable,
+    /// The license never expires.
+    Permanent,
+}
+
+impl LicenseExpiryStatus {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> c_uint {
+        match *self {
+            Self::NotAvailable => NVML_GRID_LICENSE_EXPIRY_NOT_AVAILABLE,
+            Self::Invalid => NVML_GRID_LICENSE_EXPIRY_INVALID,
+            Self::Valid => NVML_GRID_LICENSE_EXPIRY_VALID,
+            Self::NotApplicable => NVML_GRID_LICENSE_EXPIRY_NOT_APPLICABLE,
+            Self::Permanent => NVML_GRID_LICENSE_EXPIRY_PERMANENT,
+        }
+    }
+}
+
+impl TryFrom<c_uint> for LicenseExpiryStatus {
+    type Error = NvmlError;
+
+    fn try_from(data: c_uint) -> Result<Self, Self::Error> {
+        match data {
+            NVML_GRID_LICENSE_EXPIRY_NOT_AVAILABLE => Ok(Self::NotAvailable),
+            NVML_GRID_LICENSE_EXPIRY_INVALID => Ok(Self::Invalid),
+            NVML_GRID_LICENSE_EXPIRY_VALID => Ok(Self::Valid),
+            NVML_GRID_LICENSE_EXPIRY_NOT_APPLICABLE => Ok(Self::NotApplicable),
+            NVML_GRID_LICENSE_EXPIRY_PERMANENT => Ok(Self::Permanent),
+            _ => Err(NvmlError::UnexpectedVariant(data)),
+        }
+    }
+}
+
+/// Which vGPU placements to list for a physical GPU.
+///
+/// Used in [`crate::vgpu::VgpuType::supported_placements`].
+// TODO: technically this is an "enum wrapper" but the type on the C side isn't
+// an enum
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VgpuPlacementMode {
+    /// Placements for a GPU in vGPU heterogeneous mode.
+    Heterogeneous,
+    /// Placements for a GPU that only runs instances of one vGPU type.
+    Homogeneous,
+}
+
+impl VgpuPlacementMode {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> c_uint {
+        match *self {
+            Self::Heterogeneous => NVML_VGPU_PGPU_HETEROGENEOUS_MODE,
+            Self::Homogeneous => NVML_VGPU_PGPU_HOMOGENEOUS_MODE,
+        }
+    }
+}
+
+impl TryFrom<c_uint> for VgpuPlacementMode {
+    type Error = NvmlError;
+
+    fn try_from(data: c_uint) -> Result<Self, Self::Error> {
+        match data {
+            NVML_VGPU_PGPU_HETEROGENEOUS_MODE => Ok(Self::Heterogeneous),
+            NVML_VGPU_PGPU_HOMOGENEOUS_MODE => Ok(Self::Homogeneous),
+            _ => Err(NvmlError::UnexpectedVariant(data)),
+        }
+    }
+}
+
+/// A parameter of a power smoothing preset profile.
+///
+/// Used in [`crate::structs::device::PowerSmoothingProfile`].
+// TODO: technically this is an "enum wrapper" but the type on the C side isn't
+// an enum
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PowerSmoothingProfileParam {
+    /// The floor of the power draw, as a percentage (`0.0` to `100.0`) of
+    /// the total module power.
+    PercentTmpFloor,
+    /// How fast power draw may ramp up, in W/s.
+    RampUpRate,
+    /// How fast power draw may ramp down, in W/s.
+    RampDownRate,
+    /// How long power draw must stay low before it ramps down, in ms.
+    RampDownHysteresis,
+}
+
+impl PowerSmoothingProfileParam {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> c_uint {
+        match *self {
+            Self::PercentTmpFloor => NVML_POWER_SMOOTHING_PROFILE_PARAM_PERCENT_TMP_FLOOR,
+            Self::RampUpRate => NVML_POWER_SMOOTHING_PROFILE_PARAM_RAMP_UP_RATE,
+            Self::RampDownRate => NVML_POWER_SMOOTHING_PROFILE_PARAM_RAMP_DOWN_RATE,
+            Self::RampDownHysteresis => NVML_POWER_SMOOTHING_PROFILE_PARAM_RAMP_DOWN_HYSTERESIS,
+        }
+    }
+}
+
+impl TryFrom<c_uint> for PowerSmoothingProfileParam {
+    type Error = NvmlError;
+
+    fn try_from(data: c_uint) -> Result<Self, Self::Error> {
+        match data {
+            NVML_POWER_SMOOTHING_PROFILE_PARAM_PERCENT_TMP_FLOOR => Ok(Self::PercentTmpFloor),
+            NVML_POWER_SMOOTHING_PROFILE_PARAM_RAMP_UP_RATE => Ok(Self::RampUpRate),
+            NVML_POWER_SMOOTHING_PROFILE_PARAM_RAMP_DOWN_RATE => Ok(Self::RampDownRate),
+            NVML_POWER_SMOOTHING_PROFILE_PARAM_RAMP_DOWN_HYSTERESIS => Ok(Self::RampDownHysteresis),
+            _ => Err(NvmlError::UnexpectedVariant(data)),
+        }
+    }
+}
+
+/// What a power value, such as a power limit, applies to.
+///
+/// Used in [`crate::Device::set_power_management_limit_v2`].
+// TODO: technically this is an "enum wrapper" but the type on the C side isn't
+// an enum
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PowerScope {
+    /// The GPU only.
+    Gpu,
+    /// The whole module the GPU is part of (e.g. the GPU and CPU of a
+    /// Grace Hopper superchip).
+    Module,
+    /// The GPU memory.
+    Memory,
+}
+
+impl PowerScope {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> nvmlPowerScopeType_t {
+        match *self {
+            Self::Gpu => NVML_POWER_SCOPE_GPU as nvmlPowerScopeType_t,
+            Self::Module => NVML_POWER_SCOPE_MODULE as nvmlPowerScopeType_t,
+            Self::Memory => NVML_POWER_SCOPE_MEMORY as nvmlPowerScopeType_t,
+        }
+    }
+}
+
+impl TryFrom<nvmlPowerScopeType_t> for PowerScope {
+    type Error = NvmlError;
+
+    fn try_from(data: nvmlPowerScopeType_t) -> Result<Self, Self::Error> {
+        match data as c_uint {
+            NVML_POWER_SCOPE_GPU => Ok(Self::Gpu),
+            NVML_POWER_SCOPE_MODULE => Ok(Self::Module),
+            NVML_POWER_SCOPE_MEMORY => Ok(Self::Memory),
+            _ => Err(NvmlError::UnexpectedVariant(data as c_uint)),
+        }
+    }
+}
+
+/// The kind of controller reading a thermal sensor.
+///
+/// Used in [`crate::struct_wrappers::device::ThermalSensor`].
+// The C type is an enum, but bindgen maps it to `c_int` (the unknown variant
+// is -1), which `EnumWrapper` can't derive for
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ThermalController {
+    /// No thermal controller.
+    None,
+    /// The GPU's internal thermal controller.
+    GpuInternal,
+    Adm1032,
+    Adt7461,
+    Max6649,
+    Max1617,
+    Lm99,
+    Lm89,
+    Lm64,
+    G781,
+    Adt7473,
+    SbMax6649,
+    VbiosEvt,
+    Os,
+    NvSysConCanoas,
+    NvSysConE551,
+    Max6649R,
+    Adt7473S,
+    Unknown,
+}
+
+impl ThermalController {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> nvmlThermalController_t {
+        match *self {
+            Self::None => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NONE,
+            Self::GpuInternal => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_GPU_INTERNAL,
+            Self::Adm1032 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADM1032,
+            Self::Adt7461 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7461,
+            Self::Max6649 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX6649,
+            Self::Max1617 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX1617,
+            Self::Lm99 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM99,
+            Self::Lm89 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM89,
+            Self::Lm64 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM64,
+            Self::G781 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_G781,
+            Self::Adt7473 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7473,
+            Self::SbMax6649 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_SBMAX6649,
+            Self::VbiosEvt => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_VBIOSEVT,
+            Self::Os => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_OS,
+            Self::NvSysConCanoas => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NVSYSCON_CANOAS,
+            Self::NvSysConE551 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NVSYSCON_E551,
+            Self::Max6649R => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX6649R,
+            Self::Adt7473S => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7473S,
+            Self::Unknown => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_UNKNOWN,
+        }
+    }
+}
+
+impl TryFrom<nvmlThermalController_t> for ThermalController {
+    type Error = NvmlError;
+
+    fn try_from(data: nvmlThermalController_t) -> Result<Self, Self::Error> {
+        match data {
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NONE => Ok(Self::None),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_GPU_INTERNAL => Ok(Self::GpuInternal),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADM1032 => Ok(Self::Adm1032),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7461 => Ok(Self::Adt7461),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX6649 => Ok(Self::Max6649),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX1617 => Ok(Self::Max1617),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM99 => Ok(Self::Lm99),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM89 => Ok(Self::Lm89),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM64 => Ok(Self::Lm64),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_G781 => Ok(Self::G781),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7473 => Ok(Self::Adt7473),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_SBMAX6649 => Ok(Self::SbMax6649),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_VBIOSEVT => Ok(Self::VbiosEvt),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_OS => Ok(Self::Os),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NVSYSCON_CANOAS => {
+                Ok(Self::NvSysConCanoas)
+            }
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NVSYSCON_E551 => Ok(Self::NvSysConE551),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX6649R => Ok(Self::Max6649R),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7473S => Ok(Self::Adt7473S),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_UNKNOWN => Ok(Self::Unknown),
+            _ => Err(NvmlError::UnexpectedVariant(data as c_uint)),
+        }
+    }
+}
+
+/// What a thermal sensor measures the temperature of.
+///
+/// Used in [`crate::struct_wrappers::device::ThermalSensor`].
+// The C type is an enum, but bindgen maps it to `c_int` (the unknown variant
+// is -1), which `EnumWrapper` can't derive for
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ThermalTarget {
+    /// No target.
+    None,
+    /// The GPU core temperature.
+    Gpu,
+    /// The GPU memory temperature.
+    Memory,
+    /// The power supply temperature.
+    PowerSupply,
+    /// The board temperature.
+    Board,
+    /// The visual computing device board temperature.
+    VcdBoard,
+    /// The visual computing device inlet temperature.
+    VcdInlet,
+    /// The visual computing device outlet temperature.
+    VcdOutlet,
+    /// All of the above.
+    All,
+    Unknown,
+}
+
+impl ThermalTarget {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> nvmlThermalTarget_t {
+        match *self {
+            Self::None => nvmlThermalTarget_t_NVML_THERMAL_TARGET_NONE,
+            Self::Gpu => nvmlThermalTarget_t_NVML_THERMAL_TARGET_GPU,
+            Self::Memory => nvmlThermalTarget_t_NVML_THERMAL_TARGET_MEMORY,
+            Self::PowerSupply => nvmlThermalTarget_t_NVML_THERMAL_TARGET_POWER_SUPPLY,
+            Self::Board => nvmlThermalTarget_t_NVML_THERMAL_TARGET_BOARD,
+            Self::VcdBoard => nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_BOARD,
+            Self::VcdInlet => nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_INLET,
+            Self::VcdOutlet => nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_OUTLET,
+            Self::All => nvmlThermalTarget_t_NVML_THERMAL_TARGET_ALL,
+            Self::Unknown => nvmlThermalTarget_t_NVML_THERMAL_TARGET_UNKNOWN,
+        }
+    }
+}
+
+impl TryFrom<nvmlThermalTarget_t> for ThermalTarget {
+    type Error = NvmlError;
+
+    fn try_from(data: nvmlThermalTarget_t) -> Result<Self, Self::Error> {
+        match data {
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_NONE => Ok(Self::None),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_GPU => Ok(Self::Gpu),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_MEMORY => Ok(Self::Memory),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_POWER_SUPPLY => Ok(Self::PowerSupply),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_BOARD => Ok(Self::Board),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_BOARD => Ok(Self::VcdBoard),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_INLET => Ok(Self::VcdInlet),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_OUTLET => Ok(Self::VcdOutlet),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_ALL => Ok(Self::All),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_UNKNOWN => Ok(Self::Unknown),
+            _ => Err(NvmlError::UnexpectedVariant(data as c_uint)),
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::bitmasks::event::EventTypes;
 #[cfg(feature = "serde")]
 use serde_derive::{Deserialize, Serialize};
 
@@ -13,3 +14,113 @@ pub enum XidError {
     /// If the error is unknown.
     Unknown,
 }
+
+/**
+A decoded event type, with the data NVML delivers alongside it.
+
+Obtained from `EventData.kinds()` or `EventData.kind()`, so that events can
+be matched on instead of testing bits of `EventData.event_type` and
+interpreting `EventData.event_data` by convention.
+*/
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EventKind {
+    SingleBitEccError,
+    DoubleBitEccError,
+    PStateChange,
+    XidCriticalError {
+        /// The most recent XID error seen by the system.
+        xid: XidError,
+    },
+    ClockChange,
+    PowerSourceChange,
+    MigConfigChange,
+}
+
+impl EventKind {
+    /**
+    Decode every event type set in `types`, in the order the bits are defined
+    in `EventTypes`.
+
+    `xid` is used for `XidCriticalError`; `XidError::Unknown` is assumed if it
+    is `None`.
+    */
+    pub fn decode(types: EventTypes, xid: Option<&XidError>) -> Vec<Self> {
+        let all = [
+            (
+                EventTypes::SINGLE_BIT_ECC_ERROR,
+                EventKind::SingleBitEccError,
+            ),
+            (
+                EventTypes::DOUBLE_BIT_ECC_ERROR,
+                EventKind::DoubleBitEccError,
+            ),
+            (EventTypes::PSTATE_CHANGE, EventKind::PStateChange),
+            (
+                EventTypes::CRITICAL_XID_ERROR,
+                EventKind::XidCriticalError {
+                    xid: xid.cloned().unwrap_or(XidError::Unknown),
+                },
+            ),
+            (EventTypes::CLOCK_CHANGE, EventKind::ClockChange),
+            (
+                EventTypes::POWER_SOURCE_CHANGE,
+                EventKind::PowerSourceChange,
+            ),
+            (EventTypes::MIG_CONFIG_CHANGE, EventKind::MigConfigChange),
+        ];
+
+        all.into_iter()
+            .filter(|(flag, _)| types.contains(*flag))
+            .map(|(_, kind)| kind)
+            .collect()
+    }
+
+    /// The `EventTypes` flag this kind of event is registered with.
+    pub fn event_type(&self) -> EventTypes {
+        match self {
+            EventKind::SingleBitEccError => EventTypes::SINGLE_BIT_ECC_ERROR,
+            EventKind::DoubleBitEccError => EventTypes::DOUBLE_BIT_ECC_ERROR,
+            EventKind::PStateChange => EventTypes::PSTATE_CHANGE,
+            EventKind::XidCriticalError { .. } => EventTypes::CRITICAL_XID_ERROR,
+            EventKind::ClockChange => EventTypes::CLOCK_CHANGE,
+            EventKind::PowerSourceChange => EventTypes::POWER_SOURCE_CHANGE,
+            EventKind::MigConfigChange => EventTypes::MIG_CONFIG_CHANGE,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decode_event_types() {
+        assert_eq!(EventKind::decode(EventTypes::empty(), None), vec![]);
+
+        let kinds = EventKind::decode(
+            EventTypes::CRITICAL_XID_ERROR | EventTypes::SINGLE_BIT_ECC_ERROR,
+            Some(&XidError::Value(79)),
+        );
+        assert_eq!(
+            kinds,
+            vec![
+                EventKind::SingleBitEccError,
+                EventKind::XidCriticalError {
+                    xid: XidError::Value(79)
+                },
+            ]
+        );
+
+        assert_eq!(
+            EventKind::decode(EventTypes::CRITICAL_XID_ERROR, None),
+            vec![EventKind::XidCriticalError {
+                xid: XidError::Unknown
+            }]
+        );
+
+        for kind in EventKind::decode(EventTypes::all(), None) {
+            assert_eq!(EventKind::decode(kind.event_type(), None), vec![kind]);
+        }
+    }
+}
@@ -11,3 +11,16 @@ pub enum Counter {
     Zero = 0,
     One = 1,
 }
+
+/// The NvLink bandwidth mode of a device.
+///
+/// Returned from `Device.nvlink_bw_mode()` and used with
+/// `Device.set_nvlink_bw_mode()`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NvLinkBwMode {
+    /// The best bandwidth the device supports, as chosen by the driver.
+    Best,
+    /// One of the modes returned by `Device.nvlink_supported_bw_modes()`.
+    Mode(u8),
+}
@@ -1,6 +1,8 @@
 use crate::ffi::bindings::*;
 #[cfg(feature = "serde")]
 use serde_derive::{Deserialize, Serialize};
+use std::mem;
+use std::os::raw::c_uint;
 use thiserror::Error;
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -203,3 +205,215 @@ pub fn nvml_try(code: nvmlReturn_t) -> Result<(), NvmlError> {
 pub fn nvml_sym<'a, T>(sym: Result<&'a T, &libloading::Error>) -> Result<&'a T, NvmlError> {
     sym.map_err(|e| NvmlError::FailedToLoadSymbol(e.to_string()))
 }
+
+/// How many times `nvml_try_list()` retries when the list grows between calls.
+pub(crate) const LIST_QUERY_RETRIES: u32 = 4;
+
+/**
+Runs an NVML list query that is called once to get the item count and once
+more to fill a buffer of that size.
+
+`query` receives the in/out count and a pointer to the buffer (null for the
+sizing call) and returns what the NVML function returned. If items are added
+between the two calls, NVML returns `InsufficientSize` along with the new
+count; the fetch is then retried with a buffer of that size, up to
+`LIST_QUERY_RETRIES` times.
+
+# Errors
+
+* `InsufficientSize`, holding the last count reported, if the list kept growing
+* Any error returned by `query`
+*/
+pub(crate) fn nvml_try_list<T, F>(default: T, mut query: F) -> Result<Vec<T>, NvmlError>
+where
+    T: Clone,
+    F: FnMut(&mut u32, *mut T) -> nvmlReturn_t,
+{
+    let mut count = 0;
+
+    match nvml_try(query(&mut count, std::ptr::null_mut())) {
+        Ok(()) | Err(NvmlError::InsufficientSize(_)) => {}
+        Err(e) => return Err(e),
+    }
+
+    for _ in 0..=LIST_QUERY_RETRIES {
+        if count == 0 {
+            return Ok(vec![]);
+        }
+
+        let mut items = vec![default.clone(); count as usize];
+
+        match nvml_try(query(&mut count, items.as_mut_ptr())) {
+            Ok(()) => {
+                items.truncate(count as usize);
+                return Ok(items);
+            }
+            // `count` now holds the size the list grew to
+            Err(NvmlError::InsufficientSize(_)) => continue,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(NvmlError::InsufficientSize(Some(count as usize)))
+}
+
+/**
+Runs an NVML query that fills a caller-allocated, variable-size struct `T`,
+first asking for the size it needs.
+
+`query` receives a pointer to the buffer (null for the sizing call) and the
+in/out size in bytes, and returns what the NVML function returned. If the
+struct grows between calls, NVML returns `InsufficientSize` along with the new
+size; the fetch is then retried with a buffer of that size, up to
+`LIST_QUERY_RETRIES` times.
+
+# Errors
+
+* `InsufficientSize`, holding the last size reported, if the struct kept
+  growing
+* Any error returned by `query`
+*/
+pub(crate) fn nvml_try_blob<T, F>(mut query: F) -> Result<Vec<u8>, NvmlError>
+where
+    F: FnMut(*mut T, &mut c_uint) -> nvmlReturn_t,
+{
+    let mut size: c_uint = 0;
+
+    match nvml_try(query(std::ptr::null_mut(), &mut size)) {
+        Ok(()) | Err(NvmlError::InsufficientSize(_)) => {}
+        Err(e) => return Err(e),
+    }
+
+    for _ in 0..=LIST_QUERY_RETRIES {
+        let len = (size as usize).max(mem::size_of::<T>());
+        // Allocated as `u64`s to get an alignment suitable for `T`
+        let mut buffer = vec![0u64; (len + 7) / 8];
+        let mut filled = len as c_uint;
+
+        match nvml_try(query(buffer.as_mut_ptr() as *mut T, &mut filled)) {
+            Ok(()) => {
+                let mut bytes: Vec<u8> = buffer.iter().flat_map(|w| w.to_ne_bytes()).collect();
+                bytes.truncate(len);
+
+                return Ok(bytes);
+            }
+            // `filled` now holds the size the struct grew to
+            Err(NvmlError::InsufficientSize(_)) if filled as usize > len => size = filled,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(NvmlError::InsufficientSize(Some(size as usize)))
+}
+
+/// Call `f` with a pointer to a copy of `bytes` aligned for `T`.
+pub(crate) fn with_blob<T, R>(bytes: &[u8], f: impl FnOnce(*mut T) -> R) -> R {
+    let mut buffer = vec![0u64; (bytes.len() + 7) / 8];
+
+    unsafe {
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), buffer.as_mut_ptr() as *mut u8, bytes.len());
+    }
+
+    f(buffer.as_mut_ptr() as *mut T)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Simulates a list query whose list grows by one item on every call,
+    // until it reaches `max` items
+    fn growing_list(max: u32) -> impl FnMut(&mut u32, *mut u32) -> nvmlReturn_t {
+        let mut len = 0;
+
+        move |count, items| {
+            len = (len + 1).min(max);
+
+            if items.is_null() || *count < len {
+                *count = len;
+                return nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE;
+            }
+
+            for i in 0..len {
+                unsafe { *items.add(i as usize) = i }
+            }
+            *count = len;
+            nvmlReturn_enum_NVML_SUCCESS
+        }
+    }
+
+    #[test]
+    fn list_query_retries_when_list_grows() {
+        assert_eq!(nvml_try_list(0, growing_list(3)).unwrap(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn list_query_gives_up() {
+        let expected = LIST_QUERY_RETRIES as usize + 2;
+
+        assert!(matches!(
+            nvml_try_list(0, growing_list(100)),
+            Err(NvmlError::InsufficientSize(Some(count))) if count == expected
+        ));
+    }
+
+    // Simulates a query for a struct that grows by 8 bytes on every call,
+    // until it reaches `max` bytes
+    fn growing_blob(max: c_uint) -> impl FnMut(*mut u64, &mut c_uint) -> nvmlReturn_t {
+        let mut len = 8;
+
+        move |blob, size| {
+            len = (len + 8).min(max);
+
+            if blob.is_null() || *size < len {
+                *size = len;
+                return nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE;
+            }
+
+            assert_eq!(blob as usize % mem::align_of::<u64>(), 0);
+            unsafe { std::ptr::write_bytes(blob as *mut u8, 0xab, len as usize) }
+            *size = len;
+            nvmlReturn_enum_NVML_SUCCESS
+        }
+    }
+
+    #[test]
+    fn blob_query_retries_when_blob_grows() {
+        assert_eq!(nvml_try_blob(growing_blob(24)).unwrap(), vec![0xab; 24]);
+    }
+
+    #[test]
+    fn blob_query_gives_up() {
+        let expected = 8 * (LIST_QUERY_RETRIES as usize + 3);
+
+        assert!(matches!(
+            nvml_try_blob(growing_blob(1000)),
+            Err(NvmlError::InsufficientSize(Some(size))) if size == expected
+        ));
+    }
+
+    #[test]
+    fn blob_query_stops_when_blob_does_not_grow() {
+        let mut calls = 0;
+
+        let result = nvml_try_blob(|_: *mut u64, size| {
+            calls += 1;
+            *size = 16;
+            nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE
+        });
+
+        assert!(matches!(result, Err(NvmlError::InsufficientSize(_))));
+        // The sizing call and a single fetch
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn with_blob_aligns_copy() {
+        let first = with_blob(&[1, 2, 3], |blob: *mut u64| {
+            assert_eq!(blob as usize % mem::align_of::<u64>(), 0);
+            unsafe { *(blob as *const u8) }
+        });
+
+        assert_eq!(first, 1);
+    }
+}
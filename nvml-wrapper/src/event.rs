@@ -3,9 +3,23 @@ use crate::ffi::bindings::*;
 use crate::Nvml;
 
 use std::mem;
+use std::time::{Duration, Instant};
 
 use crate::struct_wrappers::event::EventData;
 
+#[cfg(feature = "tokio")]
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    thread,
+};
+#[cfg(feature = "tokio")]
+use tokio::sync::mpsc;
+
+/// How long the threads behind an `EventStream` and a `BackgroundEventLoop`
+/// wait for each event before checking whether they should stop.
+pub(crate) const BACKGROUND_WAIT_TIMEOUT_MS: u32 = 100;
+
 /**
 Handle to a set of events.
 
@@ -31,6 +45,12 @@ event_set.wait(5)?;
 # Ok(())
 # }
 ```
+
+Waiting on a background thread, with `.into_stream()` or
+`high_level::event_thread::BackgroundEventLoop`, takes an `EventSet<'static>`:
+the set must come from an `Nvml` that lives for the rest of the program (e.g.
+one stored in a `static` or leaked with `Box::leak()`), since the thread may
+outlive any borrow of it.
 */
 // Checked against local
 #[derive(Debug)]
@@ -122,6 +142,50 @@ impl<'nvml> EventSet<'nvml> {
         }
     }
 
+    /**
+    Like `.wait()`, but with the timeout given as a `Duration` and returning
+    `None` if no event arrived before it passed.
+
+    The timeout is rounded up to the next millisecond and saturates at
+    `u32::MAX` milliseconds. As with `.wait()`, this may return `None` before
+    the timeout passes.
+
+    # Errors
+
+    Any error other than `Timeout` returned by `.wait()`.
+    */
+    pub fn wait_opt(&self, timeout: Duration) -> Result<Option<EventData<'nvml>>, NvmlError> {
+        match self.wait(timeout_ms(timeout)) {
+            Ok(data) => Ok(Some(data)),
+            Err(NvmlError::Timeout) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /**
+    Wait for an event until `deadline`, returning `None` if none arrived by
+    then.
+
+    Unlike `.wait()`, this keeps waiting if NVML returns early (e.g. when an
+    interrupt arrives) before the deadline has passed. A deadline in the past
+    checks for a ready event without waiting.
+
+    # Errors
+
+    Any error other than `Timeout` returned by `.wait()`.
+    */
+    pub fn wait_deadline(&self, deadline: Instant) -> Result<Option<EventData<'nvml>>, NvmlError> {
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+
+            match self.wait_opt(remaining)? {
+                Some(data) => return Ok(Some(data)),
+                None if Instant::now() >= deadline => return Ok(None),
+                None => {}
+            }
+        }
+    }
+
     /// Get the raw device handle contained in this struct
     ///
     /// Sometimes necessary for C interop.
@@ -137,6 +201,88 @@ impl<'nvml> EventSet<'nvml> {
     }
 }
 
+#[cfg(feature = "tokio")]
+impl EventSet<'static> {
+    /**
+    Turn this set into a `futures_core::Stream` of the events it delivers.
+
+    The blocking `.wait()` runs in a loop on a dedicated thread, so the stream
+    can be polled from async code without blocking the executor. Timeouts are
+    not yielded. `Unknown` errors are yielded and waiting continues; any other
+    error is yielded and ends the stream, as further waiting will almost
+    certainly never succeed (see `high_level::event_loop`).
+
+    The thread, and with it this set, goes away shortly after the stream is
+    dropped. See `EventSet` for why the set must be `'static`.
+
+    Only available with the `tokio` feature.
+    */
+    pub fn into_stream(self) -> EventStream {
+        EventStream {
+            rx: spawn_waiter(move || self.wait(BACKGROUND_WAIT_TIMEOUT_MS)),
+        }
+    }
+}
+
+/**
+Run `wait` in a loop on a new thread, sending what it returns through a
+channel until the error policy of `EventSet::into_stream()` ends the loop or
+the receiver is dropped.
+*/
+#[cfg(feature = "tokio")]
+fn spawn_waiter<T, F>(mut wait: F) -> mpsc::UnboundedReceiver<Result<T, NvmlError>>
+where
+    T: Send + 'static,
+    F: FnMut() -> Result<T, NvmlError> + Send + 'static,
+{
+    let (tx, rx) = mpsc::unbounded_channel();
+
+    thread::spawn(move || loop {
+        let res = match wait() {
+            Err(NvmlError::Timeout) if tx.is_closed() => break,
+            Err(NvmlError::Timeout) => continue,
+            res => res,
+        };
+
+        let done = matches!(res, Err(ref e) if !matches!(e, NvmlError::Unknown));
+
+        if tx.send(res).is_err() || done {
+            break;
+        }
+    });
+
+    rx
+}
+
+/**
+A stream of the events delivered by an `EventSet`.
+
+Obtained from `EventSet::into_stream()`. Only available with the `tokio`
+feature.
+*/
+#[cfg(feature = "tokio")]
+#[derive(Debug)]
+pub struct EventStream {
+    rx: mpsc::UnboundedReceiver<Result<EventData<'static>, NvmlError>>,
+}
+
+#[cfg(feature = "tokio")]
+impl futures_core::Stream for EventStream {
+    type Item = Result<EventData<'static>, NvmlError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+/// Convert `timeout` to whole milliseconds for `EventSet::wait()`, rounding up
+/// so that sub-millisecond timeouts don't turn into busy loops.
+fn timeout_ms(timeout: Duration) -> u32 {
+    let ms = (timeout.as_nanos() + 999_999) / 1_000_000;
+
+    ms.min(u32::MAX as u128) as u32
+}
+
 /// This `Drop` implementation ignores errors! Use the `.release_events()`
 /// method on the `EventSet` struct if you care about handling them.
 impl<'nvml> Drop for EventSet<'nvml> {
@@ -151,8 +297,34 @@ impl<'nvml> Drop for EventSet<'nvml> {
 #[cfg(test)]
 #[cfg(target_os = "linux")]
 mod test {
+    use super::timeout_ms;
     use crate::bitmasks::event::*;
     use crate::test_utils::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn timeout_conversion() {
+        assert_eq!(timeout_ms(Duration::from_secs(0)), 0);
+        assert_eq!(timeout_ms(Duration::from_micros(1)), 1);
+        assert_eq!(timeout_ms(Duration::from_millis(1500)), 1500);
+        assert_eq!(timeout_ms(Duration::from_secs(u64::MAX)), u32::MAX);
+    }
+
+    #[test]
+    fn wait_deadline() {
+        let nvml = nvml();
+        let device = device(&nvml);
+        let set = nvml.create_event_set().expect("event set");
+        let set = device
+            .register_events(EventTypes::PSTATE_CHANGE | EventTypes::CLOCK_CHANGE, set)
+            .expect("registration");
+
+        let data = set
+            .wait_deadline(Instant::now() + Duration::from_millis(100))
+            .expect("no error");
+
+        print!("{:?} ...", data);
+    }
 
     #[test]
     fn release_events() {
@@ -196,4 +368,38 @@ mod test {
 
         print!("{:?} ...", data);
     }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn waiter_error_policy() {
+        use super::spawn_waiter;
+        use crate::error::NvmlError;
+
+        let mut results = vec![
+            Ok(1),
+            Err(NvmlError::Timeout),
+            Err(NvmlError::Unknown),
+            Ok(2),
+            Err(NvmlError::GpuLost),
+            Ok(3),
+        ]
+        .into_iter();
+
+        let mut rx = spawn_waiter(move || results.next().unwrap());
+        let mut received = vec![];
+
+        while let Some(res) = rx.blocking_recv() {
+            received.push(res);
+        }
+
+        assert!(matches!(
+            received[..],
+            [
+                Ok(1),
+                Err(NvmlError::Unknown),
+                Ok(2),
+                Err(NvmlError::GpuLost)
+            ]
+        ));
+    }
 }
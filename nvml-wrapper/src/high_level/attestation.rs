@@ -0,0 +1,108 @@
+/*!
+Gathering confidential compute attestation evidence.
+
+A remote verifier needs more than the attestation report to appraise a GPU:
+it checks the report against the certificate chains, the nonce it handed out
+and the driver and VBIOS versions it expects. `AttestationEvidence::collect()`
+(or `Device::attestation_evidence()`) gathers all of it in one struct, which
+can be serialized and sent as-is.
+
+```no_run
+use nvml_wrapper::Nvml;
+
+# fn main() -> Result<(), nvml_wrapper::error::NvmlError> {
+let nvml = Nvml::init()?;
+
+if !nvml.confidential_compute_capabilities()?.is_supported() {
+    return Ok(());
+}
+
+// The nonce must come from the verifier, so that it can check the report is
+// fresh
+let nonce = [0; 32];
+let evidence = nvml.device_by_index(0)?.attestation_evidence(nonce)?;
+
+println!("{} bytes of evidence for {}", evidence.attestation_report.len(), evidence.uuid);
+# Ok(())
+# }
+```
+*/
+
+use crate::error::NvmlError;
+use crate::ffi::bindings::NVML_CC_GPU_CEC_NONCE_SIZE;
+use crate::Device;
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+
+/// Everything a remote verifier needs to appraise a GPU.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AttestationEvidence {
+    /// The UUID of the device the evidence was gathered from.
+    pub uuid: String,
+    /// The version of the system's graphics driver.
+    pub driver_version: String,
+    pub vbios_version: String,
+    /// The nonce the attestation report was requested with.
+    pub nonce: [u8; NVML_CC_GPU_CEC_NONCE_SIZE as usize],
+    /// The device's certificate chain.
+    pub cert_chain: Vec<u8>,
+    /// The certificate chain the attestation report is signed with.
+    pub attestation_cert_chain: Vec<u8>,
+    pub attestation_report: Vec<u8>,
+    /// The CEC attestation report, if the device has one.
+    pub cec_attestation_report: Option<Vec<u8>>,
+}
+
+impl AttestationEvidence {
+    /**
+    Gather attestation evidence from `device`, requesting the attestation
+    report with `nonce`.
+
+    # Errors
+
+    Any error returned by `Device.uuid()`, `Device.vbios_version()`,
+    `Nvml.sys_driver_version()`,
+    `Device.confidential_compute_gpu_certificate()` or
+    `Device.confidential_compute_gpu_attestation_report()`.
+
+    # Device Support
+
+    Supports Hopper and newer fully supported devices.
+    */
+    pub fn collect(
+        device: &Device,
+        nonce: [u8; NVML_CC_GPU_CEC_NONCE_SIZE as usize],
+    ) -> Result<Self, NvmlError> {
+        let certificate = device.confidential_compute_gpu_certificate()?;
+        let report = device.confidential_compute_gpu_attestation_report(nonce)?;
+
+        Ok(Self {
+            uuid: device.uuid()?,
+            driver_version: device.nvml().sys_driver_version()?,
+            vbios_version: device.vbios_version()?,
+            nonce,
+            cert_chain: certificate.cert_chain,
+            attestation_cert_chain: certificate.attestation_cert_chain,
+            attestation_report: report.attestation_report,
+            cec_attestation_report: match report.is_cec_attestation_report_present {
+                true => Some(report.cec_attestation_report),
+                false => None,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::*;
+
+    #[test]
+    fn collect() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            AttestationEvidence::collect(device, [0; 32])
+        })
+    }
+}
@@ -0,0 +1,225 @@
+/*!
+Temporary clock changes that are undone when dropped.
+
+Benchmarks often lock clocks to a fixed range for reproducible numbers.
+`LockedClocksGuard` (or `Device::lock_clocks_scoped()`) locks the GPU clocks,
+and optionally the memory clocks, and resets them when it is dropped, so that
+a failing or panicking run cannot leave a device pinned.
+
+```no_run
+use nvml_wrapper::Nvml;
+
+# fn main() -> Result<(), nvml_wrapper::error::NvmlError> {
+let nvml = Nvml::init()?;
+let mut device = nvml.device_by_index(0)?;
+
+let mut guard = device.lock_clocks_scoped(1400, 1400)?;
+guard.lock_mem(5001, 5001)?;
+
+// Run the benchmark...
+
+guard.restore()?;
+# Ok(())
+# }
+```
+
+`ApplicationClocksGuard` (or `Device::set_applications_clocks_scoped()`) does
+the same for applications clocks, putting back the applications clocks that
+were set before, or the defaults:
+
+```no_run
+use nvml_wrapper::Nvml;
+
+# fn main() -> Result<(), nvml_wrapper::error::NvmlError> {
+let nvml = Nvml::init()?;
+let mut device = nvml.device_by_index(0)?;
+
+let guard = device.set_applications_clocks_scoped(5001, 1400)?;
+
+// Run the benchmark...
+
+guard.restore()?;
+# Ok(())
+# }
+```
+*/
+
+use crate::enum_wrappers::device::Clock;
+use crate::enums::device::GpuLockedClocksSetting;
+use crate::error::NvmlError;
+use crate::high_level::RestoreGuard;
+use crate::Device;
+
+/// Resets the locked GPU and memory clocks of a `Device` when dropped.
+#[derive(Debug)]
+pub struct LockedClocksGuard<'dev, 'nvml> {
+    // Whether the memory clocks are locked too
+    inner: RestoreGuard<&'dev mut Device<'nvml>, bool>,
+}
+
+impl<'dev, 'nvml> LockedClocksGuard<'dev, 'nvml> {
+    /**
+    Lock the GPU clocks of `device` to the range from `min_clock_mhz` to
+    `max_clock_mhz`.
+
+    # Errors
+
+    Any error returned by `Device::set_gpu_locked_clocks()`.
+    */
+    pub fn lock(
+        device: &'dev mut Device<'nvml>,
+        min_clock_mhz: u32,
+        max_clock_mhz: u32,
+    ) -> Result<Self, NvmlError> {
+        device.set_gpu_locked_clocks(GpuLockedClocksSetting::Numeric {
+            min_clock_mhz,
+            max_clock_mhz,
+        })?;
+
+        Ok(Self {
+            inner: RestoreGuard::new(device, false, |device, mem_locked| {
+                let gpu = device.reset_gpu_locked_clocks();
+                let mem = match mem_locked {
+                    true => device.reset_mem_locked_clocks(),
+                    false => Ok(()),
+                };
+
+                gpu.and(mem)
+            }),
+        })
+    }
+
+    /**
+    Also lock the memory clocks to the range from `min_clock_mhz` to
+    `max_clock_mhz`, resetting them along with the GPU clocks.
+
+    # Errors
+
+    Any error returned by `Device::set_mem_locked_clocks()`.
+    */
+    pub fn lock_mem(&mut self, min_clock_mhz: u32, max_clock_mhz: u32) -> Result<(), NvmlError> {
+        self.inner
+            .device_mut()
+            .set_mem_locked_clocks(min_clock_mhz, max_clock_mhz)?;
+        *self.inner.state_mut() = true;
+
+        Ok(())
+    }
+
+    /// The device whose clocks are locked.
+    pub fn device(&self) -> &Device<'nvml> {
+        self.inner.device()
+    }
+
+    /**
+    Reset the locked clocks now, reporting whether that worked.
+
+    Both the GPU and memory clocks are reset even if the first reset fails.
+
+    # Errors
+
+    The first error returned by `Device::reset_gpu_locked_clocks()` or
+    `Device::reset_mem_locked_clocks()`.
+    */
+    pub fn restore(self) -> Result<(), NvmlError> {
+        self.inner.restore()
+    }
+
+    /// Keep the clocks locked: drop the guard without resetting anything.
+    pub fn keep(self) {
+        self.inner.keep()
+    }
+}
+
+/// Restores the applications clocks of a `Device` when dropped.
+#[derive(Debug)]
+pub struct ApplicationClocksGuard<'dev, 'nvml> {
+    // `(mem_clock, graphics_clock)` to restore, or `None` to reset
+    inner: RestoreGuard<&'dev mut Device<'nvml>, Option<(u32, u32)>>,
+}
+
+impl<'dev, 'nvml> ApplicationClocksGuard<'dev, 'nvml> {
+    /**
+    Record the current applications clocks of `device`, then set them to
+    `mem_clock` and `graphics_clock` (in MHz).
+
+    # Errors
+
+    Any error returned by `Device::applications_clock()` or
+    `Device::set_applications_clocks()`. Nothing is changed if reading the
+    current clocks fails.
+    */
+    pub fn apply(
+        device: &'dev mut Device<'nvml>,
+        mem_clock: u32,
+        graphics_clock: u32,
+    ) -> Result<Self, NvmlError> {
+        let previous = (
+            device.applications_clock(Clock::Memory)?,
+            device.applications_clock(Clock::Graphics)?,
+        );
+
+        device.set_applications_clocks(mem_clock, graphics_clock)?;
+
+        Ok(Self::new(device, Some(previous)))
+    }
+
+    /**
+    Set the applications clocks of `device` to `mem_clock` and
+    `graphics_clock` (in MHz), resetting them to their defaults rather than
+    their current values when dropped.
+
+    # Errors
+
+    Any error returned by `Device::set_applications_clocks()`.
+    */
+    pub fn apply_resetting(
+        device: &'dev mut Device<'nvml>,
+        mem_clock: u32,
+        graphics_clock: u32,
+    ) -> Result<Self, NvmlError> {
+        device.set_applications_clocks(mem_clock, graphics_clock)?;
+
+        Ok(Self::new(device, None))
+    }
+
+    fn new(device: &'dev mut Device<'nvml>, previous: Option<(u32, u32)>) -> Self {
+        Self {
+            inner: RestoreGuard::new(device, previous, |device, previous| match previous {
+                Some((mem_clock, graphics_clock)) => {
+                    device.set_applications_clocks(mem_clock, graphics_clock)
+                }
+                None => device.reset_applications_clocks(),
+            }),
+        }
+    }
+
+    /// The device whose applications clocks are changed.
+    pub fn device(&self) -> &Device<'nvml> {
+        self.inner.device()
+    }
+
+    /// The `(mem_clock, graphics_clock)` that will be restored, or `None` if
+    /// the clocks will be reset to their defaults.
+    pub fn previous(&self) -> Option<(u32, u32)> {
+        *self.inner.state()
+    }
+
+    /**
+    Restore the recorded applications clocks now, reporting whether that
+    worked.
+
+    # Errors
+
+    Any error returned by `Device::set_applications_clocks()` or
+    `Device::reset_applications_clocks()`.
+    */
+    pub fn restore(self) -> Result<(), NvmlError> {
+        self.inner.restore()
+    }
+
+    /// Keep the applied clocks: drop the guard without restoring anything.
+    pub fn keep(self) {
+        self.inner.keep()
+    }
+}
@@ -0,0 +1,180 @@
+/*!
+Comparison of static device properties.
+
+Nodes are often expected to be made of identical GPUs. `DeviceComparison`
+(obtained from `Device::compare()`) lists the properties that differ between
+two devices so that heterogeneous nodes can be detected.
+*/
+
+use crate::enum_wrappers::device::Clock;
+use crate::enums::device::DeviceArchitecture;
+use crate::error::NvmlError;
+use crate::high_level::{active_nvlinks, supported};
+use crate::Device;
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+
+/// A static property of a `Device` that can be compared.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Property {
+    Architecture,
+    MemoryTotal,
+    MaxGraphicsClock,
+    MaxSmClock,
+    MaxMemoryClock,
+    MaxVideoClock,
+    NvLinkActiveLinks,
+    VbiosVersion,
+}
+
+/**
+The static properties of a `Device` that `Device::compare()` looks at.
+
+Properties the device does not support are `None`.
+*/
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DeviceProperties {
+    pub architecture: DeviceArchitecture,
+    /// Total installed memory, in bytes.
+    pub memory_total: u64,
+    /// Maximum graphics clock, in MHz.
+    pub max_graphics_clock: Option<u32>,
+    /// Maximum SM clock, in MHz.
+    pub max_sm_clock: Option<u32>,
+    /// Maximum memory clock, in MHz.
+    pub max_memory_clock: Option<u32>,
+    /// Maximum video clock, in MHz.
+    pub max_video_clock: Option<u32>,
+    /// The number of active NvLink links.
+    pub nvlink_active_links: Option<u32>,
+    pub vbios_version: Option<String>,
+}
+
+impl DeviceProperties {
+    /**
+    Read the static properties of the given `Device`.
+
+    # Errors
+
+    Any error other than `NotSupported` returned by the underlying `Device`
+    and `NvLink` calls.
+    */
+    pub fn of(device: &Device) -> Result<Self, NvmlError> {
+        let nvlink_active_links = active_nvlinks(device)?.map(|links| links.len() as u32);
+
+        Ok(Self {
+            architecture: device.architecture()?,
+            memory_total: device.memory_info()?.total,
+            max_graphics_clock: supported(device.max_clock_info(Clock::Graphics))?,
+            max_sm_clock: supported(device.max_clock_info(Clock::SM))?,
+            max_memory_clock: supported(device.max_clock_info(Clock::Memory))?,
+            max_video_clock: supported(device.max_clock_info(Clock::Video))?,
+            nvlink_active_links,
+            vbios_version: supported(device.vbios_version())?,
+        })
+    }
+}
+
+/// The result of comparing the static properties of two devices.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DeviceComparison {
+    /// The properties of the device `compare()` was called on.
+    pub left: DeviceProperties,
+    /// The properties of the device passed to `compare()`.
+    pub right: DeviceProperties,
+    /// The properties that differ, in `Property` declaration order.
+    pub differences: Vec<Property>,
+}
+
+impl DeviceComparison {
+    /// Compare two sets of already read properties.
+    pub fn new(left: DeviceProperties, right: DeviceProperties) -> Self {
+        let checks = [
+            (
+                Property::Architecture,
+                left.architecture == right.architecture,
+            ),
+            (
+                Property::MemoryTotal,
+                left.memory_total == right.memory_total,
+            ),
+            (
+                Property::MaxGraphicsClock,
+                left.max_graphics_clock == right.max_graphics_clock,
+            ),
+            (
+                Property::MaxSmClock,
+                left.max_sm_clock == right.max_sm_clock,
+            ),
+            (
+                Property::MaxMemoryClock,
+                left.max_memory_clock == right.max_memory_clock,
+            ),
+            (
+                Property::MaxVideoClock,
+                left.max_video_clock == right.max_video_clock,
+            ),
+            (
+                Property::NvLinkActiveLinks,
+                left.nvlink_active_links == right.nvlink_active_links,
+            ),
+            (
+                Property::VbiosVersion,
+                left.vbios_version == right.vbios_version,
+            ),
+        ];
+
+        let differences = checks
+            .iter()
+            .filter(|(_, same)| !same)
+            .map(|(property, _)| *property)
+            .collect();
+
+        Self {
+            left,
+            right,
+            differences,
+        }
+    }
+
+    /// Whether every compared property is the same on both devices.
+    pub fn is_identical(&self) -> bool {
+        self.differences.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn properties() -> DeviceProperties {
+        DeviceProperties {
+            architecture: DeviceArchitecture::Ampere,
+            memory_total: 80 * 1024 * 1024 * 1024,
+            max_graphics_clock: Some(1410),
+            max_sm_clock: Some(1410),
+            max_memory_clock: Some(1593),
+            max_video_clock: Some(1290),
+            nvlink_active_links: Some(12),
+            vbios_version: Some("92.00.45.00.06".into()),
+        }
+    }
+
+    #[test]
+    fn compare_properties() {
+        assert!(DeviceComparison::new(properties(), properties()).is_identical());
+
+        let mut right = properties();
+        right.nvlink_active_links = Some(10);
+        right.vbios_version = None;
+
+        let comparison = DeviceComparison::new(properties(), right);
+        assert_eq!(
+            comparison.differences,
+            vec![Property::NvLinkActiveLinks, Property::VbiosVersion]
+        );
+    }
+}
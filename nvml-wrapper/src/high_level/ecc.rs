@@ -0,0 +1,90 @@
+/*!
+Temporary ECC mode changes that are undone when dropped.
+
+ECC mode changes only take effect after the next reboot or GPU reset: setting
+it changes the *pending* mode, and the *current* mode follows once the device
+comes back. `EccModeGuard` (or `Device::set_ecc_scoped()`) records the pending
+mode, sets a new one and puts the recorded one back when it is dropped. If the
+device is not reset in between, this simply cancels the change; the current
+mode is never affected while the guard is alive.
+
+Use `EccModeState::is_change_pending()` to spot devices whose current and
+pending modes have drifted apart.
+
+```no_run
+use nvml_wrapper::Nvml;
+
+# fn main() -> Result<(), nvml_wrapper::error::NvmlError> {
+let nvml = Nvml::init()?;
+let mut device = nvml.device_by_index(0)?;
+
+let guard = device.set_ecc_scoped(false)?;
+assert!(!guard.device().is_ecc_enabled()?.pending_enabled);
+
+// Decide not to go through with it after all
+guard.restore()?;
+# Ok(())
+# }
+```
+*/
+
+use crate::error::NvmlError;
+use crate::high_level::RestoreGuard;
+use crate::Device;
+
+/// Restores the pending ECC mode of a `Device` when dropped.
+#[derive(Debug)]
+pub struct EccModeGuard<'dev, 'nvml> {
+    inner: RestoreGuard<&'dev mut Device<'nvml>, bool>,
+}
+
+impl<'dev, 'nvml> EccModeGuard<'dev, 'nvml> {
+    /**
+    Record the pending ECC mode of `device`, then set it to `enabled`.
+
+    The change only takes effect after the next reboot or GPU reset.
+
+    # Errors
+
+    Any error returned by `Device::is_ecc_enabled()` or `Device::set_ecc()`.
+    Nothing is changed if reading the pending mode fails.
+    */
+    pub fn set(device: &'dev mut Device<'nvml>, enabled: bool) -> Result<Self, NvmlError> {
+        let previous = device.is_ecc_enabled()?.pending_enabled;
+
+        device.set_ecc(enabled)?;
+
+        Ok(Self {
+            inner: RestoreGuard::new(device, previous, |device, previous| {
+                device.set_ecc(previous)
+            }),
+        })
+    }
+
+    /// The device whose ECC mode is changed.
+    pub fn device(&self) -> &Device<'nvml> {
+        self.inner.device()
+    }
+
+    /// The pending ECC mode that will be restored.
+    pub fn previous(&self) -> bool {
+        *self.inner.state()
+    }
+
+    /**
+    Restore the recorded pending ECC mode now, reporting whether that worked.
+
+    # Errors
+
+    Any error returned by `Device::set_ecc()`.
+    */
+    pub fn restore(self) -> Result<(), NvmlError> {
+        self.inner.restore()
+    }
+
+    /// Keep the new pending ECC mode: drop the guard without restoring
+    /// anything.
+    pub fn keep(self) {
+        self.inner.keep()
+    }
+}
@@ -0,0 +1,205 @@
+/*!
+Encoder session trends between polls.
+
+`Device::encoder_sessions()` returns moving averages of FPS and latency for
+each session at the time of the call. `EncoderSessionDiff` compares two such
+snapshots so that streaming QoS monitoring can tell which sessions started,
+ended, or got slower in between.
+*/
+
+use crate::error::NvmlError;
+use crate::struct_wrappers::device::EncoderSessionInfo;
+use crate::Device;
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How an encoder session present in both snapshots changed between them.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EncoderSessionDelta {
+    pub session_id: u32,
+    pub pid: u32,
+    /// The session's average FPS in the previous snapshot.
+    pub previous_fps: u32,
+    /// The session's average FPS in the current snapshot.
+    pub current_fps: u32,
+    /// The session's average latency in μs in the previous snapshot.
+    pub previous_latency: u32,
+    /// The session's average latency in μs in the current snapshot.
+    pub current_latency: u32,
+    /// Whether latency grew by more than the threshold given to
+    /// `EncoderSessionDiff::between()`.
+    pub latency_regressed: bool,
+}
+
+impl EncoderSessionDelta {
+    /// The change in average FPS (negative if the session got slower).
+    pub fn fps_change(&self) -> i64 {
+        self.current_fps as i64 - self.previous_fps as i64
+    }
+
+    /// The change in average latency in μs (positive if the session got slower).
+    pub fn latency_change(&self) -> i64 {
+        self.current_latency as i64 - self.previous_latency as i64
+    }
+}
+
+/// The difference between two snapshots of encoder sessions.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EncoderSessionDiff {
+    /// Sessions present in both snapshots, in current snapshot order.
+    pub continued: Vec<EncoderSessionDelta>,
+    /// Sessions only present in the current snapshot.
+    pub started: Vec<EncoderSessionInfo>,
+    /// Sessions only present in the previous snapshot.
+    pub ended: Vec<EncoderSessionInfo>,
+}
+
+impl EncoderSessionDiff {
+    /**
+    Compare two snapshots of encoder sessions.
+
+    Sessions are matched by session ID. A session is flagged as regressed when
+    its average latency grew by more than `latency_threshold_us` microseconds.
+    */
+    pub fn between(
+        previous: &[EncoderSessionInfo],
+        current: &[EncoderSessionInfo],
+        latency_threshold_us: u32,
+    ) -> Self {
+        let previous_by_id: HashMap<u32, &EncoderSessionInfo> =
+            previous.iter().map(|s| (s.session_id, s)).collect();
+
+        let mut diff = Self::default();
+
+        for session in current {
+            match previous_by_id.get(&session.session_id) {
+                Some(prev) => {
+                    let growth = session.average_latency.saturating_sub(prev.average_latency);
+
+                    diff.continued.push(EncoderSessionDelta {
+                        session_id: session.session_id,
+                        pid: session.pid,
+                        previous_fps: prev.average_fps,
+                        current_fps: session.average_fps,
+                        previous_latency: prev.average_latency,
+                        current_latency: session.average_latency,
+                        latency_regressed: growth > latency_threshold_us,
+                    })
+                }
+                None => diff.started.push(session.clone()),
+            }
+        }
+
+        let current_ids: Vec<u32> = current.iter().map(|s| s.session_id).collect();
+        diff.ended = previous
+            .iter()
+            .filter(|s| !current_ids.contains(&s.session_id))
+            .cloned()
+            .collect();
+
+        diff
+    }
+
+    /// The sessions whose latency regressed beyond the threshold.
+    pub fn regressed(&self) -> impl Iterator<Item = &EncoderSessionDelta> {
+        self.continued.iter().filter(|d| d.latency_regressed)
+    }
+}
+
+/// Polls a `Device`'s encoder sessions and diffs each poll against the last.
+#[derive(Debug, Clone)]
+pub struct EncoderSessionTracker {
+    latency_threshold_us: u32,
+    previous: Vec<EncoderSessionInfo>,
+}
+
+impl EncoderSessionTracker {
+    /// Create a tracker flagging latency growth above `latency_threshold_us`.
+    pub fn new(latency_threshold_us: u32) -> Self {
+        Self {
+            latency_threshold_us,
+            previous: vec![],
+        }
+    }
+
+    /// Diff `current` against the previously seen snapshot and remember it.
+    pub fn update(&mut self, current: Vec<EncoderSessionInfo>) -> EncoderSessionDiff {
+        let diff = EncoderSessionDiff::between(&self.previous, &current, self.latency_threshold_us);
+        self.previous = current;
+
+        diff
+    }
+
+    /**
+    Fetch the current encoder sessions from `device` and diff them against the
+    previous poll.
+
+    On the first poll every session is reported as started.
+
+    # Errors
+
+    Any error returned by `Device::encoder_sessions()`.
+    */
+    pub fn poll(&mut self, device: &Device) -> Result<EncoderSessionDiff, NvmlError> {
+        Ok(self.update(device.encoder_sessions()?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::enum_wrappers::device::EncoderType;
+
+    fn session(session_id: u32, average_fps: u32, average_latency: u32) -> EncoderSessionInfo {
+        EncoderSessionInfo {
+            session_id,
+            pid: 100 + session_id,
+            vgpu_instance: None,
+            codec_type: EncoderType::HEVC,
+            hres: 1920,
+            vres: 1080,
+            average_fps,
+            average_latency,
+        }
+    }
+
+    #[test]
+    fn diff_sessions() {
+        let previous = [
+            session(1, 60, 1000),
+            session(2, 60, 1000),
+            session(3, 30, 500),
+        ];
+        let current = [
+            session(1, 58, 1600),
+            session(2, 60, 1200),
+            session(4, 60, 900),
+        ];
+
+        let diff = EncoderSessionDiff::between(&previous, &current, 500);
+
+        assert_eq!(diff.continued.len(), 2);
+        assert_eq!(diff.continued[0].fps_change(), -2);
+        assert_eq!(diff.continued[0].latency_change(), 600);
+        assert_eq!(
+            diff.regressed().map(|d| d.session_id).collect::<Vec<_>>(),
+            vec![1]
+        );
+        assert_eq!(diff.started, vec![session(4, 60, 900)]);
+        assert_eq!(diff.ended, vec![session(3, 30, 500)]);
+    }
+
+    #[test]
+    fn tracker_remembers_previous_poll() {
+        let mut tracker = EncoderSessionTracker::new(0);
+
+        assert_eq!(tracker.update(vec![session(1, 60, 1000)]).started.len(), 1);
+
+        let diff = tracker.update(vec![session(1, 60, 1001)]);
+        assert!(diff.started.is_empty());
+        assert_eq!(diff.regressed().count(), 1);
+    }
+}
@@ -0,0 +1,411 @@
+/*!
+An event loop that runs on a background thread and dispatches events to
+callbacks.
+
+Register callbacks for the event types you care about on an `EventCallbacks`,
+then hand it to `BackgroundEventLoop::for_devices()` (or `::spawn()` with an
+`EventSet` you registered yourself). Events are waited on and dispatched on a
+dedicated thread until the loop is stopped or dropped.
+
+```no_run
+use nvml_wrapper::high_level::event_thread::{BackgroundEventLoop, EventCallbacks};
+use nvml_wrapper::Nvml;
+
+# fn main() -> Result<(), nvml_wrapper::error::NvmlErrorWithSource> {
+// The background thread may outlive any borrow, so `Nvml` must live for the
+// rest of the program
+let nvml: &'static Nvml = Box::leak(Box::new(Nvml::init()?));
+let devices = vec![nvml.device_by_index(0)?];
+
+let callbacks = EventCallbacks::new()
+    .on_xid(|device, xid| println!("{:?}: XID {:?}", device.index(), xid))
+    .on_ecc_error(|device, types| println!("{:?}: {:?}", device.index(), types))
+    .on_error(|e| eprintln!("event loop: {}", e));
+
+let event_loop = BackgroundEventLoop::for_devices(nvml, &devices, callbacks)?;
+
+// ... do other work ...
+
+let _set = event_loop.stop();
+# Ok(())
+# }
+```
+
+NVML only delivers events on Linux. Elsewhere the loop can still be spawned
+from an `EventSet`, but no devices can be registered with it and waiting
+fails, which ends the loop (see `EventCallbacks::on_error()`).
+*/
+
+use crate::bitmasks::event::EventTypes;
+use crate::enums::event::XidError;
+use crate::error::NvmlError;
+#[cfg(target_os = "linux")]
+use crate::error::NvmlErrorWithSource;
+use crate::event::BACKGROUND_WAIT_TIMEOUT_MS;
+use crate::high_level::row_remap::{RowRemappingAlert, RowRemappingWatcher, ROW_REMAPPING_EVENTS};
+use crate::struct_wrappers::event::EventData;
+use crate::Device;
+use crate::EventSet;
+#[cfg(target_os = "linux")]
+use crate::Nvml;
+use std::collections::HashMap;
+use std::fmt;
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+type EventCallback = Box<dyn FnMut(&EventData<'static>) + Send>;
+type ErrorCallback = Box<dyn FnMut(&NvmlError) + Send>;
+
+/// Callbacks to run for events, keyed by event type.
+#[derive(Default)]
+pub struct EventCallbacks {
+    callbacks: Vec<(EventTypes, EventCallback)>,
+    on_error: Option<ErrorCallback>,
+}
+
+impl fmt::Debug for EventCallbacks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventCallbacks")
+            .field(
+                "callbacks",
+                &self.callbacks.iter().map(|(t, _)| t).collect::<Vec<_>>(),
+            )
+            .field("on_error", &self.on_error.is_some())
+            .finish()
+    }
+}
+
+impl EventCallbacks {
+    /// Create an empty set of callbacks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+    Run `callback` for every event whose type intersects `types`.
+
+    Callbacks run on the background thread, in registration order. An event
+    matching several callbacks is passed to each of them.
+    */
+    pub fn on<F>(mut self, types: EventTypes, callback: F) -> Self
+    where
+        F: FnMut(&EventData<'static>) + Send + 'static,
+    {
+        self.callbacks.push((types, Box::new(callback)));
+        self
+    }
+
+    /// Run `callback` with the device and error for critical XID errors.
+    pub fn on_xid<F>(self, mut callback: F) -> Self
+    where
+        F: FnMut(&Device<'static>, &XidError) + Send + 'static,
+    {
+        self.on(EventTypes::CRITICAL_XID_ERROR, move |event| {
+            if let Some(ref xid) = event.event_data {
+                callback(&event.device, xid)
+            }
+        })
+    }
+
+    /// Run `callback` with the device for clock changes.
+    pub fn on_clock_change<F>(self, mut callback: F) -> Self
+    where
+        F: FnMut(&Device<'static>) + Send + 'static,
+    {
+        self.on(EventTypes::CLOCK_CHANGE, move |event| {
+            callback(&event.device)
+        })
+    }
+
+    /**
+    Run `callback` with the device and the ECC event types that occurred
+    (single and/or double bit) for ECC errors.
+    */
+    pub fn on_ecc_error<F>(self, mut callback: F) -> Self
+    where
+        F: FnMut(&Device<'static>, EventTypes) + Send + 'static,
+    {
+        let ecc = EventTypes::SINGLE_BIT_ECC_ERROR | EventTypes::DOUBLE_BIT_ECC_ERROR;
+
+        self.on(ecc, move |event| {
+            callback(&event.device, event.event_type & ecc)
+        })
+    }
+
+    /**
+    Run `callback` with the device and a `RowRemappingAlert` when row
+    remapping becomes pending or fails; see `high_level::row_remap`.
+
+    The remapped rows of a device are read whenever a double bit ECC error or
+    row remapping XID arrives for it, and each device is watched by its own
+    `RowRemappingWatcher`. Errors reading the remapped rows (other than
+    `NotSupported`, which is ignored) are passed to `callback` instead.
+    */
+    pub fn on_row_remapping<F>(self, mut callback: F) -> Self
+    where
+        F: FnMut(&Device<'static>, Result<RowRemappingAlert, NvmlError>) + Send + 'static,
+    {
+        let mut watchers: HashMap<u32, RowRemappingWatcher> = HashMap::new();
+
+        self.on(ROW_REMAPPING_EVENTS, move |event| {
+            if !RowRemappingWatcher::is_relevant(event) {
+                return;
+            }
+
+            let alert = event
+                .device
+                .index()
+                .and_then(|index| watchers.entry(index).or_default().poll(&event.device));
+
+            match alert {
+                Ok(Some(alert)) => callback(&event.device, Ok(alert)),
+                Ok(None) | Err(NvmlError::NotSupported) => {}
+                Err(e) => callback(&event.device, Err(e)),
+            }
+        })
+    }
+
+    /**
+    Run `callback` for errors returned while waiting for events.
+
+    Timeouts are not errors. After an `Unknown` error the loop keeps waiting;
+    any other error (e.g. `GpuLost` or `Uninitialized`) is passed to this
+    callback and then ends the loop, as further waiting will almost certainly
+    never succeed.
+    */
+    pub fn on_error<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(&NvmlError) + Send + 'static,
+    {
+        self.on_error = Some(Box::new(callback));
+        self
+    }
+
+    /// Every event type a callback is registered for.
+    pub fn event_types(&self) -> EventTypes {
+        self.callbacks
+            .iter()
+            .fold(EventTypes::empty(), |acc, (types, _)| acc | *types)
+    }
+
+    /// The positions of the callbacks `dispatch()` runs for an event of type
+    /// `event_type`.
+    fn matching(&self, event_type: EventTypes) -> Vec<usize> {
+        self.callbacks
+            .iter()
+            .enumerate()
+            .filter(|(_, (types, _))| types.intersects(event_type))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Run every callback registered for `event`'s type. Returns the number
+    /// of callbacks run.
+    pub fn dispatch(&mut self, event: &EventData<'static>) -> usize {
+        let matching = self.matching(event.event_type);
+
+        for &i in &matching {
+            (self.callbacks[i].1)(event);
+        }
+
+        matching.len()
+    }
+
+    /// Pass `error` to the error callback. Returns whether the loop should
+    /// keep waiting.
+    fn handle_error(&mut self, error: &NvmlError) -> bool {
+        if let Some(ref mut callback) = self.on_error {
+            callback(error);
+        }
+
+        matches!(error, NvmlError::Unknown)
+    }
+}
+
+/**
+Waits for events on a background thread and dispatches them to
+`EventCallbacks`.
+
+Dropping the loop stops the thread and waits for it to finish; use
+`.stop()` to get the `EventSet` back instead.
+*/
+#[derive(Debug)]
+pub struct BackgroundEventLoop {
+    stop: Arc<AtomicBool>,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<EventSet<'static>>>,
+}
+
+impl BackgroundEventLoop {
+    /**
+    Start dispatching the events delivered by `set` to `callbacks`.
+
+    See `EventSet` for why the set must be `'static`.
+    */
+    pub fn spawn(set: EventSet<'static>, mut callbacks: EventCallbacks) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let thread = {
+            let stop = stop.clone();
+            let running = running.clone();
+
+            thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let keep_going = match set.wait(BACKGROUND_WAIT_TIMEOUT_MS) {
+                        Ok(event) => {
+                            callbacks.dispatch(&event);
+                            true
+                        }
+                        Err(NvmlError::Timeout) => true,
+                        Err(e) => callbacks.handle_error(&e),
+                    };
+
+                    if !keep_going {
+                        break;
+                    }
+                }
+
+                running.store(false, Ordering::Relaxed);
+                set
+            })
+        };
+
+        Self {
+            stop,
+            running,
+            thread: Some(thread),
+        }
+    }
+
+    /**
+    Register every event type `callbacks` has a callback for with each of
+    `devices` (restricted to the types each device supports), then start
+    dispatching events.
+
+    # Errors
+
+    * Any error returned by `Nvml.create_event_set()`,
+      `Device.supported_event_types()` or `Device.register_events()`
+
+    # Platform Support
+
+    Only supports Linux.
+    */
+    #[cfg(target_os = "linux")]
+    pub fn for_devices(
+        nvml: &'static Nvml,
+        devices: &[Device<'static>],
+        callbacks: EventCallbacks,
+    ) -> Result<Self, NvmlErrorWithSource> {
+        let wanted = callbacks.event_types();
+        let mut set = nvml.create_event_set()?;
+
+        for device in devices {
+            let types = wanted & device.supported_event_types()?;
+
+            if !types.is_empty() {
+                set = device.register_events(types, set)?;
+            }
+        }
+
+        Ok(Self::spawn(set, callbacks))
+    }
+
+    /// Whether the background thread is still waiting for events. It stops
+    /// on its own after an error that ends the loop.
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::Relaxed)
+    }
+
+    /**
+    Stop the background thread, wait for it to finish and return the
+    `EventSet`.
+
+    Returns within roughly 100ms, unless a callback is still running.
+
+    # Panics
+
+    Resumes the panic if a callback panicked.
+    */
+    pub fn stop(mut self) -> EventSet<'static> {
+        self.join()
+            .expect("the thread is only taken by `stop()` and `drop()`")
+    }
+
+    fn join(&mut self) -> Option<EventSet<'static>> {
+        self.stop.store(true, Ordering::Relaxed);
+
+        self.thread
+            .take()
+            .map(|thread| thread.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+    }
+}
+
+impl Drop for BackgroundEventLoop {
+    fn drop(&mut self) {
+        // Don't panic while already panicking
+        if thread::panicking() {
+            self.stop.store(true, Ordering::Relaxed);
+        } else {
+            self.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn callbacks_match_event_types() {
+        let callbacks = EventCallbacks::new()
+            .on_xid(|_, _| {})
+            .on_clock_change(|_| {})
+            .on_ecc_error(|_, _| {})
+            .on(EventTypes::CLOCK_CHANGE | EventTypes::PSTATE_CHANGE, |_| {});
+
+        assert_eq!(
+            callbacks.event_types(),
+            EventTypes::CRITICAL_XID_ERROR
+                | EventTypes::CLOCK_CHANGE
+                | EventTypes::SINGLE_BIT_ECC_ERROR
+                | EventTypes::DOUBLE_BIT_ECC_ERROR
+                | EventTypes::PSTATE_CHANGE
+        );
+
+        assert_eq!(callbacks.matching(EventTypes::CLOCK_CHANGE), vec![1, 3]);
+        assert_eq!(
+            callbacks.matching(EventTypes::DOUBLE_BIT_ECC_ERROR | EventTypes::PSTATE_CHANGE),
+            vec![2, 3]
+        );
+        assert!(callbacks.matching(EventTypes::MIG_CONFIG_CHANGE).is_empty());
+    }
+
+    #[test]
+    fn row_remapping_event_types() {
+        let callbacks = EventCallbacks::new().on_row_remapping(|_, _| {});
+
+        assert_eq!(
+            callbacks.event_types(),
+            EventTypes::DOUBLE_BIT_ECC_ERROR | EventTypes::CRITICAL_XID_ERROR
+        );
+    }
+
+    #[test]
+    fn only_unknown_errors_keep_the_loop_going() {
+        let errors = Arc::new(std::sync::Mutex::new(vec![]));
+        let mut callbacks = {
+            let errors = errors.clone();
+            EventCallbacks::new().on_error(move |e| errors.lock().unwrap().push(e.to_string()))
+        };
+
+        assert!(callbacks.handle_error(&NvmlError::Unknown));
+        assert!(!callbacks.handle_error(&NvmlError::GpuLost));
+        assert_eq!(errors.lock().unwrap().len(), 2);
+
+        assert!(!EventCallbacks::new().handle_error(&NvmlError::Uninitialized));
+    }
+}
@@ -0,0 +1,614 @@
+/*!
+Device health reporting.
+
+This module turns the failure signals NVML exposes (XID errors, `GpuLost`,
+double-bit ECC errors and the driver's own recovery action field) into a
+`RecommendedAction`, following NVIDIA's published XID and recovery guidance.
+
+`HealthReport::read()` (or `Device::health_report()`) gathers the usual health
+checks of a fleet agent into one report with a summary `HealthStatus`. On
+Linux, `HealthReport::read_with_events()` (or
+`Device::health_report_with_events()`) also includes the critical XID errors
+delivered to an `EventSet`:
+
+```no_run
+use nvml_wrapper::Nvml;
+use nvml_wrapper::high_level::health::HealthStatus;
+
+# fn main() -> Result<(), nvml_wrapper::error::NvmlError> {
+let nvml = Nvml::init()?;
+let report = nvml.device_by_index(0)?.health_report()?;
+
+if report.status != HealthStatus::Healthy {
+    println!("{:?}: {:?}", report.action, report.signatures);
+}
+# Ok(())
+# }
+```
+*/
+
+use crate::bitmasks::device::ClocksEventReasons;
+use crate::enum_wrappers::device::{
+    EccCounter, MemoryError, RetirementCause, TemperatureSensor, TemperatureThreshold,
+};
+use crate::enums::device::SampleValue;
+use crate::enums::event::XidError;
+use crate::error::NvmlError;
+use crate::ffi::bindings::field_id::NVML_FI_DEV_GET_GPU_RECOVERY_ACTION;
+use crate::ffi::bindings::*;
+use crate::structs::device::{FieldId, RemappedRows};
+use crate::Device;
+#[cfg(target_os = "linux")]
+use crate::EventSet;
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+#[cfg(target_os = "linux")]
+use std::time::Duration;
+
+/**
+What an operator should do about a failure.
+
+Variants are ordered from least to most disruptive, so the most severe of
+several recommendations can be obtained with `Iterator::max()`.
+*/
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RecommendedAction {
+    /// Nothing needs to be done.
+    None,
+    /// The failure is transient or caused by the application; retry (or
+    /// restart the application).
+    Retry,
+    /// Reset the GPU once it is idle.
+    ResetGpu,
+    /// Drain workloads from the node (or from the GPU's peers) before resetting
+    /// the GPU.
+    DrainNode,
+    /// Reboot the node.
+    RebootNode,
+    /// The hardware is faulty; return the board to the vendor.
+    Rma,
+}
+
+/// A failure signal that a `RecommendedAction` can be derived from.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FailureSignature {
+    /// An XID error, as reported by a critical XID event.
+    Xid(XidError),
+    /// The GPU has fallen off the bus or is otherwise inaccessible.
+    GpuLost,
+    /// The driver requires the GPU to be reset.
+    ResetRequired,
+    /// The infoROM is corrupted.
+    CorruptedInfoRom,
+    /// Uncorrected (double-bit) ECC errors were counted.
+    DoubleBitEcc {
+        /// The number of errors.
+        count: u64,
+    },
+    /// Memory pages are pending retirement (or rows pending remapping).
+    PendingRetirement,
+    /// Remapping a memory row failed.
+    RowRemappingFailure,
+    /// The driver reported a value for its GPU recovery action field.
+    DriverRecoveryAction(u32),
+}
+
+impl FailureSignature {
+    /**
+    Get the failure signature an `NvmlError` corresponds to, if any.
+
+    Errors that say nothing about the health of the GPU (such as `NotSupported`
+    or `InvalidArg`) return `None`.
+    */
+    pub fn from_error(error: &NvmlError) -> Option<Self> {
+        match *error {
+            NvmlError::GpuLost => Some(FailureSignature::GpuLost),
+            NvmlError::ResetRequired => Some(FailureSignature::ResetRequired),
+            NvmlError::CorruptedInfoROM => Some(FailureSignature::CorruptedInfoRom),
+            _ => None,
+        }
+    }
+}
+
+impl RecommendedAction {
+    /**
+    The action recommended for an XID error.
+
+    XIDs that are known to be caused by applications (such as 13, 31 and 43)
+    map to `Retry`. XIDs this crate does not know about map to `None`; consult
+    NVIDIA's XID documentation for those.
+    */
+    pub fn for_xid(xid: &XidError) -> Self {
+        let value = match *xid {
+            XidError::Value(v) => v,
+            XidError::Unknown => return RecommendedAction::None,
+        };
+
+        match value {
+            // Application errors: graphics engine exception, MMU fault,
+            // channel errors, preemptive cleanup, contained ECC error
+            13 | 31 | 43 | 45 | 68 | 94 => RecommendedAction::Retry,
+            // Double-bit ECC error, page retirement / row remap event or
+            // failure, NvLink error, uncontained ECC error, GSP errors,
+            // unrecovered ECC error
+            48 | 63 | 64 | 74 | 95 | 119 | 120 | 140 => RecommendedAction::ResetGpu,
+            // Internal micro-controller breakpoint / halt
+            61 | 62 => RecommendedAction::ResetGpu,
+            // GPU has fallen off the bus
+            79 => RecommendedAction::RebootNode,
+            _ => RecommendedAction::None,
+        }
+    }
+
+    /**
+    The action recommended for a value of the driver's
+    `NVML_FI_DEV_GET_GPU_RECOVERY_ACTION` field.
+
+    Values this crate does not know about map to `ResetGpu`.
+    */
+    #[allow(non_upper_case_globals)]
+    pub fn for_driver_recovery_action(value: u32) -> Self {
+        match value {
+            nvmlDeviceGpuRecoveryAction_s_NVML_GPU_RECOVERY_ACTION_NONE => RecommendedAction::None,
+            nvmlDeviceGpuRecoveryAction_s_NVML_GPU_RECOVERY_ACTION_GPU_RESET => {
+                RecommendedAction::ResetGpu
+            }
+            nvmlDeviceGpuRecoveryAction_s_NVML_GPU_RECOVERY_ACTION_NODE_REBOOT => {
+                RecommendedAction::RebootNode
+            }
+            nvmlDeviceGpuRecoveryAction_s_NVML_GPU_RECOVERY_ACTION_DRAIN_P2P
+            | nvmlDeviceGpuRecoveryAction_s_NVML_GPU_RECOVERY_ACTION_DRAIN_AND_RESET => {
+                RecommendedAction::DrainNode
+            }
+            _ => RecommendedAction::ResetGpu,
+        }
+    }
+
+    /// The action recommended for the given failure signature.
+    pub fn for_signature(signature: &FailureSignature) -> Self {
+        match *signature {
+            FailureSignature::Xid(ref xid) => Self::for_xid(xid),
+            FailureSignature::GpuLost => RecommendedAction::RebootNode,
+            FailureSignature::ResetRequired => RecommendedAction::ResetGpu,
+            FailureSignature::CorruptedInfoRom => RecommendedAction::Rma,
+            FailureSignature::DoubleBitEcc { count: 0 } => RecommendedAction::None,
+            FailureSignature::DoubleBitEcc { .. } => RecommendedAction::ResetGpu,
+            FailureSignature::PendingRetirement => RecommendedAction::ResetGpu,
+            FailureSignature::RowRemappingFailure => RecommendedAction::Rma,
+            FailureSignature::DriverRecoveryAction(v) => Self::for_driver_recovery_action(v),
+        }
+    }
+
+    /**
+    The action recommended for an error returned by a call into NVML.
+
+    Errors that say nothing about the health of the GPU map to `None`, with the
+    exception of `Timeout` and `InUse`, which map to `Retry`.
+    */
+    pub fn for_error(error: &NvmlError) -> Self {
+        match FailureSignature::from_error(error) {
+            Some(signature) => Self::for_signature(&signature),
+            None => match *error {
+                NvmlError::Timeout | NvmlError::InUse => RecommendedAction::Retry,
+                _ => RecommendedAction::None,
+            },
+        }
+    }
+}
+
+/**
+Read the driver's own recovery recommendation for the given `Device`.
+
+This queries the `NVML_FI_DEV_GET_GPU_RECOVERY_ACTION` field.
+
+# Errors
+
+* `NotSupported`, if the driver does not expose this field
+* `UnexpectedVariant`, if the field holds a value of an unexpected type or
+  one that does not fit in a `u32`
+* Any error returned by `Device::field_values_for()`
+*/
+pub fn driver_recommended_action(device: &Device) -> Result<RecommendedAction, NvmlError> {
+    let sample = device
+        .field_values_for(&[FieldId(NVML_FI_DEV_GET_GPU_RECOVERY_ACTION)])?
+        .pop()
+        .ok_or(NvmlError::NotSupported)??;
+
+    let unexpected = NvmlError::UnexpectedVariant(NVML_FI_DEV_GET_GPU_RECOVERY_ACTION);
+
+    let action = match sample.value? {
+        SampleValue::U32(v) => v,
+        SampleValue::U64(v) => u32::try_from(v).map_err(|_| unexpected)?,
+        _ => return Err(unexpected),
+    };
+
+    Ok(RecommendedAction::for_driver_recovery_action(action))
+}
+
+/// The overall health of a device, from a `HealthReport`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum HealthStatus {
+    /// Nothing is wrong.
+    Healthy,
+    /// The device works but is throttled by its hardware, running at or
+    /// above its slowdown temperature, or has seen errors that only call for
+    /// a retry.
+    Degraded,
+    /// The device needs to be reset, drained, rebooted or replaced; see
+    /// `HealthReport::action`.
+    Unhealthy,
+}
+
+/// The result of the usual health checks on a device.
+///
+/// Each check is `None` (or empty) if the device doesn't support it or it
+/// failed with an error that is itself a failure signal, such as `GpuLost`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HealthReport {
+    /// Corrected ECC errors since the driver was last loaded.
+    pub corrected_ecc_errors: Option<u64>,
+    /// Uncorrected ECC errors since the driver was last loaded.
+    pub uncorrected_ecc_errors: Option<u64>,
+    /// The number of retired pages, for either cause.
+    pub retired_pages: Option<u32>,
+    /// Whether pages are pending retirement.
+    pub pages_pending_retirement: Option<bool>,
+    /// Remapped rows and their pending and failure flags.
+    pub remapped_rows: Option<RemappedRows>,
+    /// What is holding the clocks back right now.
+    pub clocks_event_reasons: Option<ClocksEventReasons>,
+    /// Critical XID errors taken from the event set, oldest first.
+    pub xids: Vec<XidError>,
+    /// The GPU temperature, in °C.
+    pub temperature: Option<u32>,
+    /// The temperature at which the GPU starts hardware slowdown, in °C.
+    pub slowdown_temperature: Option<u32>,
+    /// The temperature at which the GPU shuts down, in °C.
+    pub shutdown_temperature: Option<u32>,
+    /// The speed of each fan, as a percentage of its maximum.
+    pub fan_speeds: Vec<u32>,
+    /// The failure signals found by the checks.
+    pub signatures: Vec<FailureSignature>,
+    /// The most severe action recommended for `signatures`.
+    pub action: RecommendedAction,
+    /// The summary verdict.
+    pub status: HealthStatus,
+}
+
+// Clocks event reasons that mean the hardware is protecting itself.
+const SLOWDOWN_REASONS: ClocksEventReasons = ClocksEventReasons::HW_SLOWDOWN
+    .union(ClocksEventReasons::HW_THERMAL_SLOWDOWN)
+    .union(ClocksEventReasons::HW_POWER_BRAKE_SLOWDOWN)
+    .union(ClocksEventReasons::SW_THERMAL_SLOWDOWN);
+
+impl HealthReport {
+    /**
+    Run the health checks on `device`.
+
+    Checks the device doesn't support are left out, and checks failing with
+    an error that is itself a failure signal (see
+    `FailureSignature::from_error()`) are recorded as that signal.
+
+    # Errors
+
+    Any other error returned by the underlying `Device` queries.
+    */
+    pub fn read(device: &Device) -> Result<Self, NvmlError> {
+        let mut report = Self::check(device)?;
+        report.summarize();
+
+        Ok(report)
+    }
+
+    /**
+    Run the health checks on `device`, including the critical XID errors
+    waiting in `events`.
+
+    Every event waiting in the set is consumed and attributed to `device`,
+    so the set should be registered for `device` only.
+
+    # Errors
+
+    Any error returned by `HealthReport::read()` or `EventSet::wait_opt()`.
+
+    # Platform Support
+
+    Only supports Linux.
+    */
+    #[cfg(target_os = "linux")]
+    pub fn read_with_events(device: &Device, events: &EventSet) -> Result<Self, NvmlError> {
+        let mut report = Self::check(device)?;
+
+        while let Some(event) = events.wait_opt(Duration::ZERO)? {
+            report.xids.extend(event.event_data);
+        }
+
+        report.summarize();
+
+        Ok(report)
+    }
+
+    // Runs the checks, leaving the summary to `summarize()`.
+    fn check(device: &Device) -> Result<Self, NvmlError> {
+        let mut signatures = vec![];
+
+        let corrected_ecc_errors = checked(
+            device.total_ecc_errors(MemoryError::Corrected, EccCounter::Volatile),
+            &mut signatures,
+        )?;
+        let uncorrected_ecc_errors = checked(
+            device.total_ecc_errors(MemoryError::Uncorrected, EccCounter::Volatile),
+            &mut signatures,
+        )?;
+
+        let retired_pages = match (
+            checked(
+                device.retired_pages(RetirementCause::MultipleSingleBitEccErrors),
+                &mut signatures,
+            )?,
+            checked(
+                device.retired_pages(RetirementCause::DoubleBitEccError),
+                &mut signatures,
+            )?,
+        ) {
+            (None, None) => None,
+            (single, double) => {
+                Some((single.map_or(0, |p| p.len()) + double.map_or(0, |p| p.len())) as u32)
+            }
+        };
+
+        let pages_pending_retirement =
+            checked(device.are_pages_pending_retired(), &mut signatures)?;
+        let remapped_rows = checked(device.remapped_rows(), &mut signatures)?;
+        let clocks_event_reasons = checked(device.current_clocks_event_reasons(), &mut signatures)?;
+        let temperature = checked(device.temperature(TemperatureSensor::Gpu), &mut signatures)?;
+        let slowdown_temperature = checked(
+            device.temperature_threshold(TemperatureThreshold::Slowdown),
+            &mut signatures,
+        )?;
+        let shutdown_temperature = checked(
+            device.temperature_threshold(TemperatureThreshold::Shutdown),
+            &mut signatures,
+        )?;
+
+        let mut fan_speeds = vec![];
+
+        for fan in 0..checked(device.num_fans(), &mut signatures)?.unwrap_or(0) {
+            if let Some(speed) = checked(device.fan_speed(fan), &mut signatures)? {
+                fan_speeds.push(speed);
+            }
+        }
+
+        Ok(Self {
+            corrected_ecc_errors,
+            uncorrected_ecc_errors,
+            retired_pages,
+            pages_pending_retirement,
+            remapped_rows,
+            clocks_event_reasons,
+            xids: vec![],
+            temperature,
+            slowdown_temperature,
+            shutdown_temperature,
+            fan_speeds,
+            signatures,
+            action: RecommendedAction::None,
+            status: HealthStatus::Healthy,
+        })
+    }
+
+    // Derives the failure signatures of the checks, the recommended action
+    // and the status.
+    fn summarize(&mut self) {
+        if let Some(count @ 1..) = self.uncorrected_ecc_errors {
+            self.signatures
+                .push(FailureSignature::DoubleBitEcc { count });
+        }
+
+        let rows = self.remapped_rows.as_ref();
+
+        if self.pages_pending_retirement == Some(true) || rows.is_some_and(|r| r.pending) {
+            self.signatures.push(FailureSignature::PendingRetirement);
+        }
+
+        if rows.is_some_and(|r| r.failure_occurred) {
+            self.signatures.push(FailureSignature::RowRemappingFailure);
+        }
+
+        self.signatures
+            .extend(self.xids.iter().cloned().map(FailureSignature::Xid));
+
+        self.action = self
+            .signatures
+            .iter()
+            .map(RecommendedAction::for_signature)
+            .max()
+            .unwrap_or(RecommendedAction::None);
+
+        let slowing_down = self
+            .clocks_event_reasons
+            .is_some_and(|r| r.intersects(SLOWDOWN_REASONS));
+        let overheating = matches!(
+            (self.temperature, self.slowdown_temperature),
+            (Some(t), Some(limit)) if t >= limit
+        );
+
+        self.status = match self.action {
+            RecommendedAction::None if !slowing_down && !overheating => HealthStatus::Healthy,
+            RecommendedAction::None | RecommendedAction::Retry => HealthStatus::Degraded,
+            _ => HealthStatus::Unhealthy,
+        };
+    }
+}
+
+// `NotSupported` and errors that are failure signals become `None`.
+fn checked<T>(
+    res: Result<T, NvmlError>,
+    signatures: &mut Vec<FailureSignature>,
+) -> Result<Option<T>, NvmlError> {
+    match res {
+        Ok(value) => Ok(Some(value)),
+        Err(NvmlError::NotSupported) => Ok(None),
+        Err(e) => match FailureSignature::from_error(&e) {
+            Some(signature) => {
+                if !signatures.contains(&signature) {
+                    signatures.push(signature);
+                }
+
+                Ok(None)
+            }
+            None => Err(e),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::*;
+
+    #[test]
+    fn xid_actions() {
+        assert_eq!(
+            RecommendedAction::for_xid(&XidError::Value(13)),
+            RecommendedAction::Retry
+        );
+        assert_eq!(
+            RecommendedAction::for_xid(&XidError::Value(48)),
+            RecommendedAction::ResetGpu
+        );
+        assert_eq!(
+            RecommendedAction::for_xid(&XidError::Value(79)),
+            RecommendedAction::RebootNode
+        );
+        assert_eq!(
+            RecommendedAction::for_xid(&XidError::Unknown),
+            RecommendedAction::None
+        );
+    }
+
+    #[test]
+    fn error_actions() {
+        assert_eq!(
+            RecommendedAction::for_error(&NvmlError::GpuLost),
+            RecommendedAction::RebootNode
+        );
+        assert_eq!(
+            RecommendedAction::for_error(&NvmlError::CorruptedInfoROM),
+            RecommendedAction::Rma
+        );
+        assert_eq!(
+            RecommendedAction::for_error(&NvmlError::NotSupported),
+            RecommendedAction::None
+        );
+    }
+
+    #[test]
+    fn most_severe() {
+        let worst = [
+            FailureSignature::DoubleBitEcc { count: 2 },
+            FailureSignature::Xid(XidError::Value(13)),
+            FailureSignature::GpuLost,
+        ]
+        .iter()
+        .map(RecommendedAction::for_signature)
+        .max();
+
+        assert_eq!(worst, Some(RecommendedAction::RebootNode));
+    }
+
+    fn report() -> HealthReport {
+        HealthReport {
+            corrected_ecc_errors: Some(3),
+            uncorrected_ecc_errors: Some(0),
+            retired_pages: Some(0),
+            pages_pending_retirement: Some(false),
+            remapped_rows: None,
+            clocks_event_reasons: Some(ClocksEventReasons::GPU_IDLE),
+            xids: vec![],
+            temperature: Some(40),
+            slowdown_temperature: Some(90),
+            shutdown_temperature: Some(95),
+            fan_speeds: vec![30],
+            signatures: vec![],
+            action: RecommendedAction::None,
+            status: HealthStatus::Healthy,
+        }
+    }
+
+    #[test]
+    fn report_verdict() {
+        let mut healthy = report();
+        healthy.summarize();
+
+        assert_eq!(healthy.status, HealthStatus::Healthy);
+        assert!(healthy.signatures.is_empty());
+
+        let mut hot = report();
+        hot.temperature = Some(91);
+        hot.summarize();
+
+        assert_eq!(hot.action, RecommendedAction::None);
+        assert_eq!(hot.status, HealthStatus::Degraded);
+
+        let mut failing = report();
+        failing.uncorrected_ecc_errors = Some(1);
+        failing.xids = vec![XidError::Value(13)];
+        failing.remapped_rows = Some(RemappedRows {
+            correctable: 0,
+            uncorrectable: 1,
+            pending: true,
+            failure_occurred: true,
+        });
+        failing.summarize();
+
+        assert_eq!(
+            failing.signatures,
+            vec![
+                FailureSignature::DoubleBitEcc { count: 1 },
+                FailureSignature::PendingRetirement,
+                FailureSignature::RowRemappingFailure,
+                FailureSignature::Xid(XidError::Value(13)),
+            ]
+        );
+        assert_eq!(failing.action, RecommendedAction::Rma);
+        assert_eq!(failing.status, HealthStatus::Unhealthy);
+    }
+
+    #[test]
+    fn checks_record_failure_signals() {
+        let mut signatures = vec![];
+
+        assert!(matches!(checked(Ok(1), &mut signatures), Ok(Some(1))));
+        assert!(matches!(
+            checked::<u32>(Err(NvmlError::NotSupported), &mut signatures),
+            Ok(None)
+        ));
+
+        for _ in 0..2 {
+            assert!(matches!(
+                checked::<u32>(Err(NvmlError::GpuLost), &mut signatures),
+                Ok(None)
+            ));
+        }
+
+        assert!(checked::<u32>(Err(NvmlError::InvalidArg), &mut signatures).is_err());
+        assert_eq!(signatures, vec![FailureSignature::GpuLost]);
+    }
+
+    #[test]
+    fn health_report() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.health_report())
+    }
+
+    #[test]
+    fn driver_recommended_action() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, super::driver_recommended_action)
+    }
+}
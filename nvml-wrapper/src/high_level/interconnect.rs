@@ -0,0 +1,335 @@
+/*!
+Combined PCIe and NvLink bandwidth metering.
+
+PCIe throughput is reported by NVML as a rate, while NvLink throughput is
+reported as ever-increasing per-link data counters. `InterconnectMeter` reads
+both together on a fixed tick and turns them into achieved bandwidth in bytes
+per second, per link and in total.
+
+```no_run
+use nvml_wrapper::Nvml;
+use nvml_wrapper::high_level::interconnect::InterconnectMeter;
+use std::time::Duration;
+
+# fn main() -> Result<(), nvml_wrapper::error::NvmlError> {
+let nvml = Nvml::init()?;
+let device = nvml.device_by_index(0)?;
+
+let mut meter = InterconnectMeter::for_device(&device, Duration::from_secs(1))?;
+
+loop {
+    let sample = meter.next(&device)?;
+    println!("{:.0} B/s off-chip", sample.total_bytes_per_sec());
+#   break;
+}
+# Ok(())
+# }
+```
+*/
+
+use crate::enum_wrappers::device::PcieUtilCounter;
+use crate::error::NvmlError;
+use crate::ffi::bindings::field_id::{
+    NVML_FI_DEV_NVLINK_THROUGHPUT_DATA_RX, NVML_FI_DEV_NVLINK_THROUGHPUT_DATA_TX,
+};
+use crate::high_level::{active_nvlinks, supported};
+use crate::structs::device::FieldId;
+use crate::Device;
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The raw interconnect readings taken at one point in time.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InterconnectCounters {
+    /// PCIe transmit throughput in KiB/s, if supported.
+    pub pcie_tx: Option<u32>,
+    /// PCIe receive throughput in KiB/s, if supported.
+    pub pcie_rx: Option<u32>,
+    /// NvLink data counters as `(link, transmitted KiB, received KiB)`.
+    pub nvlink: Vec<(u32, u64, u64)>,
+}
+
+impl InterconnectCounters {
+    /**
+    Read PCIe throughput and the NvLink data counters of `links` from `device`.
+
+    # Errors
+
+    * `NotSupported`, if a link's counters are not supported
+    * Any error other than `NotSupported` returned by
+      `Device::pcie_throughput()`, and any error returned by
+      `Device::scoped_field_values_for()`
+    */
+    pub fn read(device: &Device, links: &[u32]) -> Result<Self, NvmlError> {
+        let pcie_tx = supported(device.pcie_throughput(PcieUtilCounter::Send))?;
+        let pcie_rx = supported(device.pcie_throughput(PcieUtilCounter::Receive))?;
+
+        let mut nvlink = Vec::with_capacity(links.len());
+
+        if !links.is_empty() {
+            let ids: Vec<(FieldId, u32)> = links
+                .iter()
+                .flat_map(|&link| {
+                    [
+                        (FieldId(NVML_FI_DEV_NVLINK_THROUGHPUT_DATA_TX), link),
+                        (FieldId(NVML_FI_DEV_NVLINK_THROUGHPUT_DATA_RX), link),
+                    ]
+                })
+                .collect();
+
+            let mut samples = device.scoped_field_values_for(&ids)?.into_iter();
+
+            for &link in links {
+                let mut next = || -> Result<u64, NvmlError> {
+                    let sample = samples.next().ok_or(NvmlError::NotSupported)??;
+                    Ok(sample.value?.as_f64() as u64)
+                };
+
+                nvlink.push((link, next()?, next()?));
+            }
+        }
+
+        Ok(Self {
+            pcie_tx,
+            pcie_rx,
+            nvlink,
+        })
+    }
+}
+
+/// Achieved bandwidth on one NvLink link.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LinkBandwidth {
+    pub link: u32,
+    pub tx_bytes_per_sec: f64,
+    pub rx_bytes_per_sec: f64,
+}
+
+/// Achieved interconnect bandwidth over one tick.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InterconnectSample {
+    /// The time the bandwidth was averaged over.
+    pub interval: Duration,
+    /// PCIe transmit bandwidth, if supported.
+    ///
+    /// NVML measures PCIe throughput over a short (20ms) window at the end of
+    /// the tick rather than over the whole tick.
+    pub pcie_tx_bytes_per_sec: Option<f64>,
+    /// PCIe receive bandwidth, if supported.
+    pub pcie_rx_bytes_per_sec: Option<f64>,
+    /// Bandwidth of each metered NvLink link.
+    pub nvlink: Vec<LinkBandwidth>,
+}
+
+impl InterconnectSample {
+    /// Total NvLink bandwidth, both directions, across all metered links.
+    pub fn nvlink_bytes_per_sec(&self) -> f64 {
+        self.nvlink
+            .iter()
+            .map(|l| l.tx_bytes_per_sec + l.rx_bytes_per_sec)
+            .sum()
+    }
+
+    /// Total PCIe bandwidth, both directions (zero if unsupported).
+    pub fn pcie_bytes_per_sec(&self) -> f64 {
+        self.pcie_tx_bytes_per_sec.unwrap_or(0.0) + self.pcie_rx_bytes_per_sec.unwrap_or(0.0)
+    }
+
+    /// Total off-chip bandwidth over PCIe and NvLink, both directions.
+    pub fn total_bytes_per_sec(&self) -> f64 {
+        self.pcie_bytes_per_sec() + self.nvlink_bytes_per_sec()
+    }
+}
+
+/// Samples PCIe and NvLink bandwidth of a `Device` on a fixed tick.
+#[derive(Debug, Clone)]
+pub struct InterconnectMeter {
+    links: Vec<u32>,
+    tick: Duration,
+    previous: Option<(Instant, InterconnectCounters)>,
+}
+
+impl InterconnectMeter {
+    /// Create a meter for the given NvLink `links`, sampling every `tick`.
+    pub fn new(links: Vec<u32>, tick: Duration) -> Self {
+        Self {
+            links,
+            tick,
+            previous: None,
+        }
+    }
+
+    /**
+    Create a meter for every active NvLink link of `device`, sampling every
+    `tick`.
+
+    Devices without NvLink support get a meter for PCIe only.
+
+    # Errors
+
+    Any error other than `NotSupported` and `InvalidArg` returned by
+    `NvLink::is_active()`.
+    */
+    pub fn for_device(device: &Device, tick: Duration) -> Result<Self, NvmlError> {
+        let links = active_nvlinks(device)?.unwrap_or_default();
+
+        Ok(Self::new(links, tick))
+    }
+
+    /// The NvLink links this meter reads.
+    pub fn links(&self) -> &[u32] {
+        &self.links
+    }
+
+    /**
+    Feed counters read at `at` through the meter.
+
+    Returns `None` for the first readings, which only set the baseline. NvLink
+    counters that went backwards (e.g. after being reset) count as no traffic.
+    */
+    pub fn update(
+        &mut self,
+        at: Instant,
+        counters: InterconnectCounters,
+    ) -> Option<InterconnectSample> {
+        let sample = self.previous.as_ref().map(|(prev_at, prev)| {
+            let interval = at.saturating_duration_since(*prev_at);
+            let secs = interval.as_secs_f64();
+            let rate = |prev: u64, cur: u64| match secs {
+                s if s > 0.0 => cur.saturating_sub(prev) as f64 * 1024.0 / s,
+                _ => 0.0,
+            };
+
+            let nvlink = counters
+                .nvlink
+                .iter()
+                .filter_map(|&(link, tx, rx)| {
+                    prev.nvlink
+                        .iter()
+                        .find(|(l, _, _)| *l == link)
+                        .map(|&(_, prev_tx, prev_rx)| LinkBandwidth {
+                            link,
+                            tx_bytes_per_sec: rate(prev_tx, tx),
+                            rx_bytes_per_sec: rate(prev_rx, rx),
+                        })
+                })
+                .collect();
+
+            InterconnectSample {
+                interval,
+                pcie_tx_bytes_per_sec: counters.pcie_tx.map(|kib| kib as f64 * 1024.0),
+                pcie_rx_bytes_per_sec: counters.pcie_rx.map(|kib| kib as f64 * 1024.0),
+                nvlink,
+            }
+        });
+
+        self.previous = Some((at, counters));
+
+        sample
+    }
+
+    /**
+    Read the counters from `device` now and feed them through the meter.
+
+    # Errors
+
+    Any error returned by `InterconnectCounters::read()`.
+    */
+    pub fn poll(&mut self, device: &Device) -> Result<Option<InterconnectSample>, NvmlError> {
+        let counters = InterconnectCounters::read(device, &self.links)?;
+
+        Ok(self.update(Instant::now(), counters))
+    }
+
+    /**
+    Block until the next tick and return the bandwidth achieved since the
+    previous one.
+
+    The first call takes a baseline reading and waits a full tick.
+
+    # Errors
+
+    Any error returned by `InterconnectCounters::read()`.
+    */
+    pub fn next(&mut self, device: &Device) -> Result<InterconnectSample, NvmlError> {
+        loop {
+            if let Some((at, _)) = self.previous {
+                let elapsed = at.elapsed();
+
+                if elapsed < self.tick {
+                    thread::sleep(self.tick - elapsed);
+                }
+            }
+
+            if let Some(sample) = self.poll(device)? {
+                return Ok(sample);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::*;
+
+    #[test]
+    fn bandwidth_from_counters() {
+        let start = Instant::now();
+        let mut meter = InterconnectMeter::new(vec![0, 1], Duration::from_secs(1));
+
+        let baseline = InterconnectCounters {
+            pcie_tx: Some(100),
+            pcie_rx: None,
+            nvlink: vec![(0, 1000, 2000), (1, 500, 500)],
+        };
+        assert_eq!(meter.update(start, baseline), None);
+
+        let sample = meter
+            .update(
+                start + Duration::from_secs(2),
+                InterconnectCounters {
+                    pcie_tx: Some(200),
+                    pcie_rx: None,
+                    nvlink: vec![(0, 3000, 2000), (1, 100, 900)],
+                },
+            )
+            .unwrap();
+
+        assert_eq!(sample.interval, Duration::from_secs(2));
+        assert_eq!(sample.pcie_tx_bytes_per_sec, Some(200.0 * 1024.0));
+        assert_eq!(
+            sample.nvlink,
+            vec![
+                LinkBandwidth {
+                    link: 0,
+                    tx_bytes_per_sec: 1000.0 * 1024.0,
+                    rx_bytes_per_sec: 0.0,
+                },
+                // The TX counter went backwards
+                LinkBandwidth {
+                    link: 1,
+                    tx_bytes_per_sec: 0.0,
+                    rx_bytes_per_sec: 200.0 * 1024.0,
+                },
+            ]
+        );
+        assert_eq!(
+            sample.total_bytes_per_sec(),
+            (200.0 + 1000.0 + 200.0) * 1024.0
+        );
+    }
+
+    #[test]
+    fn next() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            InterconnectMeter::for_device(device, Duration::from_millis(50))?.next(device)
+        })
+    }
+}
@@ -0,0 +1,672 @@
+/*!
+Helpers for working with MIG (Multi-Instance GPU) devices.
+
+MIG devices are represented by regular `Device` handles obtained from their
+parent via `Device::mig_device_by_index()` or `Device::mig_devices()`. Most
+`Device` methods work on them, but a few (such as `utilization_rates()`) are
+not supported by the driver when called on a MIG handle.
+*/
+
+use crate::error::NvmlError;
+#[cfg(target_os = "linux")]
+use crate::ffi::bindings::{NVML_DEVICE_MIG_ENABLE, NVML_GPU_INSTANCE_PROFILE_COUNT};
+use crate::struct_wrappers::device::{
+    GpuInstancePlacement, GpuInstanceProfileInfo, MemoryInfo, ProcessInfo, Utilization,
+};
+use crate::Device;
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use thiserror::Error;
+
+/// Metrics for a single MIG device, gathered in one call.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MigDeviceStats {
+    /// The UUID of the MIG device (in the `MIG-...` form).
+    pub uuid: String,
+    /// The GPU instance this MIG device belongs to.
+    pub gpu_instance_id: u32,
+    /// The compute instance this MIG device belongs to.
+    pub compute_instance_id: u32,
+    /// Memory usage of the MIG device's memory slice.
+    pub memory: MemoryInfo,
+    /// Utilization rates, if the driver reports them for MIG devices.
+    ///
+    /// This is `None` when `utilization_rates()` returns `NotSupported`, which
+    /// is the case for MIG handles on current drivers.
+    pub utilization: Option<Utilization>,
+    /// Compute processes running on this MIG device.
+    pub compute_processes: Vec<ProcessInfo>,
+}
+
+impl MigDeviceStats {
+    /**
+    Gather metrics for the given MIG device.
+
+    # Errors
+
+    * `NotSupported`, if `device` is not a MIG device handle
+    * Any error returned by the underlying `Device` calls; `NotSupported`
+      from `utilization_rates()` is not treated as an error
+    */
+    pub fn collect(device: &Device) -> Result<Self, NvmlError> {
+        let utilization = match device.utilization_rates() {
+            Ok(u) => Some(u),
+            Err(NvmlError::NotSupported) => None,
+            Err(e) => return Err(e),
+        };
+
+        Ok(Self {
+            uuid: device.uuid()?,
+            gpu_instance_id: device.mig_gpu_instance_id()?,
+            compute_instance_id: device.mig_compute_instance_id()?,
+            memory: device.memory_info()?,
+            utilization,
+            compute_processes: device.running_compute_processes()?,
+        })
+    }
+
+    /**
+    Gather metrics for every MIG device on the given parent `Device`.
+
+    # Errors
+
+    Any error returned by `Device::mig_devices()` or `MigDeviceStats::collect()`.
+    */
+    pub fn collect_all(parent: &Device) -> Result<Vec<Self>, NvmlError> {
+        parent.mig_devices()?.iter().map(Self::collect).collect()
+    }
+}
+
+/// The identity of a MIG device: its UUID and the instance IDs it is made of.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MigSlice {
+    /// The UUID of the MIG device (in the `MIG-...` form).
+    pub uuid: String,
+    pub gpu_instance_id: u32,
+    pub compute_instance_id: u32,
+}
+
+impl MigSlice {
+    /**
+    Read the identity of the given MIG device.
+
+    # Errors
+
+    * `NotSupported`, if `device` is not a MIG device handle
+    * Any error returned by `Device::uuid()`
+    */
+    pub fn of(device: &Device) -> Result<Self, NvmlError> {
+        Ok(Self {
+            uuid: device.uuid()?,
+            gpu_instance_id: device.mig_gpu_instance_id()?,
+            compute_instance_id: device.mig_compute_instance_id()?,
+        })
+    }
+}
+
+/**
+A lookup table between MIG device UUIDs, (GPU instance ID, compute instance ID)
+pairs and `ProcessInfo`s.
+
+Processes listed on a parent `Device` carry the GPU and compute instance IDs
+they run on; this index turns those into the MIG device they belong to.
+*/
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct MigSliceIndex {
+    slices: Vec<MigSlice>,
+    by_uuid: HashMap<String, usize>,
+    by_ids: HashMap<(u32, u32), usize>,
+}
+
+impl MigSliceIndex {
+    /**
+    Build an index of every MIG device on the given parent `Device`.
+
+    # Errors
+
+    Any error returned by `Device::mig_devices()` or `MigSlice::of()`.
+    */
+    pub fn build(parent: &Device) -> Result<Self, NvmlError> {
+        let slices = parent
+            .mig_devices()?
+            .iter()
+            .map(MigSlice::of)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self::from_slices(slices))
+    }
+
+    /// Build an index from already known slices.
+    pub fn from_slices<I: IntoIterator<Item = MigSlice>>(slices: I) -> Self {
+        let mut index = Self::default();
+
+        for slice in slices {
+            let i = index.slices.len();
+            index.by_uuid.insert(slice.uuid.clone(), i);
+            index
+                .by_ids
+                .insert((slice.gpu_instance_id, slice.compute_instance_id), i);
+            index.slices.push(slice);
+        }
+
+        index
+    }
+
+    /// All the slices in this index.
+    pub fn slices(&self) -> &[MigSlice] {
+        &self.slices
+    }
+
+    /// Look up a slice by its MIG device UUID.
+    pub fn by_uuid(&self, uuid: &str) -> Option<&MigSlice> {
+        self.by_uuid.get(uuid).map(|i| &self.slices[*i])
+    }
+
+    /// Look up a slice by its GPU instance ID and compute instance ID.
+    pub fn by_ids(&self, gpu_instance_id: u32, compute_instance_id: u32) -> Option<&MigSlice> {
+        self.by_ids
+            .get(&(gpu_instance_id, compute_instance_id))
+            .map(|i| &self.slices[*i])
+    }
+
+    /// Look up the slice a process is running on.
+    ///
+    /// Returns `None` for processes that do not carry MIG instance IDs.
+    pub fn for_process(&self, process: &ProcessInfo) -> Option<&MigSlice> {
+        match (process.gpu_instance_id, process.compute_instance_id) {
+            (Some(gi), Some(ci)) => self.by_ids(gi, ci),
+            _ => None,
+        }
+    }
+
+    /// Pair every process with the slice it is running on, if any.
+    pub fn attribute<I>(&self, processes: I) -> Vec<(ProcessInfo, Option<&MigSlice>)>
+    where
+        I: IntoIterator<Item = ProcessInfo>,
+    {
+        processes
+            .into_iter()
+            .map(|p| {
+                let slice = self.for_process(&p);
+                (p, slice)
+            })
+            .collect()
+    }
+}
+
+/// What a `Planner` knows about one GPU instance profile on a device.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProfileCapacity {
+    pub info: GpuInstanceProfileInfo,
+    /// Where instances of this profile can be placed.
+    pub placements: Vec<GpuInstancePlacement>,
+    /// How many more instances of this profile can be created.
+    pub remaining: u32,
+}
+
+/// One GPU instance in a requested partition layout.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PlannedInstance {
+    /// The ID of the GPU instance profile, as in `GpuInstanceProfileInfo::id`
+    /// (not one of the `NVML_GPU_INSTANCE_PROFILE_*` indices).
+    pub profile: u32,
+    /// The memory slice the instance must start at, or `None` to let the
+    /// planner pick one.
+    pub start: Option<u32>,
+}
+
+impl PlannedInstance {
+    /// An instance of `profile` placed wherever it fits.
+    pub fn new(profile: u32) -> Self {
+        Self {
+            profile,
+            start: None,
+        }
+    }
+
+    /// An instance of `profile` starting at memory slice `start`.
+    pub fn at(profile: u32, start: u32) -> Self {
+        Self {
+            profile,
+            start: Some(start),
+        }
+    }
+}
+
+/**
+The reason a partition layout cannot be created.
+
+Indices refer to positions in the layout given to `Planner::validate_only()`.
+*/
+#[derive(Error, Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Infeasible {
+    #[error("MIG mode is not enabled on the device")]
+    MigDisabled,
+    #[error("instance {index} uses profile {profile}, which the device does not support")]
+    UnknownProfile { index: usize, profile: u32 },
+    #[error(
+        "{requested} instances of profile {profile} requested, but only {remaining} can be created"
+    )]
+    InsufficientCapacity {
+        profile: u32,
+        requested: u32,
+        remaining: u32,
+    },
+    #[error("instance {index} of profile {profile} cannot start at memory slice {start}")]
+    InvalidPlacement {
+        index: usize,
+        profile: u32,
+        start: u32,
+    },
+    #[error("instances {first} and {second} overlap")]
+    Overlap { first: usize, second: usize },
+    #[error("instance {index} of profile {profile} does not fit alongside the other instances")]
+    NoPlacement { index: usize, profile: u32 },
+}
+
+/**
+Checks MIG partition layouts against a device's GPU instance profiles.
+
+Validation does not create or modify anything, which makes it suitable for
+admission control before reconfiguring a node.
+
+The planner knows how many more instances of each profile can be created, but
+not where existing instances are placed; placements are checked against each
+other only.
+*/
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Planner {
+    mig_enabled: bool,
+    profiles: BTreeMap<u32, ProfileCapacity>,
+}
+
+impl Planner {
+    /// Create a planner for a device with MIG enabled and the given profiles.
+    pub fn new<I: IntoIterator<Item = ProfileCapacity>>(profiles: I) -> Self {
+        Self {
+            mig_enabled: true,
+            profiles: profiles.into_iter().map(|p| (p.info.id, p)).collect(),
+        }
+    }
+
+    /**
+    Read the current MIG mode, GPU instance profiles, placements and remaining
+    capacity of the given `Device`.
+
+    Profiles the device does not support are left out. If MIG mode is
+    disabled, every layout is infeasible.
+
+    # Errors
+
+    * `NotSupported`, if the device does not support MIG
+    * Any error returned by `Device::mig_mode()`,
+      `Device::gpu_instance_profile_info()`, `Device::possible_placements()` or
+      `Device::gpu_instance_remaining_capacity()`
+
+    # Platform Support
+
+    Only supports Linux.
+    */
+    #[cfg(target_os = "linux")]
+    pub fn from_device(device: &Device) -> Result<Self, NvmlError> {
+        let mig_enabled = device.mig_mode()?.current == NVML_DEVICE_MIG_ENABLE;
+        let mut profiles = BTreeMap::new();
+
+        if mig_enabled {
+            for profile in 0..NVML_GPU_INSTANCE_PROFILE_COUNT {
+                let info = match device.gpu_instance_profile_info(profile) {
+                    Ok(info) => info,
+                    Err(NvmlError::NotSupported) | Err(NvmlError::InvalidArg) => continue,
+                    Err(e) => return Err(e),
+                };
+
+                profiles.insert(
+                    info.id,
+                    ProfileCapacity {
+                        placements: device.possible_placements(info.id)?,
+                        remaining: device.gpu_instance_remaining_capacity(info.id)?,
+                        info,
+                    },
+                );
+            }
+        }
+
+        Ok(Self {
+            mig_enabled,
+            profiles,
+        })
+    }
+
+    /// The profiles this planner knows about, ordered by profile ID.
+    pub fn profiles(&self) -> impl Iterator<Item = &ProfileCapacity> {
+        self.profiles.values()
+    }
+
+    /**
+    Check whether `layout` could be created, without creating anything.
+
+    On success, returns the placement chosen for each instance, in layout
+    order. Instances with an explicit `start` keep it; the others are placed
+    at the first position that leaves room for the rest of the layout.
+
+    # Errors
+
+    The first reason found for the layout being infeasible, checked in the
+    order of the `Infeasible` variants.
+    */
+    pub fn validate_only(
+        &self,
+        layout: &[PlannedInstance],
+    ) -> Result<Vec<GpuInstancePlacement>, Infeasible> {
+        if !self.mig_enabled {
+            return Err(Infeasible::MigDisabled);
+        }
+
+        let mut profiles = Vec::with_capacity(layout.len());
+        let mut requested: BTreeMap<u32, u32> = BTreeMap::new();
+
+        for (index, instance) in layout.iter().enumerate() {
+            match self.profiles.get(&instance.profile) {
+                Some(p) => profiles.push(p),
+                None => {
+                    return Err(Infeasible::UnknownProfile {
+                        index,
+                        profile: instance.profile,
+                    })
+                }
+            }
+
+            *requested.entry(instance.profile).or_insert(0) += 1;
+        }
+
+        for (&profile, &requested) in &requested {
+            let remaining = self.profiles[&profile].remaining;
+
+            if requested > remaining {
+                return Err(Infeasible::InsufficientCapacity {
+                    profile,
+                    requested,
+                    remaining,
+                });
+            }
+        }
+
+        let mut chosen: Vec<Option<GpuInstancePlacement>> = vec![None; layout.len()];
+        let mut occupied: Vec<(usize, &GpuInstancePlacement)> = Vec::new();
+
+        for (index, instance) in layout.iter().enumerate() {
+            let start = match instance.start {
+                Some(start) => start,
+                None => continue,
+            };
+
+            let placement = profiles[index]
+                .placements
+                .iter()
+                .find(|p| p.start == start)
+                .ok_or(Infeasible::InvalidPlacement {
+                    index,
+                    profile: instance.profile,
+                    start,
+                })?;
+
+            if let Some(&(first, _)) = occupied.iter().find(|(_, p)| overlaps(p, placement)) {
+                return Err(Infeasible::Overlap {
+                    first,
+                    second: index,
+                });
+            }
+
+            occupied.push((index, placement));
+            chosen[index] = Some(placement.clone());
+        }
+
+        let unplaced: Vec<usize> = (0..layout.len()).filter(|&i| chosen[i].is_none()).collect();
+        let mut deepest = 0;
+
+        if !place(
+            &unplaced,
+            0,
+            &profiles,
+            &mut occupied,
+            &mut chosen,
+            &mut deepest,
+        ) {
+            let index = unplaced[deepest];
+
+            return Err(Infeasible::NoPlacement {
+                index,
+                profile: layout[index].profile,
+            });
+        }
+
+        Ok(chosen.into_iter().flatten().collect())
+    }
+}
+
+fn overlaps(a: &GpuInstancePlacement, b: &GpuInstancePlacement) -> bool {
+    a.start < b.start + b.size && b.start < a.start + a.size
+}
+
+// Backtracking search placing `unplaced[depth..]`. `deepest` records how far
+// into `unplaced` the search got, for error reporting.
+fn place<'a>(
+    unplaced: &[usize],
+    depth: usize,
+    profiles: &[&'a ProfileCapacity],
+    occupied: &mut Vec<(usize, &'a GpuInstancePlacement)>,
+    chosen: &mut [Option<GpuInstancePlacement>],
+    deepest: &mut usize,
+) -> bool {
+    *deepest = (*deepest).max(depth);
+
+    let index = match unplaced.get(depth) {
+        Some(&i) => i,
+        None => return true,
+    };
+
+    for placement in &profiles[index].placements {
+        if occupied.iter().any(|(_, p)| overlaps(p, placement)) {
+            continue;
+        }
+
+        occupied.push((index, placement));
+        chosen[index] = Some(placement.clone());
+
+        if place(unplaced, depth + 1, profiles, occupied, chosen, deepest) {
+            return true;
+        }
+
+        occupied.pop();
+        chosen[index] = None;
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::enums::device::UsedGpuMemory;
+    use crate::test_utils::*;
+
+    #[test]
+    fn collect_all() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, MigDeviceStats::collect_all)
+    }
+
+    fn slice(uuid: &str, gi: u32, ci: u32) -> MigSlice {
+        MigSlice {
+            uuid: uuid.into(),
+            gpu_instance_id: gi,
+            compute_instance_id: ci,
+        }
+    }
+
+    fn process(pid: u32, gi: Option<u32>, ci: Option<u32>) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            used_gpu_memory: UsedGpuMemory::Unavailable,
+            gpu_instance_id: gi,
+            compute_instance_id: ci,
+        }
+    }
+
+    #[test]
+    fn slice_index_lookups() {
+        let index = MigSliceIndex::from_slices(vec![slice("MIG-a", 1, 0), slice("MIG-b", 2, 0)]);
+
+        assert_eq!(index.by_uuid("MIG-b"), Some(&slice("MIG-b", 2, 0)));
+        assert_eq!(index.by_ids(1, 0), Some(&slice("MIG-a", 1, 0)));
+        assert_eq!(index.by_ids(1, 1), None);
+
+        let attributed =
+            index.attribute(vec![process(10, Some(2), Some(0)), process(11, None, None)]);
+        assert_eq!(attributed[0].1.map(|s| s.uuid.as_str()), Some("MIG-b"));
+        assert_eq!(attributed[1].1, None);
+    }
+
+    #[test]
+    fn build_slice_index() {
+        let nvml = nvml();
+        let device = device(&nvml);
+        test(3, || MigSliceIndex::build(&device))
+    }
+
+    fn profile(id: u32, size: u32, starts: &[u32], remaining: u32) -> ProfileCapacity {
+        ProfileCapacity {
+            info: GpuInstanceProfileInfo {
+                id,
+                is_p2p_supported: false,
+                slice_count: size,
+                instance_count: remaining,
+                multiprocessor_count: 0,
+                copy_engine_count: 0,
+                decoder_count: 0,
+                encoder_count: 0,
+                jpeg_count: 0,
+                ofa_count: 0,
+                memory_size_mb: 0,
+            },
+            placements: starts
+                .iter()
+                .map(|&start| GpuInstancePlacement { start, size })
+                .collect(),
+            remaining,
+        }
+    }
+
+    // A 7-slice A100-like layout: 1g on any slice, 2g on even slices, 3g on 0
+    // or 4, 7g on 0 (with 8 memory slices).
+    fn planner() -> Planner {
+        Planner::new(vec![
+            profile(0, 1, &[0, 1, 2, 3, 4, 5, 6], 7),
+            profile(1, 2, &[0, 2, 4], 3),
+            profile(2, 4, &[0, 4], 2),
+            profile(4, 8, &[0], 1),
+        ])
+    }
+
+    #[test]
+    fn planner_places_instances() {
+        let planner = planner();
+
+        let placements = planner
+            .validate_only(&[PlannedInstance::new(0), PlannedInstance::at(2, 0)])
+            .unwrap();
+        assert_eq!(
+            placements,
+            vec![
+                GpuInstancePlacement { start: 4, size: 1 },
+                GpuInstancePlacement { start: 0, size: 4 },
+            ]
+        );
+
+        // The 1g instance has to be moved past the three 2g ones
+        let placements = planner
+            .validate_only(&[
+                PlannedInstance::new(0),
+                PlannedInstance::new(1),
+                PlannedInstance::new(1),
+                PlannedInstance::new(1),
+            ])
+            .unwrap();
+        assert_eq!(
+            placements,
+            vec![
+                GpuInstancePlacement { start: 6, size: 1 },
+                GpuInstancePlacement { start: 0, size: 2 },
+                GpuInstancePlacement { start: 2, size: 2 },
+                GpuInstancePlacement { start: 4, size: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn planner_reports_infeasible_layouts() {
+        let planner = planner();
+
+        assert_eq!(
+            planner.validate_only(&[PlannedInstance::new(3)]),
+            Err(Infeasible::UnknownProfile {
+                index: 0,
+                profile: 3
+            })
+        );
+        assert_eq!(
+            planner.validate_only(&[PlannedInstance::new(4), PlannedInstance::new(4)]),
+            Err(Infeasible::InsufficientCapacity {
+                profile: 4,
+                requested: 2,
+                remaining: 1
+            })
+        );
+        assert_eq!(
+            planner.validate_only(&[PlannedInstance::at(1, 1)]),
+            Err(Infeasible::InvalidPlacement {
+                index: 0,
+                profile: 1,
+                start: 1
+            })
+        );
+        assert_eq!(
+            planner.validate_only(&[PlannedInstance::at(2, 0), PlannedInstance::at(1, 2)]),
+            Err(Infeasible::Overlap {
+                first: 0,
+                second: 1
+            })
+        );
+        assert_eq!(
+            planner.validate_only(&[
+                PlannedInstance::new(2),
+                PlannedInstance::new(2),
+                PlannedInstance::new(0),
+            ]),
+            Err(Infeasible::NoPlacement {
+                index: 2,
+                profile: 0
+            })
+        );
+        assert_eq!(
+            Planner {
+                mig_enabled: false,
+                profiles: BTreeMap::new(),
+            }
+            .validate_only(&[]),
+            Err(Infeasible::MigDisabled)
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn planner_from_device() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, Planner::from_device)
+    }
+}
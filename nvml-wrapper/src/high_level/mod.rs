@@ -1,4 +1,171 @@
+/*!
+Higher-level helpers built on top of the NVML wrappers.
+
+Guards such as `ecc::EccModeGuard`, `clocks::LockedClocksGuard`,
+`clocks::ApplicationClocksGuard` and `vgpu_scheduler::VgpuSchedulerGuard` undo
+a device setting when dropped, so that a failing or panicking caller cannot
+leave a device changed. Errors cannot be reported from `Drop`, so dropping a
+guard ignores them; call its `restore()` to undo the change and see whether
+that worked, or `keep()` to keep the change.
+*/
+
+pub mod attestation;
+pub mod clocks;
+pub mod compare;
+pub mod ecc;
+pub mod encoder;
+pub mod event_thread;
+pub mod health;
+pub mod interconnect;
+pub mod mig;
+pub mod multi;
+pub mod policy;
+pub mod power;
+pub mod power_budget;
+pub mod process;
+pub mod query;
+pub mod rate_limit;
+pub mod recovery;
+pub mod replay;
+pub mod row_remap;
+pub mod subscription;
+pub mod support;
+pub mod throttle;
+pub mod vgpu_scheduler;
+pub mod xid;
+
 #[cfg(target_os = "linux")]
 pub mod event_loop;
 #[cfg(target_os = "linux")]
 pub use self::event_loop::{Event, EventLoop, EventLoopProvider};
+#[cfg(target_os = "linux")]
+pub mod topology;
+
+use crate::error::NvmlError;
+use crate::ffi::bindings::NVML_NVLINK_MAX_LINKS;
+use crate::Device;
+
+/**
+Holds the state to put a device back in and puts it back with `restore` when
+dropped, unless it was kept. The guards of this module wrap one of these.
+*/
+#[derive(Debug)]
+pub(crate) struct RestoreGuard<D, S> {
+    device: D,
+    state: Option<S>,
+    restore: fn(&mut D, S) -> Result<(), NvmlError>,
+}
+
+impl<D, S> RestoreGuard<D, S> {
+    pub(crate) fn new(
+        device: D,
+        state: S,
+        restore: fn(&mut D, S) -> Result<(), NvmlError>,
+    ) -> Self {
+        Self {
+            device,
+            state: Some(state),
+            restore,
+        }
+    }
+
+    pub(crate) fn device(&self) -> &D {
+        &self.device
+    }
+
+    pub(crate) fn device_mut(&mut self) -> &mut D {
+        &mut self.device
+    }
+
+    pub(crate) fn state(&self) -> &S {
+        // Only `restore()` and `keep()` take it, and those consume the guard
+        self.state.as_ref().expect("state to restore")
+    }
+
+    pub(crate) fn state_mut(&mut self) -> &mut S {
+        self.state.as_mut().expect("state to restore")
+    }
+
+    pub(crate) fn restore(mut self) -> Result<(), NvmlError> {
+        self.run()
+    }
+
+    pub(crate) fn keep(mut self) {
+        self.state = None;
+    }
+
+    fn run(&mut self) -> Result<(), NvmlError> {
+        match self.state.take() {
+            Some(state) => (self.restore)(&mut self.device, state),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<D, S> Drop for RestoreGuard<D, S> {
+    fn drop(&mut self) {
+        let _ = self.run();
+    }
+}
+
+/// Turn `NotSupported` into `None`, for readings a device may not have.
+pub(crate) fn supported<T>(res: Result<T, NvmlError>) -> Result<Option<T>, NvmlError> {
+    match res {
+        Ok(v) => Ok(Some(v)),
+        Err(NvmlError::NotSupported) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/**
+The active NvLink links of `device`, or `None` if it doesn't support NvLink.
+
+# Errors
+
+Any error other than `NotSupported` and `InvalidArg` returned by
+`NvLink::is_active()`.
+*/
+pub(crate) fn active_nvlinks(device: &Device) -> Result<Option<Vec<u32>>, NvmlError> {
+    let mut active = None;
+
+    for link in 0..NVML_NVLINK_MAX_LINKS {
+        match device.link_wrapper_for(link).is_active() {
+            Ok(true) => active.get_or_insert_with(Vec::new).push(link),
+            Ok(false) => {
+                active.get_or_insert_with(Vec::new);
+            }
+            // Links past the last one the device has are invalid
+            Err(NvmlError::NotSupported) | Err(NvmlError::InvalidArg) => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(active)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::Cell;
+
+    fn guard(restored: &Cell<Option<u32>>) -> RestoreGuard<&Cell<Option<u32>>, u32> {
+        RestoreGuard::new(restored, 1, |restored, state| {
+            restored.set(Some(state));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn restore_guard() {
+        let restored = Cell::new(None);
+
+        drop(guard(&restored));
+        assert_eq!(restored.take(), Some(1));
+
+        guard(&restored).restore().unwrap();
+        assert_eq!(restored.take(), Some(1));
+
+        guard(&restored).keep();
+        assert_eq!(restored.take(), None);
+    }
+}
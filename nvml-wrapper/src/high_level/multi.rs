@@ -0,0 +1,238 @@
+/*!
+Per-device results of operations over many devices.
+
+Fleet-wide helpers such as `Nvml::map_devices()` should not give up on the
+whole node because one GPU fell off the bus. `MultiResult` collects the
+outcome for each device so that callers can use what succeeded and report
+what failed in a consistent way.
+
+```no_run
+use nvml_wrapper::Nvml;
+
+# fn main() -> Result<(), nvml_wrapper::error::NvmlError> {
+let nvml = Nvml::init()?;
+
+let temperatures = nvml.map_devices(|device| {
+    device.temperature(nvml_wrapper::enum_wrappers::device::TemperatureSensor::Gpu)
+})?;
+
+temperatures.log_failures(|line| eprintln!("{}", line));
+println!("{}", temperatures.summary());
+
+for (index, temperature) in temperatures.successes() {
+    println!("GPU {}: {}°C", index, temperature);
+}
+# Ok(())
+# }
+```
+*/
+
+use crate::error::NvmlError;
+use std::fmt;
+use std::iter::FromIterator;
+
+/// The outcome of an operation on each of several devices, keyed by device
+/// index.
+#[derive(Debug)]
+pub struct MultiResult<T> {
+    /// Results in the order they were collected.
+    pub results: Vec<(u32, Result<T, NvmlError>)>,
+}
+
+impl<T> Default for MultiResult<T> {
+    fn default() -> Self {
+        Self { results: vec![] }
+    }
+}
+
+impl<T> MultiResult<T> {
+    /// Create an empty `MultiResult`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the result for the device at `index`.
+    pub fn push(&mut self, index: u32, result: Result<T, NvmlError>) {
+        self.results.push((index, result));
+    }
+
+    /// The number of devices with a result.
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Whether there are no results at all.
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Whether the operation succeeded on every device.
+    pub fn is_all_ok(&self) -> bool {
+        self.results.iter().all(|(_, r)| r.is_ok())
+    }
+
+    /// The devices the operation succeeded on, with their values.
+    pub fn successes(&self) -> impl Iterator<Item = (u32, &T)> {
+        self.results
+            .iter()
+            .filter_map(|(i, r)| r.as_ref().ok().map(|v| (*i, v)))
+    }
+
+    /// The devices the operation failed on, with their errors.
+    pub fn failures(&self) -> impl Iterator<Item = (u32, &NvmlError)> {
+        self.results
+            .iter()
+            .filter_map(|(i, r)| r.as_ref().err().map(|e| (*i, e)))
+    }
+
+    /// Split the results into successes and failures.
+    #[allow(clippy::type_complexity)]
+    pub fn partition(self) -> (Vec<(u32, T)>, Vec<(u32, NvmlError)>) {
+        let mut successes = vec![];
+        let mut failures = vec![];
+
+        for (index, result) in self.results {
+            match result {
+                Ok(v) => successes.push((index, v)),
+                Err(e) => failures.push((index, e)),
+            }
+        }
+
+        (successes, failures)
+    }
+
+    /**
+    Turn the results into a single `Result`, as if the operation had
+    short-circuited.
+
+    # Errors
+
+    The first failure, along with the index of the device it happened on.
+    */
+    pub fn into_result(self) -> Result<Vec<T>, (u32, NvmlError)> {
+        self.results
+            .into_iter()
+            .map(|(i, r)| r.map_err(|e| (i, e)))
+            .collect()
+    }
+
+    /// Counts of successes and failures.
+    pub fn summary(&self) -> MultiSummary {
+        let failed = self.failures().count();
+
+        MultiSummary {
+            total: self.len(),
+            succeeded: self.len() - failed,
+            failed,
+        }
+    }
+
+    /**
+    Pass a line describing each failure to `sink`, e.g. a logging macro.
+
+    Lines have the form `device 3: <error>`, so that failures are reported
+    the same way by every helper.
+    */
+    pub fn log_failures<F: FnMut(&str)>(&self, mut sink: F) {
+        for (index, error) in self.failures() {
+            sink(&format!("device {}: {}", index, error));
+        }
+    }
+}
+
+impl<T> FromIterator<(u32, Result<T, NvmlError>)> for MultiResult<T> {
+    fn from_iter<I: IntoIterator<Item = (u32, Result<T, NvmlError>)>>(iter: I) -> Self {
+        Self {
+            results: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> IntoIterator for MultiResult<T> {
+    type Item = (u32, Result<T, NvmlError>);
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.results.into_iter()
+    }
+}
+
+/// Returned from `MultiResult.summary()`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct MultiSummary {
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+}
+
+impl fmt::Display for MultiSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} devices succeeded, {} failed",
+            self.succeeded, self.total, self.failed
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn results() -> MultiResult<u32> {
+        vec![
+            (0, Ok(40)),
+            (1, Err(NvmlError::GpuLost)),
+            (2, Ok(42)),
+            (3, Err(NvmlError::NotSupported)),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn partition_and_summarize() {
+        let results = results();
+
+        assert!(!results.is_all_ok());
+        assert_eq!(
+            results.successes().collect::<Vec<_>>(),
+            vec![(0, &40), (2, &42)]
+        );
+        assert_eq!(
+            results.summary(),
+            MultiSummary {
+                total: 4,
+                succeeded: 2,
+                failed: 2
+            }
+        );
+        assert_eq!(
+            results.summary().to_string(),
+            "2 of 4 devices succeeded, 2 failed"
+        );
+
+        let mut lines = vec![];
+        results.log_failures(|line| lines.push(line.to_string()));
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("device 1: "));
+
+        let (successes, failures) = results.partition();
+        assert_eq!(successes, vec![(0, 40), (2, 42)]);
+        assert_eq!(
+            failures.iter().map(|(i, _)| *i).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+    }
+
+    #[test]
+    fn into_result_returns_first_failure() {
+        assert!(matches!(
+            results().into_result(),
+            Err((1, NvmlError::GpuLost))
+        ));
+
+        let ok: MultiResult<u32> = vec![(0, Ok(1)), (1, Ok(2))].into_iter().collect();
+        assert_eq!(ok.into_result().unwrap(), vec![1, 2]);
+    }
+}
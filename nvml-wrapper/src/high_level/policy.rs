@@ -0,0 +1,471 @@
+/*!
+Rule-based clock and power policies.
+
+A `PolicyEngine` holds rules such as "if the GPU temperature stays above 83°C
+for 30 seconds, lower the power limit by 10%". Each call to
+`PolicyEngine::tick()` reads the metrics the rules need (see
+`high_level::query`), checks the rules, and applies the actions that are due
+through a `RateLimitedDevice`. Every action taken is recorded; the engine
+keeps the latest `DEFAULT_RECORD_CAPACITY` records unless created with
+`PolicyEngine::with_record_capacity()`.
+
+```no_run
+use nvml_wrapper::Nvml;
+use nvml_wrapper::high_level::policy::{Condition, PolicyAction, PolicyEngine, Rule};
+use nvml_wrapper::high_level::query::Metric;
+use nvml_wrapper::high_level::rate_limit::{RateLimitedDevice, RateLimiter};
+use std::{thread, time::Duration};
+
+# fn main() -> Result<(), nvml_wrapper::error::NvmlError> {
+let nvml = Nvml::init()?;
+let mut device = RateLimitedDevice::new(nvml.device_by_index(0)?, RateLimiter::new(1.0, 1));
+
+let mut engine = PolicyEngine::new();
+engine.add_rule(Rule::new(
+    "hot",
+    Condition::above(Metric::TemperatureGpu, 83.0).sustained_for(Duration::from_secs(30)),
+    PolicyAction::ScalePowerLimit(0.9),
+));
+
+loop {
+    for record in engine.tick(&mut device) {
+        println!("{:?}", record);
+    }
+#   break;
+    thread::sleep(Duration::from_secs(1));
+}
+# Ok(())
+# }
+```
+*/
+
+use crate::enums::device::GpuLockedClocksSetting;
+use crate::error::NvmlError;
+use crate::high_level::query::Metric;
+use crate::high_level::rate_limit::{ControlError, RateLimitedDevice};
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+use std::time::{Duration, Instant, SystemTime};
+
+/// How many records a `PolicyEngine` keeps by default.
+pub const DEFAULT_RECORD_CAPACITY: usize = 1024;
+
+/// How a metric is compared against a threshold.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Comparison {
+    Above,
+    Below,
+}
+
+/// A condition on a metric that must hold for some time.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Condition {
+    /// The metric to check. Only numeric metrics can ever hold.
+    pub metric: Metric,
+    pub comparison: Comparison,
+    /// The threshold, in the unit `Metric::query()` returns the metric in.
+    pub threshold: f64,
+    /// How long the condition must hold continuously before the rule fires.
+    pub sustained_for: Duration,
+}
+
+impl Condition {
+    /// `metric` is strictly greater than `threshold`.
+    pub fn above(metric: Metric, threshold: f64) -> Self {
+        Self {
+            metric,
+            comparison: Comparison::Above,
+            threshold,
+            sustained_for: Duration::from_secs(0),
+        }
+    }
+
+    /// `metric` is strictly less than `threshold`.
+    pub fn below(metric: Metric, threshold: f64) -> Self {
+        Self {
+            comparison: Comparison::Below,
+            ..Self::above(metric, threshold)
+        }
+    }
+
+    /// Require the condition to hold for `duration` before the rule fires.
+    pub fn sustained_for(mut self, duration: Duration) -> Self {
+        self.sustained_for = duration;
+        self
+    }
+
+    /// Whether `value` satisfies the comparison.
+    pub fn holds(&self, value: f64) -> bool {
+        match self.comparison {
+            Comparison::Above => value > self.threshold,
+            Comparison::Below => value < self.threshold,
+        }
+    }
+}
+
+/// A change a rule makes to a device.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PolicyAction {
+    /// Multiply the current power limit by the given factor (e.g. `0.9` to
+    /// lower it by 10%), clamped to the device's power limit constraints.
+    ScalePowerLimit(f64),
+    /// Set the power limit, in milliwatts.
+    SetPowerLimit(u32),
+    SetGpuLockedClocks(GpuLockedClocksSetting),
+    ResetGpuLockedClocks,
+    ResetApplicationsClocks,
+}
+
+impl PolicyAction {
+    /**
+    Apply this action to `device`.
+
+    # Errors
+
+    * `RateLimited`, if the device's rate limit is exceeded
+    * `Nvml`, if reading the current state or applying the change failed
+    */
+    pub fn apply(&self, device: &mut RateLimitedDevice) -> Result<(), ControlError> {
+        match *self {
+            PolicyAction::ScalePowerLimit(factor) => {
+                let current = device.device().power_management_limit()?;
+                let constraints = device.device().power_management_limit_constraints()?;
+
+                let limit = (current as f64 * factor).round() as u32;
+                let limit = limit.max(constraints.min_limit).min(constraints.max_limit);
+
+                device.set_power_management_limit(limit)
+            }
+            PolicyAction::SetPowerLimit(limit) => device.set_power_management_limit(limit),
+            PolicyAction::SetGpuLockedClocks(ref setting) => {
+                device.set_gpu_locked_clocks(setting.clone())
+            }
+            PolicyAction::ResetGpuLockedClocks => device.reset_gpu_locked_clocks(),
+            PolicyAction::ResetApplicationsClocks => device.reset_applications_clocks(),
+        }
+    }
+}
+
+/// A named condition and the action to take when it holds.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rule {
+    pub name: String,
+    pub condition: Condition,
+    pub action: PolicyAction,
+}
+
+impl Rule {
+    pub fn new<S: Into<String>>(name: S, condition: Condition, action: PolicyAction) -> Self {
+        Self {
+            name: name.into(),
+            condition,
+            action,
+        }
+    }
+}
+
+/// A rule that fired, as returned by `PolicyEngine::evaluate()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trigger {
+    /// The name of the rule.
+    pub rule: String,
+    /// The metric value the rule fired on.
+    pub value: f64,
+    pub action: PolicyAction,
+}
+
+/// A record of an action taken by a `PolicyEngine`.
+#[derive(Debug)]
+pub struct PolicyRecord {
+    /// When the action was applied.
+    pub time: SystemTime,
+    pub trigger: Trigger,
+    pub result: Result<(), ControlError>,
+}
+
+#[derive(Debug, Clone)]
+struct RuleState {
+    rule: Rule,
+    holding_since: Option<Instant>,
+}
+
+/**
+Evaluates rules against device metrics and applies their actions.
+
+Only the latest records are kept: once there are as many as the record
+capacity, recording an action drops the oldest record. Use `drain_records()`
+regularly to keep every record.
+*/
+#[derive(Debug)]
+pub struct PolicyEngine {
+    rules: Vec<RuleState>,
+    records: Vec<PolicyRecord>,
+    record_capacity: usize,
+}
+
+impl Default for PolicyEngine {
+    fn default() -> Self {
+        Self::with_record_capacity(DEFAULT_RECORD_CAPACITY)
+    }
+}
+
+impl PolicyEngine {
+    /// Create an engine without any rules, keeping the latest
+    /// `DEFAULT_RECORD_CAPACITY` records.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an engine without any rules, keeping the latest `capacity`
+    /// records.
+    pub fn with_record_capacity(capacity: usize) -> Self {
+        Self {
+            rules: vec![],
+            records: vec![],
+            record_capacity: capacity,
+        }
+    }
+
+    /// Register a rule. Rules are checked in registration order.
+    pub fn add_rule(&mut self, rule: Rule) -> &mut Self {
+        self.rules.push(RuleState {
+            rule,
+            holding_since: None,
+        });
+        self
+    }
+
+    /// Remove every rule named `name`.
+    pub fn remove_rule(&mut self, name: &str) -> &mut Self {
+        self.rules.retain(|s| s.rule.name != name);
+        self
+    }
+
+    /// The registered rules.
+    pub fn rules(&self) -> impl Iterator<Item = &Rule> {
+        self.rules.iter().map(|s| &s.rule)
+    }
+
+    /// The metrics the registered rules depend on, without duplicates.
+    pub fn metrics(&self) -> Vec<Metric> {
+        let mut metrics = Vec::new();
+
+        for state in &self.rules {
+            if !metrics.contains(&state.rule.condition.metric) {
+                metrics.push(state.rule.condition.metric);
+            }
+        }
+
+        metrics
+    }
+
+    /**
+    Check the rules against metric values read at `at`.
+
+    A rule whose metric is missing from `values`, or whose condition does not
+    hold, starts waiting for its condition afresh. A rule that fires also
+    starts afresh, so a rule whose condition keeps holding fires once every
+    `sustained_for`.
+    */
+    pub fn evaluate(&mut self, at: Instant, values: &[(Metric, f64)]) -> Vec<Trigger> {
+        let mut triggers = vec![];
+
+        for state in &mut self.rules {
+            let condition = &state.rule.condition;
+
+            let value = match values.iter().find(|(m, _)| *m == condition.metric) {
+                Some(&(_, v)) if condition.holds(v) => v,
+                _ => {
+                    state.holding_since = None;
+                    continue;
+                }
+            };
+
+            let since = *state.holding_since.get_or_insert(at);
+
+            if at.saturating_duration_since(since) >= condition.sustained_for {
+                state.holding_since = None;
+
+                triggers.push(Trigger {
+                    rule: state.rule.name.clone(),
+                    value,
+                    action: state.rule.action.clone(),
+                });
+            }
+        }
+
+        triggers
+    }
+
+    /**
+    Read the metrics the rules depend on from `device`, check the rules and
+    apply the actions that are due.
+
+    Metrics that cannot be read count as not satisfying any condition. Returns
+    the records of the actions taken during this tick; the records are also
+    kept in `records()`, up to the record capacity.
+    */
+    pub fn tick(&mut self, device: &mut RateLimitedDevice) -> &[PolicyRecord] {
+        let values: Vec<(Metric, f64)> = self
+            .metrics()
+            .into_iter()
+            .filter_map(|m| {
+                let value = m.query(device.device()).ok()?.as_f64()?;
+                Some((m, value))
+            })
+            .collect();
+
+        let triggers = self.evaluate(Instant::now(), &values);
+        let taken = triggers.len();
+
+        for trigger in triggers {
+            let result = trigger.action.apply(device);
+
+            self.record(PolicyRecord {
+                time: SystemTime::now(),
+                trigger,
+                result,
+            });
+        }
+
+        &self.records[self.records.len().saturating_sub(taken)..]
+    }
+
+    fn record(&mut self, record: PolicyRecord) {
+        if self.record_capacity == 0 {
+            return;
+        }
+
+        if self.records.len() == self.record_capacity {
+            self.records.remove(0);
+        }
+
+        self.records.push(record);
+    }
+
+    /// The latest actions taken, oldest first, up to the record capacity.
+    pub fn records(&self) -> &[PolicyRecord] {
+        &self.records
+    }
+
+    /// Take every record out of the engine, e.g. to ship them to an audit log.
+    pub fn drain_records(&mut self) -> Vec<PolicyRecord> {
+        std::mem::take(&mut self.records)
+    }
+}
+
+impl PolicyRecord {
+    /// The NVML error the action failed with, if any.
+    pub fn nvml_error(&self) -> Option<&NvmlError> {
+        match self.result {
+            Err(ControlError::Nvml(ref e)) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn engine() -> PolicyEngine {
+        let mut engine = PolicyEngine::new();
+        engine
+            .add_rule(Rule::new(
+                "hot",
+                Condition::above(Metric::TemperatureGpu, 83.0)
+                    .sustained_for(Duration::from_secs(30)),
+                PolicyAction::ScalePowerLimit(0.9),
+            ))
+            .add_rule(Rule::new(
+                "idle",
+                Condition::below(Metric::UtilizationGpu, 5.0),
+                PolicyAction::ResetGpuLockedClocks,
+            ));
+        engine
+    }
+
+    #[test]
+    fn rules_fire_when_sustained() {
+        let start = Instant::now();
+        let mut engine = engine();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        assert_eq!(
+            engine.metrics(),
+            vec![Metric::TemperatureGpu, Metric::UtilizationGpu]
+        );
+
+        let hot = [
+            (Metric::TemperatureGpu, 85.0),
+            (Metric::UtilizationGpu, 90.0),
+        ];
+
+        assert!(engine.evaluate(at(0), &hot).is_empty());
+        assert!(engine.evaluate(at(20), &hot).is_empty());
+
+        let triggers = engine.evaluate(at(30), &hot);
+        assert_eq!(triggers.len(), 1);
+        assert_eq!(triggers[0].rule, "hot");
+        assert_eq!(triggers[0].value, 85.0);
+
+        // Firing restarts the wait
+        assert!(engine.evaluate(at(40), &hot).is_empty());
+
+        // Cooling down in between restarts it too
+        engine.evaluate(at(50), &[(Metric::TemperatureGpu, 80.0)]);
+        assert!(engine.evaluate(at(60), &hot).is_empty());
+        assert!(engine.evaluate(at(80), &hot).is_empty());
+        assert_eq!(engine.evaluate(at(90), &hot).len(), 1);
+    }
+
+    #[test]
+    fn rules_without_duration_fire_immediately() {
+        let mut engine = engine();
+        engine.remove_rule("hot");
+
+        let triggers = engine.evaluate(Instant::now(), &[(Metric::UtilizationGpu, 0.0)]);
+        assert_eq!(
+            triggers.iter().map(|t| &t.action).collect::<Vec<_>>(),
+            vec![&PolicyAction::ResetGpuLockedClocks]
+        );
+    }
+
+    #[test]
+    fn records_are_capped() {
+        let mut engine = PolicyEngine::with_record_capacity(2);
+        let record = |value| PolicyRecord {
+            time: SystemTime::now(),
+            trigger: Trigger {
+                rule: "idle".into(),
+                value,
+                action: PolicyAction::ResetGpuLockedClocks,
+            },
+            result: Ok(()),
+        };
+
+        for value in [1.0, 2.0, 3.0] {
+            engine.record(record(value));
+        }
+
+        assert_eq!(
+            engine
+                .records()
+                .iter()
+                .map(|r| r.trigger.value)
+                .collect::<Vec<_>>(),
+            vec![2.0, 3.0]
+        );
+
+        assert_eq!(engine.drain_records().len(), 2);
+        assert!(engine.records().is_empty());
+
+        let mut engine = PolicyEngine::with_record_capacity(0);
+        engine.record(record(1.0));
+        assert!(engine.records().is_empty());
+    }
+}
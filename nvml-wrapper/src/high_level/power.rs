@@ -0,0 +1,286 @@
+/*!
+GPU and module power readings in one call.
+
+`Device::power_usage()` reports the power averaged over a second on some
+architectures and the instantaneous power on others, and only covers the GPU.
+On parts that share a power budget with a CPU (e.g. Grace Hopper superchips)
+the module power matters as much. `PowerReadings::read()` (or
+`Device::power_readings()`) fetches the instantaneous and averaged GPU power
+and the module power with a single field values request.
+
+```no_run
+use nvml_wrapper::Nvml;
+
+# fn main() -> Result<(), nvml_wrapper::error::NvmlError> {
+let nvml = Nvml::init()?;
+let readings = nvml.device_by_index(0)?.power_readings()?;
+
+if let (Some(gpu), Some(module)) = (readings.gpu_w, readings.module_w) {
+    println!("GPU {:.1} W of {:.1} W module power", gpu, module);
+}
+# Ok(())
+# }
+```
+
+`EnergyMeter` turns the total energy counter of `Device::total_energy_consumption()`
+into the energy used and the average power drawn between two readings:
+
+```no_run
+use nvml_wrapper::Nvml;
+use nvml_wrapper::high_level::power::EnergyMeter;
+
+# fn main() -> Result<(), nvml_wrapper::error::NvmlError> {
+let nvml = Nvml::init()?;
+let device = nvml.device_by_index(0)?;
+
+let mut meter = EnergyMeter::new();
+meter.poll(&device)?;
+
+// Run the workload...
+
+if let Some(sample) = meter.poll(&device)? {
+    println!("{:.1} J at {:.1} W on average", sample.energy_j, sample.average_w);
+}
+# Ok(())
+# }
+```
+*/
+
+use crate::error::NvmlError;
+use crate::ffi::bindings::field_id::{NVML_FI_DEV_POWER_AVERAGE, NVML_FI_DEV_POWER_INSTANT};
+use crate::ffi::bindings::{NVML_POWER_SCOPE_GPU, NVML_POWER_SCOPE_MODULE};
+use crate::struct_wrappers::device::FieldValueSample;
+use crate::structs::device::FieldId;
+use crate::Device;
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// The fields `PowerReadings::read()` requests, in the order of its fields.
+const FIELDS: [(u32, u32); 3] = [
+    (NVML_FI_DEV_POWER_INSTANT, NVML_POWER_SCOPE_GPU),
+    (NVML_FI_DEV_POWER_INSTANT, NVML_POWER_SCOPE_MODULE),
+    (NVML_FI_DEV_POWER_AVERAGE, NVML_POWER_SCOPE_GPU),
+];
+
+/// Power readings of a device, in watts. Each is `None` if not supported.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PowerReadings {
+    /// The current power draw of the GPU.
+    pub gpu_w: Option<f64>,
+    /// The current power draw of the whole module the GPU is part of (e.g.
+    /// the GPU and CPU of a Grace Hopper superchip).
+    pub module_w: Option<f64>,
+    /// The power draw of the GPU averaged over one second.
+    pub average_w: Option<f64>,
+}
+
+impl PowerReadings {
+    /**
+    Read the power of `device`.
+
+    # Errors
+
+    * Any error returned by `Device::scoped_field_values_for()`
+    * Any error other than `NotSupported` returned for an individual field
+    */
+    pub fn read(device: &Device) -> Result<Self, NvmlError> {
+        let ids: Vec<(FieldId, u32)> = FIELDS
+            .iter()
+            .map(|&(id, scope)| (FieldId(id), scope))
+            .collect();
+
+        Self::from_samples(device.scoped_field_values_for(&ids)?)
+    }
+
+    /// Build readings from the samples returned for `FIELDS`, in order.
+    fn from_samples(samples: Vec<Result<FieldValueSample, NvmlError>>) -> Result<Self, NvmlError> {
+        let mut watts = samples.into_iter().map(|sample| {
+            match sample.and_then(|s| s.value) {
+                // Power fields are reported in milliwatts
+                Ok(value) => Ok(Some(value.as_f64() / 1000.0)),
+                Err(NvmlError::NotSupported) => Ok(None),
+                Err(e) => Err(e),
+            }
+        });
+
+        let mut next = || watts.next().unwrap_or(Ok(None));
+
+        Ok(Self {
+            gpu_w: next()?,
+            module_w: next()?,
+            average_w: next()?,
+        })
+    }
+}
+
+/// The energy used by a device between two readings of an `EnergyMeter`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EnergySample {
+    /// The time between the two readings.
+    pub interval: Duration,
+    /// The energy used over `interval`, in joules.
+    pub energy_j: f64,
+    /// The average power drawn over `interval`, in watts (zero if `interval`
+    /// is).
+    pub average_w: f64,
+}
+
+/// Tracks the total energy counter of a `Device` between readings.
+#[derive(Debug, Clone, Default)]
+pub struct EnergyMeter {
+    previous: Option<(Instant, u64)>,
+}
+
+impl EnergyMeter {
+    /// Create a meter without a baseline reading.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+    Feed a total energy reading in millijoules, taken at `at`, through the
+    meter.
+
+    Returns `None` for the first reading, which only sets the baseline. The
+    64-bit counter does not wrap in practice, but it restarts from zero when
+    the driver is reloaded; a reading below the previous one is taken to be
+    the energy used since that restart.
+    */
+    pub fn update(&mut self, at: Instant, energy_mj: u64) -> Option<EnergySample> {
+        let sample = self.previous.map(|(prev_at, prev_mj)| {
+            let interval = at.saturating_duration_since(prev_at);
+            let used_mj = if energy_mj >= prev_mj {
+                energy_mj - prev_mj
+            } else {
+                energy_mj
+            };
+
+            let energy_j = used_mj as f64 / 1000.0;
+            let secs = interval.as_secs_f64();
+
+            EnergySample {
+                interval,
+                energy_j,
+                average_w: if secs > 0.0 { energy_j / secs } else { 0.0 },
+            }
+        });
+
+        self.previous = Some((at, energy_mj));
+
+        sample
+    }
+
+    /**
+    Read the total energy of `device` now and feed it through the meter.
+
+    # Errors
+
+    Any error returned by `Device::total_energy_consumption()`.
+    */
+    pub fn poll(&mut self, device: &Device) -> Result<Option<EnergySample>, NvmlError> {
+        let energy_mj = device.total_energy_consumption()?;
+
+        Ok(self.update(Instant::now(), energy_mj))
+    }
+
+    /// Forget the baseline, so that the next reading sets a new one.
+    pub fn reset(&mut self) {
+        self.previous = None;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::enums::device::SampleValue;
+    use crate::test_utils::*;
+
+    fn sample(field: u32, value: Result<SampleValue, NvmlError>) -> FieldValueSample {
+        FieldValueSample {
+            field: FieldId(field),
+            timestamp: 0,
+            latency: 0,
+            value,
+        }
+    }
+
+    #[test]
+    fn readings_from_samples() {
+        let readings = PowerReadings::from_samples(vec![
+            Ok(sample(
+                NVML_FI_DEV_POWER_INSTANT,
+                Ok(SampleValue::U32(350_500)),
+            )),
+            Ok(sample(
+                NVML_FI_DEV_POWER_INSTANT,
+                Err(NvmlError::NotSupported),
+            )),
+            Ok(sample(
+                NVML_FI_DEV_POWER_AVERAGE,
+                Ok(SampleValue::U32(300_000)),
+            )),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            readings,
+            PowerReadings {
+                gpu_w: Some(350.5),
+                module_w: None,
+                average_w: Some(300.0),
+            }
+        );
+
+        assert!(PowerReadings::from_samples(vec![Err(NvmlError::GpuLost)]).is_err());
+    }
+
+    #[test]
+    fn energy_from_counter() {
+        let start = Instant::now();
+        let mut meter = EnergyMeter::new();
+
+        assert_eq!(meter.update(start, 1_000_000), None);
+
+        let sample = meter
+            .update(start + Duration::from_secs(2), 1_600_000)
+            .unwrap();
+        assert_eq!(sample.interval, Duration::from_secs(2));
+        assert_eq!(sample.energy_j, 600.0);
+        assert_eq!(sample.average_w, 300.0);
+
+        // The counter restarts after a driver reload
+        let sample = meter
+            .update(start + Duration::from_secs(3), 250_000)
+            .unwrap();
+        assert_eq!(sample.energy_j, 250.0);
+        assert_eq!(sample.average_w, 250.0);
+
+        let sample = meter
+            .update(start + Duration::from_secs(3), 250_000)
+            .unwrap();
+        assert_eq!(sample.energy_j, 0.0);
+        assert_eq!(sample.average_w, 0.0);
+
+        meter.reset();
+        assert_eq!(meter.update(start, 0), None);
+    }
+
+    #[test]
+    fn energy_meter_poll() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let mut meter = EnergyMeter::new();
+            meter.poll(device)?;
+            meter.poll(device)
+        })
+    }
+
+    #[test]
+    fn read() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, PowerReadings::read)
+    }
+}
@@ -0,0 +1,324 @@
+/*!
+Power capping across several devices.
+
+Rack-level power management usually starts from a budget for a whole node and
+has to turn it into a power limit per GPU. `PowerBudget` splits a total
+budget between devices according to a `BudgetPolicy`, keeping every limit
+within the constraints reported by
+`Device::power_management_limit_constraints()`, and applies the limits. The
+`AppliedBudget` it returns remembers the limits that were replaced so that
+they can be put back.
+
+```no_run
+use nvml_wrapper::Nvml;
+use nvml_wrapper::high_level::power_budget::{BudgetPolicy, PowerBudget};
+
+# fn main() -> Result<(), nvml_wrapper::error::NvmlError> {
+let nvml = Nvml::init()?;
+let mut devices = vec![nvml.device_by_index(0)?, nvml.device_by_index(1)?];
+
+// 1.2 kW across both GPUs, in milliwatts
+let budget = PowerBudget::new(1_200_000, BudgetPolicy::ProportionalToMax);
+let applied = budget.apply(&mut devices)?;
+
+println!("limits: {:?}", applied.limits);
+
+// Put the previous limits back
+applied.rollback(&mut devices).into_result().map_err(|(_, e)| e)?;
+# Ok(())
+# }
+```
+*/
+
+use crate::error::NvmlError;
+use crate::high_level::multi::MultiResult;
+use crate::structs::device::PowerManagementConstraints;
+use crate::Device;
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+
+/// How a `PowerBudget` is split between devices.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BudgetPolicy {
+    /// Every device gets the same share.
+    Equal,
+    /// Every device gets a share proportional to its maximum power limit.
+    ProportionalToMax,
+    /// Every device gets a share proportional to its weight, given in the
+    /// order of the devices.
+    Weighted(Vec<f64>),
+}
+
+/// A total power budget, in milliwatts, to split between devices.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PowerBudget {
+    /// The total power budget, in milliwatts.
+    pub total: u32,
+    /// How the budget is split.
+    pub policy: BudgetPolicy,
+}
+
+/// The result of `PowerBudget::apply()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AppliedBudget {
+    /// The power limits that were in place before, in milliwatts, in the
+    /// order of the devices.
+    pub previous: Vec<u32>,
+    /// The power limits that were set, in milliwatts, in the order of the
+    /// devices.
+    pub limits: Vec<u32>,
+}
+
+impl PowerBudget {
+    /// Create a budget of `total` milliwatts, split according to `policy`.
+    pub fn new(total: u32, policy: BudgetPolicy) -> Self {
+        Self { total, policy }
+    }
+
+    /**
+    Split this budget between devices with the given `constraints`.
+
+    Each share is kept within its device's constraints, with whatever a
+    clamped device can't take going to the others. If the budget is more than
+    all devices can take, every device gets its maximum limit.
+
+    # Errors
+
+    * `InvalidArg`, if the budget is less than the sum of the minimum limits,
+      or the weights of `BudgetPolicy::Weighted` don't match the devices or
+      aren't finite and non-negative
+    */
+    pub fn allocate(
+        &self,
+        constraints: &[PowerManagementConstraints],
+    ) -> Result<Vec<u32>, NvmlError> {
+        let weights: Vec<f64> = match &self.policy {
+            BudgetPolicy::Equal => vec![1.0; constraints.len()],
+            BudgetPolicy::ProportionalToMax => {
+                constraints.iter().map(|c| c.max_limit as f64).collect()
+            }
+            BudgetPolicy::Weighted(weights) => weights.clone(),
+        };
+
+        if weights.len() != constraints.len()
+            || weights.iter().any(|w| !w.is_finite() || *w < 0.0)
+            || constraints.iter().any(|c| c.min_limit > c.max_limit)
+        {
+            return Err(NvmlError::InvalidArg);
+        }
+
+        let total = self.total as u64;
+        let min_sum: u64 = constraints.iter().map(|c| c.min_limit as u64).sum();
+        let max_sum: u64 = constraints.iter().map(|c| c.max_limit as u64).sum();
+
+        if total < min_sum {
+            return Err(NvmlError::InvalidArg);
+        }
+
+        if total >= max_sum {
+            return Ok(constraints.iter().map(|c| c.max_limit).collect());
+        }
+
+        let share = |scale: f64, i: usize| {
+            let c = &constraints[i];
+
+            (scale * weights[i]).clamp(c.min_limit as f64, c.max_limit as f64)
+        };
+        let allocated = |scale: f64| (0..constraints.len()).map(|i| share(scale, i)).sum::<f64>();
+
+        // Find the scale at which the clamped shares add up to the budget;
+        // the sum only grows with the scale
+        let mut low = 0.0;
+        let mut high = constraints
+            .iter()
+            .zip(&weights)
+            .filter(|(_, w)| **w > 0.0)
+            .map(|(c, w)| c.max_limit as f64 / w)
+            .fold(0.0, f64::max);
+
+        for _ in 0..100 {
+            let mid = (low + high) / 2.0;
+
+            if allocated(mid) > total as f64 {
+                high = mid;
+            } else {
+                low = mid;
+            }
+        }
+
+        let mut limits: Vec<u32> = (0..constraints.len())
+            .map(|i| share(low, i).floor() as u32)
+            .collect();
+
+        // Hand out what rounding down left over, a milliwatt at a time
+        let mut left = total - limits.iter().map(|&l| l as u64).sum::<u64>();
+
+        while left > 0 {
+            let mut gave = false;
+
+            for (limit, c) in limits.iter_mut().zip(constraints) {
+                if left > 0 && *limit < c.max_limit {
+                    *limit += 1;
+                    left -= 1;
+                    gave = true;
+                }
+            }
+
+            if !gave {
+                break;
+            }
+        }
+
+        Ok(limits)
+    }
+
+    /**
+    Split this budget between `devices` and set their power management
+    limits accordingly.
+
+    If setting a limit fails, the limits already set are put back before the
+    error is returned.
+
+    # Errors
+
+    * Any error returned by `Device::power_management_limit_constraints()`,
+      `Device::power_management_limit()` or `Device::set_power_management_limit()`
+    * Any error returned by `PowerBudget::allocate()`
+    */
+    pub fn apply(&self, devices: &mut [Device]) -> Result<AppliedBudget, NvmlError> {
+        let constraints = devices
+            .iter()
+            .map(Device::power_management_limit_constraints)
+            .collect::<Result<Vec<_>, _>>()?;
+        let previous = devices
+            .iter()
+            .map(Device::power_management_limit)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let limits = self.allocate(&constraints)?;
+
+        for i in 0..devices.len() {
+            if let Err(e) = devices[i].set_power_management_limit(limits[i]) {
+                for (device, &limit) in devices[..i].iter_mut().zip(&previous) {
+                    // The original error is the one worth reporting
+                    let _ = device.set_power_management_limit(limit);
+                }
+
+                return Err(e);
+            }
+        }
+
+        Ok(AppliedBudget { previous, limits })
+    }
+}
+
+impl AppliedBudget {
+    /**
+    Put back the power limits that were in place before the budget was
+    applied to `devices`, which must be the same devices in the same order.
+
+    Every device is attempted; the result for each is keyed by its position
+    in `devices`.
+    */
+    pub fn rollback(&self, devices: &mut [Device]) -> MultiResult<()> {
+        devices
+            .iter_mut()
+            .zip(&self.previous)
+            .enumerate()
+            .map(|(i, (device, &limit))| (i as u32, device.set_power_management_limit(limit)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn constraints(limits: &[(u32, u32)]) -> Vec<PowerManagementConstraints> {
+        limits
+            .iter()
+            .map(|&(min_limit, max_limit)| PowerManagementConstraints {
+                min_limit,
+                max_limit,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn allocate_equal() {
+        let c = constraints(&[(100_000, 400_000), (100_000, 400_000), (100_000, 400_000)]);
+        let limits = PowerBudget::new(600_000, BudgetPolicy::Equal)
+            .allocate(&c)
+            .unwrap();
+
+        assert_eq!(limits, vec![200_000, 200_000, 200_000]);
+
+        // Whatever a device can't take goes to the others
+        let c = constraints(&[(100_000, 150_000), (100_000, 400_000), (100_000, 400_000)]);
+        let limits = PowerBudget::new(700_000, BudgetPolicy::Equal)
+            .allocate(&c)
+            .unwrap();
+
+        assert_eq!(limits, vec![150_000, 275_000, 275_000]);
+
+        // Nothing goes below the minimum limit
+        let c = constraints(&[(300_000, 400_000), (100_000, 400_000)]);
+        let limits = PowerBudget::new(450_000, BudgetPolicy::Equal)
+            .allocate(&c)
+            .unwrap();
+
+        assert_eq!(limits, vec![300_000, 150_000]);
+    }
+
+    #[test]
+    fn allocate_proportional_and_weighted() {
+        let c = constraints(&[(100_000, 300_000), (100_000, 600_000)]);
+        let limits = PowerBudget::new(600_000, BudgetPolicy::ProportionalToMax)
+            .allocate(&c)
+            .unwrap();
+
+        assert_eq!(limits, vec![200_000, 400_000]);
+
+        let limits = PowerBudget::new(500_000, BudgetPolicy::Weighted(vec![1.0, 0.0]))
+            .allocate(&c)
+            .unwrap();
+
+        assert_eq!(limits, vec![300_000, 200_000]);
+    }
+
+    #[test]
+    fn allocate_rounding_and_bounds() {
+        let c = constraints(&[(0, 1000), (0, 1000), (0, 1000)]);
+        let limits = PowerBudget::new(1000, BudgetPolicy::Equal)
+            .allocate(&c)
+            .unwrap();
+
+        assert_eq!(limits.iter().sum::<u32>(), 1000);
+        assert!(limits.iter().all(|&l| (333..=334).contains(&l)));
+
+        // More than the devices can take
+        let limits = PowerBudget::new(5000, BudgetPolicy::Equal)
+            .allocate(&c)
+            .unwrap();
+
+        assert_eq!(limits, vec![1000, 1000, 1000]);
+
+        let c = constraints(&[(500, 1000), (500, 1000)]);
+
+        assert!(matches!(
+            PowerBudget::new(900, BudgetPolicy::Equal).allocate(&c),
+            Err(NvmlError::InvalidArg)
+        ));
+        assert!(matches!(
+            PowerBudget::new(1500, BudgetPolicy::Weighted(vec![1.0])).allocate(&c),
+            Err(NvmlError::InvalidArg)
+        ));
+        assert!(matches!(
+            PowerBudget::new(1500, BudgetPolicy::Weighted(vec![1.0, -1.0])).allocate(&c),
+            Err(NvmlError::InvalidArg)
+        ));
+    }
+}
@@ -0,0 +1,196 @@
+/*!
+Per-process views of engine usage.
+
+NVML reports SM / memory / encoder / decoder utilization, encoder sessions and
+frame buffer capture sessions through separate calls. `ProcessEngineUsage`
+joins them by PID so that everything a process is doing on a `Device` can be
+looked at in one place.
+*/
+
+use crate::error::NvmlError;
+use crate::struct_wrappers::device::{
+    EncoderSessionInfo, FbcSessionInfo, ProcessUtilizationSample,
+};
+use crate::Device;
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Engine usage of a single process on a `Device`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessEngineUsage {
+    pub pid: u32,
+    /// The most recent utilization sample for this process, if one was
+    /// reported during the sample period.
+    pub utilization: Option<ProcessUtilizationSample>,
+    /// Encoder sessions owned by this process.
+    pub encoder_sessions: Vec<EncoderSessionInfo>,
+    /// Frame buffer capture sessions owned by this process.
+    pub fbc_sessions: Vec<FbcSessionInfo>,
+}
+
+impl ProcessEngineUsage {
+    fn new(pid: u32) -> Self {
+        Self {
+            pid,
+            utilization: None,
+            encoder_sessions: vec![],
+            fbc_sessions: vec![],
+        }
+    }
+
+    /**
+    Join utilization samples, encoder sessions and FBC sessions by PID.
+
+    If several utilization samples exist for the same PID, the one with the
+    latest timestamp is kept. The returned records are sorted by PID.
+    */
+    pub fn join<S, E, F>(samples: S, encoder_sessions: E, fbc_sessions: F) -> Vec<Self>
+    where
+        S: IntoIterator<Item = ProcessUtilizationSample>,
+        E: IntoIterator<Item = EncoderSessionInfo>,
+        F: IntoIterator<Item = FbcSessionInfo>,
+    {
+        let mut map: BTreeMap<u32, Self> = BTreeMap::new();
+
+        for sample in samples {
+            let entry = map
+                .entry(sample.pid)
+                .or_insert_with(|| Self::new(sample.pid));
+
+            match entry.utilization {
+                Some(ref existing) if existing.timestamp >= sample.timestamp => {}
+                _ => entry.utilization = Some(sample),
+            }
+        }
+
+        for session in encoder_sessions {
+            map.entry(session.pid)
+                .or_insert_with(|| Self::new(session.pid))
+                .encoder_sessions
+                .push(session);
+        }
+
+        for session in fbc_sessions {
+            map.entry(session.pid)
+                .or_insert_with(|| Self::new(session.pid))
+                .fbc_sessions
+                .push(session);
+        }
+
+        map.into_values().collect()
+    }
+
+    /**
+    Query the given `Device` and join the results by PID.
+
+    `last_seen_timestamp` is passed through to `Device::process_utilization_stats()`.
+
+    A `NotSupported` error from any one of the underlying calls (and `NotFound`
+    from `process_utilization_stats()`, which is returned when there are no
+    samples) is treated as that source being empty rather than as an error.
+
+    # Errors
+
+    Any other error returned by `Device::process_utilization_stats()`,
+    `Device::encoder_sessions()` or `Device::fbc_sessions_info()`.
+    */
+    pub fn collect<T>(device: &Device, last_seen_timestamp: T) -> Result<Vec<Self>, NvmlError>
+    where
+        T: Into<Option<u64>>,
+    {
+        fn or_empty<T>(res: Result<Vec<T>, NvmlError>) -> Result<Vec<T>, NvmlError> {
+            match res {
+                Err(NvmlError::NotSupported) | Err(NvmlError::NotFound) => Ok(vec![]),
+                other => other,
+            }
+        }
+
+        let samples = or_empty(device.process_utilization_stats(last_seen_timestamp))?;
+        let encoder_sessions = or_empty(device.encoder_sessions())?;
+        let fbc_sessions = or_empty(device.fbc_sessions_info())?;
+
+        Ok(Self::join(samples, encoder_sessions, fbc_sessions))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bitmasks::device::FbcFlags;
+    use crate::enum_wrappers::device::{EncoderType, FbcSessionType};
+    use crate::test_utils::*;
+
+    fn sample(pid: u32, timestamp: u64, sm_util: u32) -> ProcessUtilizationSample {
+        ProcessUtilizationSample {
+            pid,
+            timestamp,
+            sm_util,
+            mem_util: 0,
+            enc_util: 0,
+            dec_util: 0,
+        }
+    }
+
+    fn encoder_session(session_id: u32, pid: u32) -> EncoderSessionInfo {
+        EncoderSessionInfo {
+            session_id,
+            pid,
+            vgpu_instance: None,
+            codec_type: EncoderType::H264,
+            hres: 1920,
+            vres: 1080,
+            average_fps: 60,
+            average_latency: 1000,
+        }
+    }
+
+    fn fbc_session(session_id: u32, pid: u32) -> FbcSessionInfo {
+        FbcSessionInfo {
+            session_id,
+            pid,
+            vgpu_instance: None,
+            display_ordinal: 0,
+            session_type: FbcSessionType::ToSys,
+            session_flags: FbcFlags::empty(),
+            hres_max: 1920,
+            vres_max: 1080,
+            hres: 1920,
+            vres: 1080,
+            average_fps: 60,
+            average_latency: 1000,
+        }
+    }
+
+    #[test]
+    fn join_by_pid() {
+        let usage = ProcessEngineUsage::join(
+            vec![sample(20, 2, 50), sample(10, 1, 5), sample(20, 1, 90)],
+            vec![encoder_session(1, 20), encoder_session(2, 30)],
+            vec![fbc_session(3, 20)],
+        );
+
+        assert_eq!(
+            usage.iter().map(|u| u.pid).collect::<Vec<_>>(),
+            vec![10, 20, 30]
+        );
+
+        assert_eq!(usage[0].utilization, Some(sample(10, 1, 5)));
+        assert!(usage[0].encoder_sessions.is_empty());
+
+        assert_eq!(usage[1].utilization, Some(sample(20, 2, 50)));
+        assert_eq!(usage[1].encoder_sessions, vec![encoder_session(1, 20)]);
+        assert_eq!(usage[1].fbc_sessions, vec![fbc_session(3, 20)]);
+
+        assert_eq!(usage[2].utilization, None);
+        assert_eq!(usage[2].encoder_sessions.len(), 1);
+        assert!(usage[2].fbc_sessions.is_empty());
+    }
+
+    #[test]
+    fn collect() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| ProcessEngineUsage::collect(device, None))
+    }
+}
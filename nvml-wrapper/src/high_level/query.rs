@@ -0,0 +1,376 @@
+/*!
+Query device metrics by name.
+
+This module lets you describe the values you want from a `Device` with plain
+strings (the same dotted names `nvidia-smi --query-gpu` uses where one exists)
+and get typed values back. It is intended for exporters and other tools that
+want to read the set of metrics to collect from a config file rather than
+having it compiled in.
+
+```no_run
+# use nvml_wrapper::Nvml;
+use nvml_wrapper::high_level::query::Query;
+
+# fn main() -> Result<(), Box<dyn std::error::Error>> {
+let nvml = Nvml::init()?;
+let device = nvml.device_by_index(0)?;
+
+let query: Query = "utilization.gpu, memory.used, clocks.sm".parse()?;
+
+for (metric, value) in query.run(&device) {
+    match value {
+        Ok(value) => println!("{}: {}", metric, value),
+        Err(e) => println!("{}: {}", metric, e),
+    }
+}
+# Ok(())
+# }
+```
+*/
+
+use crate::enum_wrappers::device::{Clock, PerformanceState, TemperatureSensor};
+use crate::error::NvmlError;
+use crate::Device;
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Returned when a metric name is not recognized.
+#[derive(Error, Debug, Clone, Eq, PartialEq, Hash)]
+#[error("unknown metric name: {0}")]
+pub struct UnknownMetric(pub String);
+
+/// The metrics that can be queried by name.
+///
+/// Use `Metric::name()` to get the string form of a metric and `str::parse()`
+/// to go the other way.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Metric {
+    /// `name`
+    Name,
+    /// `uuid`
+    Uuid,
+    /// `pci.bus_id`
+    PciBusId,
+    /// `vbios_version`
+    VbiosVersion,
+    /// `pstate`
+    PerformanceState,
+    /// `utilization.gpu`
+    UtilizationGpu,
+    /// `utilization.memory`
+    UtilizationMemory,
+    /// `utilization.encoder`
+    UtilizationEncoder,
+    /// `utilization.decoder`
+    UtilizationDecoder,
+    /// `memory.total`
+    MemoryTotal,
+    /// `memory.reserved`
+    MemoryReserved,
+    /// `memory.used`
+    MemoryUsed,
+    /// `memory.free`
+    MemoryFree,
+    /// `clocks.gr`
+    ClocksGraphics,
+    /// `clocks.sm`
+    ClocksSm,
+    /// `clocks.mem`
+    ClocksMemory,
+    /// `clocks.video`
+    ClocksVideo,
+    /// `clocks.max.gr`
+    MaxClocksGraphics,
+    /// `clocks.max.sm`
+    MaxClocksSm,
+    /// `clocks.max.mem`
+    MaxClocksMemory,
+    /// `power.draw`
+    PowerDraw,
+    /// `power.limit`
+    PowerLimit,
+    /// `enforced.power.limit`
+    EnforcedPowerLimit,
+    /// `temperature.gpu`
+    TemperatureGpu,
+    /// `fan.speed` (of the first fan)
+    FanSpeed,
+    /// `pcie.link.gen.current`
+    PcieLinkGenCurrent,
+    /// `pcie.link.width.current`
+    PcieLinkWidthCurrent,
+    /// `energy.total`
+    TotalEnergy,
+}
+
+impl Metric {
+    /// Every metric that can be queried, in declaration order.
+    pub const ALL: &'static [Metric] = &[
+        Metric::Name,
+        Metric::Uuid,
+        Metric::PciBusId,
+        Metric::VbiosVersion,
+        Metric::PerformanceState,
+        Metric::UtilizationGpu,
+        Metric::UtilizationMemory,
+        Metric::UtilizationEncoder,
+        Metric::UtilizationDecoder,
+        Metric::MemoryTotal,
+        Metric::MemoryReserved,
+        Metric::MemoryUsed,
+        Metric::MemoryFree,
+        Metric::ClocksGraphics,
+        Metric::ClocksSm,
+        Metric::ClocksMemory,
+        Metric::ClocksVideo,
+        Metric::MaxClocksGraphics,
+        Metric::MaxClocksSm,
+        Metric::MaxClocksMemory,
+        Metric::PowerDraw,
+        Metric::PowerLimit,
+        Metric::EnforcedPowerLimit,
+        Metric::TemperatureGpu,
+        Metric::FanSpeed,
+        Metric::PcieLinkGenCurrent,
+        Metric::PcieLinkWidthCurrent,
+        Metric::TotalEnergy,
+    ];
+
+    /// The string name of this metric.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Metric::Name => "name",
+            Metric::Uuid => "uuid",
+            Metric::PciBusId => "pci.bus_id",
+            Metric::VbiosVersion => "vbios_version",
+            Metric::PerformanceState => "pstate",
+            Metric::UtilizationGpu => "utilization.gpu",
+            Metric::UtilizationMemory => "utilization.memory",
+            Metric::UtilizationEncoder => "utilization.encoder",
+            Metric::UtilizationDecoder => "utilization.decoder",
+            Metric::MemoryTotal => "memory.total",
+            Metric::MemoryReserved => "memory.reserved",
+            Metric::MemoryUsed => "memory.used",
+            Metric::MemoryFree => "memory.free",
+            Metric::ClocksGraphics => "clocks.gr",
+            Metric::ClocksSm => "clocks.sm",
+            Metric::ClocksMemory => "clocks.mem",
+            Metric::ClocksVideo => "clocks.video",
+            Metric::MaxClocksGraphics => "clocks.max.gr",
+            Metric::MaxClocksSm => "clocks.max.sm",
+            Metric::MaxClocksMemory => "clocks.max.mem",
+            Metric::PowerDraw => "power.draw",
+            Metric::PowerLimit => "power.limit",
+            Metric::EnforcedPowerLimit => "enforced.power.limit",
+            Metric::TemperatureGpu => "temperature.gpu",
+            Metric::FanSpeed => "fan.speed",
+            Metric::PcieLinkGenCurrent => "pcie.link.gen.current",
+            Metric::PcieLinkWidthCurrent => "pcie.link.width.current",
+            Metric::TotalEnergy => "energy.total",
+        }
+    }
+
+    /**
+    Read the value of this metric from the given `Device`.
+
+    # Errors
+
+    Any error returned by the `Device` method backing this metric.
+    */
+    pub fn query(&self, device: &Device) -> Result<QueryValue, NvmlError> {
+        use self::QueryValue::*;
+
+        Ok(match *self {
+            Metric::Name => Text(device.name()?),
+            Metric::Uuid => Text(device.uuid()?),
+            Metric::PciBusId => Text(device.pci_info()?.bus_id),
+            Metric::VbiosVersion => Text(device.vbios_version()?),
+            Metric::PerformanceState => PerformanceState(device.performance_state()?),
+            Metric::UtilizationGpu => Percent(device.utilization_rates()?.gpu),
+            Metric::UtilizationMemory => Percent(device.utilization_rates()?.memory),
+            Metric::UtilizationEncoder => Percent(device.encoder_utilization()?.utilization),
+            Metric::UtilizationDecoder => Percent(device.decoder_utilization()?.utilization),
+            Metric::MemoryTotal => Bytes(device.memory_info()?.total),
+            Metric::MemoryReserved => Bytes(device.memory_info()?.reserved),
+            Metric::MemoryUsed => Bytes(device.memory_info()?.used),
+            Metric::MemoryFree => Bytes(device.memory_info()?.free),
+            Metric::ClocksGraphics => MegaHertz(device.clock_info(Clock::Graphics)?),
+            Metric::ClocksSm => MegaHertz(device.clock_info(Clock::SM)?),
+            Metric::ClocksMemory => MegaHertz(device.clock_info(Clock::Memory)?),
+            Metric::ClocksVideo => MegaHertz(device.clock_info(Clock::Video)?),
+            Metric::MaxClocksGraphics => MegaHertz(device.max_clock_info(Clock::Graphics)?),
+            Metric::MaxClocksSm => MegaHertz(device.max_clock_info(Clock::SM)?),
+            Metric::MaxClocksMemory => MegaHertz(device.max_clock_info(Clock::Memory)?),
+            Metric::PowerDraw => Milliwatts(device.power_usage()?),
+            Metric::PowerLimit => Milliwatts(device.power_management_limit()?),
+            Metric::EnforcedPowerLimit => Milliwatts(device.enforced_power_limit()?),
+            Metric::TemperatureGpu => Celsius(device.temperature(TemperatureSensor::Gpu)?),
+            Metric::FanSpeed => Percent(device.fan_speed(0)?),
+            Metric::PcieLinkGenCurrent => Count(device.current_pcie_link_gen()?),
+            Metric::PcieLinkWidthCurrent => Count(device.current_pcie_link_width()?),
+            Metric::TotalEnergy => Millijoules(device.total_energy_consumption()?),
+        })
+    }
+}
+
+impl FromStr for Metric {
+    type Err = UnknownMetric;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        Metric::ALL
+            .iter()
+            .find(|m| m.name() == s)
+            .copied()
+            .ok_or_else(|| UnknownMetric(s.into()))
+    }
+}
+
+impl Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A typed value obtained from `Metric::query()`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum QueryValue {
+    Text(String),
+    PerformanceState(PerformanceState),
+    /// A percentage, 0 to 100.
+    Percent(u32),
+    Bytes(u64),
+    MegaHertz(u32),
+    Milliwatts(u32),
+    Millijoules(u64),
+    /// Degrees Celsius.
+    Celsius(u32),
+    /// A plain unitless number.
+    Count(u32),
+}
+
+impl QueryValue {
+    /// The value as a number, if it is numeric.
+    ///
+    /// Useful for exporters that only deal in numbers.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            QueryValue::Text(_) | QueryValue::PerformanceState(_) => None,
+            QueryValue::Percent(v)
+            | QueryValue::MegaHertz(v)
+            | QueryValue::Milliwatts(v)
+            | QueryValue::Celsius(v)
+            | QueryValue::Count(v) => Some(v as f64),
+            QueryValue::Bytes(v) | QueryValue::Millijoules(v) => Some(v as f64),
+        }
+    }
+}
+
+impl Display for QueryValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryValue::Text(s) => f.write_str(s),
+            QueryValue::PerformanceState(p) => write!(f, "{:?}", p),
+            QueryValue::Percent(v) => write!(f, "{} %", v),
+            QueryValue::Bytes(v) => write!(f, "{} B", v),
+            QueryValue::MegaHertz(v) => write!(f, "{} MHz", v),
+            QueryValue::Milliwatts(v) => write!(f, "{} mW", v),
+            QueryValue::Millijoules(v) => write!(f, "{} mJ", v),
+            QueryValue::Celsius(v) => write!(f, "{} C", v),
+            QueryValue::Count(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+/// A list of metrics to read from a `Device`.
+///
+/// Parse one from a comma-separated list of metric names.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Query {
+    pub metrics: Vec<Metric>,
+}
+
+impl Query {
+    /// Create a query for the given metrics.
+    pub fn new(metrics: Vec<Metric>) -> Self {
+        Self { metrics }
+    }
+
+    /// Parse a query from an iterator of metric names.
+    pub fn from_names<I, S>(names: I) -> Result<Self, UnknownMetric>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Ok(Self {
+            metrics: names
+                .into_iter()
+                .map(|n| n.as_ref().parse())
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /**
+    Read every metric in this query from the given `Device`.
+
+    Metrics are read one at a time and a failure to read one of them does not
+    stop the others from being read.
+    */
+    pub fn run(&self, device: &Device) -> Vec<(Metric, Result<QueryValue, NvmlError>)> {
+        self.metrics.iter().map(|m| (*m, m.query(device))).collect()
+    }
+}
+
+impl FromStr for Query {
+    type Err = UnknownMetric;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_names(s.split(',').filter(|n| !n.trim().is_empty()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::*;
+
+    #[test]
+    fn metric_names_round_trip() {
+        for metric in Metric::ALL {
+            assert_eq!(metric.name().parse::<Metric>(), Ok(*metric));
+        }
+    }
+
+    #[test]
+    fn parse_query() {
+        let query: Query = " utilization.gpu,memory.used , clocks.sm,".parse().unwrap();
+
+        assert_eq!(
+            query.metrics,
+            vec![Metric::UtilizationGpu, Metric::MemoryUsed, Metric::ClocksSm]
+        );
+        assert_eq!(
+            "memory.used,bogus".parse::<Query>(),
+            Err(UnknownMetric("bogus".into()))
+        );
+    }
+
+    #[test]
+    fn query_all() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            Ok(Query::new(Metric::ALL.to_vec())
+                .run(device)
+                .into_iter()
+                .map(|(metric, value)| (metric, value.ok()))
+                .collect::<Vec<_>>())
+        })
+    }
+}
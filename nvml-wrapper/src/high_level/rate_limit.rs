@@ -0,0 +1,368 @@
+/*!
+Rate limiting for device management operations.
+
+Control loops built on top of the clock, power and fan setters can misbehave
+and hammer a shared system with changes. `RateLimitedDevice` wraps a `Device`
+and lets mutating calls through at no more than a configured rate, optionally
+reporting every attempt to an audit hook.
+
+```no_run
+use nvml_wrapper::Nvml;
+use nvml_wrapper::high_level::rate_limit::{RateLimitedDevice, RateLimiter};
+
+# fn main() -> Result<(), Box<dyn std::error::Error>> {
+let nvml = Nvml::init()?;
+let device = nvml.device_by_index(0)?;
+
+// At most two changes per second, with bursts of up to four.
+let mut limited = RateLimitedDevice::new(device, RateLimiter::new(2.0, 4))
+    .with_audit_hook(|record| println!("{:?}", record));
+
+limited.set_power_management_limit(250_000)?;
+# Ok(())
+# }
+```
+*/
+
+use crate::enum_wrappers::device::{Clock, PerformanceState};
+use crate::enums::device::{FanControlPolicy, GpuLockedClocksSetting};
+use crate::error::NvmlError;
+use crate::Device;
+use std::fmt;
+use std::time::{Duration, Instant, SystemTime};
+use thiserror::Error;
+
+/// Errors returned by the operations on a `RateLimitedDevice`.
+#[derive(Error, Debug)]
+pub enum ControlError {
+    /// The operation was not attempted because the rate limit was exceeded.
+    #[error("rate limit exceeded, retry in {retry_after:?}")]
+    RateLimited {
+        /// How long until the operation would be allowed.
+        retry_after: Duration,
+    },
+    /// The operation was attempted and NVML returned an error.
+    #[error(transparent)]
+    Nvml(#[from] NvmlError),
+}
+
+/**
+A token bucket rate limiter.
+
+Tokens are refilled continuously at `qps` per second, up to `burst`. Each
+allowed operation consumes one token.
+*/
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    qps: f64,
+    burst: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /**
+    Create a rate limiter allowing `qps` operations per second on average and
+    bursts of up to `burst` operations.
+
+    The bucket starts full. A `burst` of zero is treated as one.
+
+    # Panics
+
+    If `qps` is not a finite, positive number.
+    */
+    pub fn new(qps: f64, burst: u32) -> Self {
+        assert!(qps.is_finite() && qps > 0.0, "qps must be positive");
+
+        let burst = burst.max(1) as f64;
+
+        Self {
+            qps,
+            burst,
+            tokens: burst,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * self.qps).min(self.burst);
+        self.last_refill = now;
+    }
+
+    /**
+    Try to take a token at time `now`.
+
+    Returns `Err` with the time to wait until a token is available if the
+    bucket is empty.
+    */
+    pub fn try_acquire_at(&mut self, now: Instant) -> Result<(), Duration> {
+        self.refill(now);
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / self.qps))
+        }
+    }
+
+    /// Try to take a token now.
+    pub fn try_acquire(&mut self) -> Result<(), Duration> {
+        self.try_acquire_at(Instant::now())
+    }
+}
+
+/// What happened to an operation submitted to a `RateLimitedDevice`.
+#[derive(Debug)]
+pub enum AuditOutcome<'a> {
+    /// The operation was performed and succeeded.
+    Succeeded,
+    /// The operation was performed and NVML returned an error.
+    Failed(&'a NvmlError),
+    /// The operation was rejected by the rate limiter.
+    RateLimited { retry_after: Duration },
+}
+
+/// A record of an operation submitted to a `RateLimitedDevice`.
+#[derive(Debug)]
+pub struct AuditRecord<'a> {
+    /// The name of the operation, e.g. `"set_power_management_limit"`.
+    pub operation: &'static str,
+    /// When the operation was submitted.
+    pub time: SystemTime,
+    pub outcome: AuditOutcome<'a>,
+}
+
+type AuditHook = Box<dyn FnMut(&AuditRecord) + Send>;
+
+/// A `Device` whose mutating operations are rate limited.
+pub struct RateLimitedDevice<'nvml> {
+    device: Device<'nvml>,
+    limiter: RateLimiter,
+    audit: Option<AuditHook>,
+}
+
+impl<'nvml> fmt::Debug for RateLimitedDevice<'nvml> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RateLimitedDevice")
+            .field("device", &self.device)
+            .field("limiter", &self.limiter)
+            .field("audit", &self.audit.is_some())
+            .finish()
+    }
+}
+
+impl<'nvml> RateLimitedDevice<'nvml> {
+    /// Wrap `device`, limiting its operations with `limiter`.
+    pub fn new(device: Device<'nvml>, limiter: RateLimiter) -> Self {
+        Self {
+            device,
+            limiter,
+            audit: None,
+        }
+    }
+
+    /// Call `hook` for every operation submitted, including rejected ones.
+    pub fn with_audit_hook<F>(mut self, hook: F) -> Self
+    where
+        F: FnMut(&AuditRecord) + Send + 'static,
+    {
+        self.audit = Some(Box::new(hook));
+        self
+    }
+
+    /// Access the wrapped `Device` for read-only queries.
+    ///
+    /// Queries are not rate limited.
+    pub fn device(&self) -> &Device<'nvml> {
+        &self.device
+    }
+
+    /// Unwrap into the inner `Device`.
+    pub fn into_inner(self) -> Device<'nvml> {
+        self.device
+    }
+
+    /**
+    Run an arbitrary mutating operation through the rate limiter.
+
+    `operation` names the operation in audit records.
+
+    # Errors
+
+    * `RateLimited`, if the rate limit is exceeded; `f` is not called
+    * `Nvml`, if `f` returns an error
+    */
+    pub fn control<T, F>(&mut self, operation: &'static str, f: F) -> Result<T, ControlError>
+    where
+        F: FnOnce(&mut Device<'nvml>) -> Result<T, NvmlError>,
+    {
+        let time = SystemTime::now();
+
+        if let Err(retry_after) = self.limiter.try_acquire() {
+            self.audit(AuditRecord {
+                operation,
+                time,
+                outcome: AuditOutcome::RateLimited { retry_after },
+            });
+
+            return Err(ControlError::RateLimited { retry_after });
+        }
+
+        let res = f(&mut self.device);
+
+        let outcome = match res {
+            Ok(_) => AuditOutcome::Succeeded,
+            Err(ref e) => AuditOutcome::Failed(e),
+        };
+        self.audit(AuditRecord {
+            operation,
+            time,
+            outcome,
+        });
+
+        res.map_err(ControlError::from)
+    }
+
+    fn audit(&mut self, record: AuditRecord) {
+        if let Some(ref mut hook) = self.audit {
+            hook(&record);
+        }
+    }
+
+    /// Rate limited `Device::set_applications_clocks()`.
+    pub fn set_applications_clocks(
+        &mut self,
+        mem_clock: u32,
+        graphics_clock: u32,
+    ) -> Result<(), ControlError> {
+        self.control("set_applications_clocks", |d| {
+            d.set_applications_clocks(mem_clock, graphics_clock)
+        })
+    }
+
+    /// Rate limited `Device::reset_applications_clocks()`.
+    pub fn reset_applications_clocks(&mut self) -> Result<(), ControlError> {
+        self.control("reset_applications_clocks", |d| {
+            d.reset_applications_clocks()
+        })
+    }
+
+    /// Rate limited `Device::set_gpu_locked_clocks()`.
+    pub fn set_gpu_locked_clocks(
+        &mut self,
+        setting: GpuLockedClocksSetting,
+    ) -> Result<(), ControlError> {
+        self.control("set_gpu_locked_clocks", |d| {
+            d.set_gpu_locked_clocks(setting)
+        })
+    }
+
+    /// Rate limited `Device::reset_gpu_locked_clocks()`.
+    pub fn reset_gpu_locked_clocks(&mut self) -> Result<(), ControlError> {
+        self.control("reset_gpu_locked_clocks", |d| d.reset_gpu_locked_clocks())
+    }
+
+    /// Rate limited `Device::set_mem_locked_clocks()`.
+    pub fn set_mem_locked_clocks(
+        &mut self,
+        min_clock_mhz: u32,
+        max_clock_mhz: u32,
+    ) -> Result<(), ControlError> {
+        self.control("set_mem_locked_clocks", |d| {
+            d.set_mem_locked_clocks(min_clock_mhz, max_clock_mhz)
+        })
+    }
+
+    /// Rate limited `Device::reset_mem_locked_clocks()`.
+    pub fn reset_mem_locked_clocks(&mut self) -> Result<(), ControlError> {
+        self.control("reset_mem_locked_clocks", |d| d.reset_mem_locked_clocks())
+    }
+
+    /// Rate limited `Device::set_clock_offset()`.
+    pub fn set_clock_offset(
+        &mut self,
+        clock_type: Clock,
+        power_state: PerformanceState,
+        offset: i32,
+    ) -> Result<(), ControlError> {
+        self.control("set_clock_offset", |d| {
+            d.set_clock_offset(clock_type, power_state, offset)
+        })
+    }
+
+    /// Rate limited `Device::set_gpc_clock_vf_offset()`.
+    pub fn set_gpc_clock_vf_offset(&mut self, offset: i32) -> Result<(), ControlError> {
+        self.control("set_gpc_clock_vf_offset", |d| {
+            d.set_gpc_clock_vf_offset(offset)
+        })
+    }
+
+    /// Rate limited `Device::set_mem_clock_vf_offset()`.
+    pub fn set_mem_clock_vf_offset(&mut self, offset: i32) -> Result<(), ControlError> {
+        self.control("set_mem_clock_vf_offset", |d| {
+            d.set_mem_clock_vf_offset(offset)
+        })
+    }
+
+    /// Rate limited `Device::set_power_management_limit()`.
+    pub fn set_power_management_limit(&mut self, limit: u32) -> Result<(), ControlError> {
+        self.control("set_power_management_limit", |d| {
+            d.set_power_management_limit(limit)
+        })
+    }
+
+    /// Rate limited `Device::set_fan_speed()`.
+    pub fn set_fan_speed(&mut self, fan_idx: u32, speed: u32) -> Result<(), ControlError> {
+        self.control("set_fan_speed", |d| d.set_fan_speed(fan_idx, speed))
+    }
+
+    /// Rate limited `Device::set_default_fan_speed()`.
+    pub fn set_default_fan_speed(&mut self, fan_idx: u32) -> Result<(), ControlError> {
+        self.control("set_default_fan_speed", |d| {
+            d.set_default_fan_speed(fan_idx)
+        })
+    }
+
+    /// Rate limited `Device::set_fan_control_policy()`.
+    pub fn set_fan_control_policy(
+        &mut self,
+        fan_idx: u32,
+        policy: FanControlPolicy,
+    ) -> Result<(), ControlError> {
+        self.control("set_fan_control_policy", |d| {
+            d.set_fan_control_policy(fan_idx, policy)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn token_bucket() {
+        let start = Instant::now();
+        let mut limiter = RateLimiter::new(2.0, 2);
+
+        assert!(limiter.try_acquire_at(start).is_ok());
+        assert!(limiter.try_acquire_at(start).is_ok());
+
+        let wait = limiter.try_acquire_at(start).unwrap_err();
+        assert_eq!(wait, Duration::from_millis(500));
+
+        assert!(limiter
+            .try_acquire_at(start + Duration::from_millis(500))
+            .is_ok());
+        assert!(limiter
+            .try_acquire_at(start + Duration::from_millis(500))
+            .is_err());
+
+        // Refill is capped at the burst size
+        let later = start + Duration::from_secs(60);
+        assert!(limiter.try_acquire_at(later).is_ok());
+        assert!(limiter.try_acquire_at(later).is_ok());
+        assert!(limiter.try_acquire_at(later).is_err());
+    }
+}
@@ -0,0 +1,352 @@
+/*!
+Opt-in recovery from driver reloads and lost GPUs.
+
+Every `Device` handle, and the `Nvml` instance itself, becomes useless when
+the driver is reloaded (e.g. on a driver update): calls start returning
+`Uninitialized`, `DriverNotLoaded` or `GpuLost` until NVML is initialized
+again. `RecoveringNvml` owns the `Nvml` instance, and when a call fails with
+one of those errors it shuts NVML down, initializes it again, resolves
+devices by UUID and retries the call, notifying a callback when it does.
+
+```no_run
+use nvml_wrapper::high_level::recovery::RecoveringNvml;
+
+# fn main() -> Result<(), nvml_wrapper::error::NvmlError> {
+let mut nvml = RecoveringNvml::new()?
+    .on_recovered(|event| eprintln!("NVML re-initialized after {}", event.trigger));
+
+let uuid = nvml.call(|nvml| nvml.device_by_index(0)?.uuid())?;
+
+loop {
+    let power = nvml.call_device(&uuid, |device| device.power_usage())?;
+    println!("{} mW", power);
+#   break;
+}
+# Ok(())
+# }
+```
+*/
+
+use crate::error::NvmlError;
+use crate::{Device, Nvml};
+use std::fmt;
+use std::time::SystemTime;
+
+/**
+Whether re-initializing NVML may make a call that failed with `error`
+succeed.
+
+This is the case for `Uninitialized`, `DriverNotLoaded`,
+`LibRmVersionMismatch` (the driver was updated underneath the process) and
+`GpuLost`. A GPU that has really fallen off the bus stays lost, which is why
+the number of retries is bounded.
+*/
+pub fn needs_recovery(error: &NvmlError) -> bool {
+    matches!(
+        error,
+        NvmlError::Uninitialized
+            | NvmlError::DriverNotLoaded
+            | NvmlError::LibRmVersionMismatch
+            | NvmlError::GpuLost
+    )
+}
+
+/// Passed to the callback registered with `RecoveringNvml::on_recovered()`.
+#[derive(Debug)]
+pub struct RecoveryEvent {
+    /// The error that triggered the recovery.
+    pub trigger: NvmlError,
+    /// When NVML was initialized again.
+    pub time: SystemTime,
+    /// The number of recoveries so far, including this one.
+    pub generation: u64,
+}
+
+type InitFn = Box<dyn Fn() -> Result<Nvml, NvmlError> + Send + Sync>;
+type RecoveredFn = Box<dyn FnMut(&RecoveryEvent) + Send>;
+
+/// An `Nvml` instance that is initialized again when calls fail in a way
+/// that suggests the driver was reloaded.
+pub struct RecoveringNvml {
+    init: InitFn,
+    nvml: Option<Nvml>,
+    max_retries: u32,
+    generation: u64,
+    on_recovered: Option<RecoveredFn>,
+}
+
+impl fmt::Debug for RecoveringNvml {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecoveringNvml")
+            .field("nvml", &self.nvml)
+            .field("max_retries", &self.max_retries)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+impl RecoveringNvml {
+    /**
+    Initialize NVML with `Nvml::init()`, which is also used to initialize it
+    again.
+
+    # Errors
+
+    Any error returned by `Nvml::init()`.
+    */
+    pub fn new() -> Result<Self, NvmlError> {
+        Self::with_init(Nvml::init)
+    }
+
+    /**
+    Initialize NVML with `init` (e.g. a closure calling `Nvml::builder()`),
+    which is also used to initialize it again.
+
+    # Errors
+
+    Any error returned by `init`.
+    */
+    pub fn with_init<F>(init: F) -> Result<Self, NvmlError>
+    where
+        F: Fn() -> Result<Nvml, NvmlError> + Send + Sync + 'static,
+    {
+        let nvml = init()?;
+
+        Ok(Self {
+            init: Box::new(init),
+            nvml: Some(nvml),
+            max_retries: 1,
+            generation: 0,
+            on_recovered: None,
+        })
+    }
+
+    /// Re-initialize and retry a failing call at most `max_retries` times
+    /// (once by default).
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Run `callback` every time NVML is successfully initialized again.
+    pub fn on_recovered<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(&RecoveryEvent) + Send + 'static,
+    {
+        self.on_recovered = Some(Box::new(callback));
+        self
+    }
+
+    /// The number of times NVML has been successfully initialized again.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /**
+    The current `Nvml` instance, if NVML is initialized.
+
+    The instance is replaced on recovery, so do not hold on to it (or to
+    `Device`s obtained from it) across calls to this struct's methods.
+    */
+    pub fn current(&self) -> Option<&Nvml> {
+        self.nvml.as_ref()
+    }
+
+    /**
+    Shut NVML down and initialize it again, whether or not any call has
+    failed.
+
+    `trigger` is passed on to the `on_recovered()` callback.
+
+    # Errors
+
+    Any error returned by the init function. NVML is then left uninitialized,
+    and the next call tries to initialize it again.
+    */
+    pub fn reinit(&mut self, trigger: NvmlError) -> Result<(), NvmlError> {
+        // Errors shutting down are ignored; the driver may well be gone
+        self.nvml = None;
+        self.nvml = Some((self.init)()?);
+        self.generation += 1;
+
+        if let Some(ref mut callback) = self.on_recovered {
+            callback(&RecoveryEvent {
+                trigger,
+                time: SystemTime::now(),
+                generation: self.generation,
+            });
+        }
+
+        Ok(())
+    }
+
+    /**
+    Run `f` with the `Nvml` instance, re-initializing NVML and running `f`
+    again if it fails with an error for which `needs_recovery()` is true.
+
+    `f` may run several times, so it should not have side effects that must
+    only happen once.
+
+    # Errors
+
+    * The error `f` returned, if it does not need recovery or retries are
+      exhausted
+    * Any error returned by the init function while recovering
+    */
+    pub fn call<T, F>(&mut self, f: F) -> Result<T, NvmlError>
+    where
+        F: FnMut(&Nvml) -> Result<T, NvmlError>,
+    {
+        let max_retries = self.max_retries;
+
+        retry(
+            self,
+            max_retries,
+            f,
+            |this| this.nvml.as_ref(),
+            Self::reinit,
+        )
+    }
+
+    /**
+    Run `f` with the device that has the given UUID, resolving the UUID
+    again after recovery.
+
+    The UUID is resolved on every call, since `Device` handles do not survive
+    recovery.
+
+    # Errors
+
+    * Any error returned by `Nvml::device_by_uuid()`
+    * Any error returned by `.call()`
+    */
+    pub fn call_device<T, F>(&mut self, uuid: &str, mut f: F) -> Result<T, NvmlError>
+    where
+        F: FnMut(&Device) -> Result<T, NvmlError>,
+    {
+        self.call(|nvml| f(&nvml.device_by_uuid(uuid)?))
+    }
+}
+
+/**
+The retry loop behind `RecoveringNvml::call()`, generic over the state so it
+can be tested without NVML.
+
+A missing instance counts as an `Uninitialized` failure.
+*/
+fn retry<S, N, T, F, G, R>(
+    state: &mut S,
+    max_retries: u32,
+    mut f: F,
+    get: G,
+    mut recover: R,
+) -> Result<T, NvmlError>
+where
+    F: FnMut(&N) -> Result<T, NvmlError>,
+    G: Fn(&S) -> Option<&N>,
+    R: FnMut(&mut S, NvmlError) -> Result<(), NvmlError>,
+{
+    let mut retries = 0;
+
+    loop {
+        let res = match get(state) {
+            Some(instance) => f(instance),
+            None => Err(NvmlError::Uninitialized),
+        };
+
+        match res {
+            Err(e) if needs_recovery(&e) && retries < max_retries => {
+                retries += 1;
+                recover(state, e)?;
+            }
+            res => return res,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // (instance, number of recoveries, whether recovering works)
+    type State = (Option<u32>, u32, bool);
+
+    fn recover(state: &mut State, _: NvmlError) -> Result<(), NvmlError> {
+        state.1 += 1;
+
+        match state.2 {
+            true => state.0 = Some(state.0.unwrap_or(0) + 1),
+            false => {
+                state.0 = None;
+                return Err(NvmlError::DriverNotLoaded);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn retries_after_recovery() {
+        let mut state: State = (Some(0), 0, true);
+
+        // Fails on the first instance only
+        let res = retry(
+            &mut state,
+            1,
+            |&n| match n {
+                0 => Err(NvmlError::Uninitialized),
+                n => Ok(n),
+            },
+            |s| s.0.as_ref(),
+            recover,
+        );
+        assert_eq!(res.unwrap(), 1);
+        assert_eq!(state.1, 1);
+
+        // Errors that don't need recovery are returned as-is
+        let res: Result<(), _> = retry(
+            &mut state,
+            1,
+            |_| Err(NvmlError::NotSupported),
+            |s| s.0.as_ref(),
+            recover,
+        );
+        assert!(matches!(res, Err(NvmlError::NotSupported)));
+        assert_eq!(state.1, 1);
+    }
+
+    #[test]
+    fn retries_are_bounded() {
+        let mut state: State = (Some(0), 0, true);
+
+        let res: Result<(), _> = retry(
+            &mut state,
+            2,
+            |_| Err(NvmlError::GpuLost),
+            |s| s.0.as_ref(),
+            recover,
+        );
+        assert!(matches!(res, Err(NvmlError::GpuLost)));
+        assert_eq!(state.1, 2);
+    }
+
+    #[test]
+    fn failed_recovery_is_retried_on_the_next_call() {
+        let mut state: State = (Some(0), 0, false);
+
+        let res = retry(
+            &mut state,
+            1,
+            |_| Err::<(), _>(NvmlError::Uninitialized),
+            |s| s.0.as_ref(),
+            recover,
+        );
+        assert!(matches!(res, Err(NvmlError::DriverNotLoaded)));
+        assert_eq!(state.0, None);
+
+        state.2 = true;
+        let res = retry(&mut state, 1, |&n| Ok(n), |s| s.0.as_ref(), recover);
+        assert_eq!(res.unwrap(), 1);
+        assert_eq!(state.1, 2);
+    }
+}
@@ -0,0 +1,405 @@
+/*!
+Recording events and replaying them without a GPU.
+
+`EventData` borrows the `Nvml` instance it came from, so it cannot be stored
+or constructed in tests. An `EventRecorder` turns received events into owned
+`RecordedEvent`s, which can be written to and read back from a simple
+line-based text format. An `EventReplay` then feeds them to the code under
+test, optionally with their original timing.
+
+Write event handling logic against `RecordedEvent` (e.g. by converting each
+`EventData` with `RecordedEvent::from_event()`) so that it can be tested with
+recorded or hand-written events.
+
+```
+use nvml_wrapper::enums::event::{EventKind, XidError};
+use nvml_wrapper::high_level::replay::{read_events, EventReplay};
+
+let recording = "\
+## nvml-wrapper events v1
+0\t0\tGPU-0\t8\t79\t-\t-
+1500\t1\tGPU-1\t1\t-\t-\t-
+";
+
+let events = read_events(recording.as_bytes()).unwrap();
+
+let xids: Vec<_> = EventReplay::new(events)
+    .filter_map(|event| match event.kind() {
+        Some(EventKind::XidCriticalError { xid }) => Some((event.source.index, xid)),
+        _ => None,
+    })
+    .collect();
+
+assert_eq!(xids, vec![(0, XidError::Value(79))]);
+```
+*/
+
+use crate::bitmasks::event::EventTypes;
+use crate::enums::event::{EventKind, XidError};
+use crate::error::NvmlError;
+use crate::struct_wrappers::event::{EventData, EventSource};
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+use std::io::{self, BufRead, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const HEADER: &str = "# nvml-wrapper events v1";
+
+/// An owned copy of the information in an `EventData`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RecordedEvent {
+    /// When the event was received, relative to the start of the recording.
+    pub at: Duration,
+    /// The device the event occurred on.
+    pub source: EventSource,
+    pub event_type: EventTypes,
+    /// The XID error of a critical XID event.
+    pub xid: Option<XidError>,
+}
+
+impl RecordedEvent {
+    /**
+    Copy `event`, received at `at` relative to the start of the recording.
+
+    # Errors
+
+    Any error returned by `EventData.source()`.
+    */
+    pub fn from_event(event: &EventData, at: Duration) -> Result<Self, NvmlError> {
+        Ok(Self {
+            at,
+            source: event.source()?,
+            event_type: event.event_type,
+            xid: event.event_data.clone(),
+        })
+    }
+
+    /// Decode every event type in `event_type`; see `EventData.kinds()`.
+    pub fn kinds(&self) -> Vec<EventKind> {
+        EventKind::decode(self.event_type, self.xid.as_ref())
+    }
+
+    /// Decode the type of this event; see `EventData.kind()`.
+    pub fn kind(&self) -> Option<EventKind> {
+        self.kinds().into_iter().next()
+    }
+}
+
+/// Records received events, timestamped relative to its creation.
+#[derive(Debug, Clone)]
+pub struct EventRecorder {
+    start: Instant,
+    events: Vec<RecordedEvent>,
+}
+
+impl Default for EventRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventRecorder {
+    /// Start a new recording.
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            events: vec![],
+        }
+    }
+
+    /**
+    Record `event`, and return the recorded copy.
+
+    # Errors
+
+    Any error returned by `RecordedEvent::from_event()`.
+    */
+    pub fn record(&mut self, event: &EventData) -> Result<&RecordedEvent, NvmlError> {
+        let recorded = RecordedEvent::from_event(event, self.start.elapsed())?;
+        self.events.push(recorded);
+
+        Ok(&self.events[self.events.len() - 1])
+    }
+
+    /// The events recorded so far.
+    pub fn events(&self) -> &[RecordedEvent] {
+        &self.events
+    }
+
+    /// Stop recording and return the recorded events.
+    pub fn into_events(self) -> Vec<RecordedEvent> {
+        self.events
+    }
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map_or_else(|| "-".into(), |v| v.to_string())
+}
+
+/**
+Write `events` to `writer`, one per line.
+
+Each line holds tab-separated fields: the time in microseconds since the
+start of the recording, the device index, the device UUID, the event type
+bits, the XID (`unknown` for `XidError::Unknown`) and the GPU and compute
+instance IDs, with `-` for absent values.
+
+# Errors
+
+Any error returned by `writer`.
+*/
+pub fn write_events<W: Write>(events: &[RecordedEvent], mut writer: W) -> io::Result<()> {
+    writeln!(writer, "{}", HEADER)?;
+
+    for event in events {
+        let xid = event.xid.as_ref().map(|xid| match *xid {
+            XidError::Value(v) => v.to_string(),
+            XidError::Unknown => "unknown".into(),
+        });
+
+        writeln!(
+            writer,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            event.at.as_micros(),
+            event.source.index,
+            event.source.uuid,
+            event.event_type.bits(),
+            optional(xid),
+            optional(event.source.gpu_instance_id),
+            optional(event.source.compute_instance_id),
+        )?;
+    }
+
+    Ok(())
+}
+
+fn invalid(line: usize, what: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("line {}: {}", line, what),
+    )
+}
+
+/**
+Read events written by `write_events()` from `reader`.
+
+Empty lines are skipped, and event type bits this version of the crate does
+not know about are dropped.
+
+# Errors
+
+* `InvalidData`, if the header is missing or a line is malformed
+* Any error returned by `reader`
+*/
+pub fn read_events<R: BufRead>(reader: R) -> io::Result<Vec<RecordedEvent>> {
+    let mut lines = reader.lines().enumerate();
+
+    let header = lines.next().map(|(_, line)| line).transpose()?;
+
+    if header.as_deref().map(str::trim_end) != Some(HEADER) {
+        return Err(invalid(1, "missing header"));
+    }
+
+    let mut events = vec![];
+
+    for (i, line) in lines {
+        let line = line?;
+        let n = i + 1;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.trim_end().split('\t').collect();
+
+        if fields.len() != 7 {
+            return Err(invalid(n, "expected 7 fields"));
+        }
+
+        let number = |field: &str| -> io::Result<u64> {
+            field.parse().map_err(|_| invalid(n, "invalid number"))
+        };
+        let optional_id = |field: &str| -> io::Result<Option<u32>> {
+            match field {
+                "-" => Ok(None),
+                f => f.parse().map(Some).map_err(|_| invalid(n, "invalid ID")),
+            }
+        };
+
+        events.push(RecordedEvent {
+            at: Duration::from_micros(number(fields[0])?),
+            source: EventSource {
+                index: u32::try_from(number(fields[1])?)
+                    .map_err(|_| invalid(n, "invalid index"))?,
+                uuid: fields[2].into(),
+                gpu_instance_id: optional_id(fields[5])?,
+                compute_instance_id: optional_id(fields[6])?,
+            },
+            event_type: EventTypes::from_bits_truncate(number(fields[3])?),
+            xid: match fields[4] {
+                "-" => None,
+                "unknown" => Some(XidError::Unknown),
+                f => Some(XidError::Value(number(f)?)),
+            },
+        });
+    }
+
+    Ok(events)
+}
+
+/**
+Replays recorded events, in order.
+
+By default events are yielded immediately; use `.timed()` to reproduce the
+original gaps between them.
+*/
+#[derive(Debug, Clone)]
+pub struct EventReplay {
+    events: std::vec::IntoIter<RecordedEvent>,
+    speed: Option<f64>,
+    start: Option<(Instant, Duration)>,
+}
+
+impl EventReplay {
+    /// Replay `events`.
+    pub fn new(events: Vec<RecordedEvent>) -> Self {
+        Self {
+            events: events.into_iter(),
+            speed: None,
+            start: None,
+        }
+    }
+
+    /**
+    Sleep before each event so that events are yielded with their recorded
+    gaps, sped up by `speed` (e.g. `2.0` for twice as fast).
+
+    # Errors
+
+    * `InvalidArg`, if `speed` is not a positive, finite number
+    */
+    pub fn timed(mut self, speed: f64) -> Result<Self, NvmlError> {
+        if !speed.is_finite() || speed <= 0.0 {
+            return Err(NvmlError::InvalidArg);
+        }
+
+        self.speed = Some(speed);
+        Ok(self)
+    }
+}
+
+impl Iterator for EventReplay {
+    type Item = RecordedEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let event = self.events.next()?;
+
+        if let Some(speed) = self.speed {
+            let (started, first) = *self.start.get_or_insert((Instant::now(), event.at));
+            let gap = event.at.saturating_sub(first).as_secs_f64() / speed;
+
+            // Gaps too long to represent are not waited for
+            let due = Duration::try_from_secs_f64(gap)
+                .ok()
+                .and_then(|gap| started.checked_add(gap));
+            let now = Instant::now();
+
+            if let Some(due) = due.filter(|due| *due > now) {
+                thread::sleep(due - now);
+            }
+        }
+
+        Some(event)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn events() -> Vec<RecordedEvent> {
+        vec![
+            RecordedEvent {
+                at: Duration::from_micros(0),
+                source: EventSource {
+                    index: 0,
+                    uuid: "GPU-0".into(),
+                    gpu_instance_id: Some(1),
+                    compute_instance_id: Some(0),
+                },
+                event_type: EventTypes::CRITICAL_XID_ERROR,
+                xid: Some(XidError::Value(48)),
+            },
+            RecordedEvent {
+                at: Duration::from_millis(20),
+                source: EventSource {
+                    index: 1,
+                    uuid: "GPU-1".into(),
+                    gpu_instance_id: None,
+                    compute_instance_id: None,
+                },
+                event_type: EventTypes::CRITICAL_XID_ERROR,
+                xid: Some(XidError::Unknown),
+            },
+            RecordedEvent {
+                at: Duration::from_millis(40),
+                source: EventSource {
+                    index: 1,
+                    uuid: "GPU-1".into(),
+                    gpu_instance_id: None,
+                    compute_instance_id: None,
+                },
+                event_type: EventTypes::CLOCK_CHANGE | EventTypes::PSTATE_CHANGE,
+                xid: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut buf = vec![];
+        write_events(&events(), &mut buf).unwrap();
+
+        assert_eq!(read_events(&buf[..]).unwrap(), events());
+    }
+
+    #[test]
+    fn malformed_input() {
+        assert!(read_events(&b""[..]).is_err());
+        assert!(read_events(&b"0\t0\tGPU-0\t8\t-\t-\t-\n"[..]).is_err());
+
+        let err = read_events(&b"# nvml-wrapper events v1\n0\t0\tGPU-0\t8\t-\n"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().starts_with("line 2"));
+    }
+
+    #[test]
+    fn replay() {
+        let kinds: Vec<_> = EventReplay::new(events()).map(|e| e.kind()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Some(EventKind::XidCriticalError {
+                    xid: XidError::Value(48)
+                }),
+                Some(EventKind::XidCriticalError {
+                    xid: XidError::Unknown
+                }),
+                Some(EventKind::PStateChange),
+            ]
+        );
+
+        let start = Instant::now();
+        assert_eq!(EventReplay::new(events()).timed(2.0).unwrap().count(), 3);
+        assert!(start.elapsed() >= Duration::from_millis(20));
+
+        for speed in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                EventReplay::new(events()).timed(speed),
+                Err(NvmlError::InvalidArg)
+            ));
+        }
+    }
+}
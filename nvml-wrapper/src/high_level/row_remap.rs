@@ -0,0 +1,164 @@
+/*!
+Notifications for pending and failed row remapping.
+
+On Ampere and newer, memory rows with uncorrectable (or too many correctable)
+errors are remapped to spare rows. Remappings only take effect after a GPU
+reset, and a failed remapping means the GPU should be taken out of service, so
+operators want to hear about both as soon as they happen to schedule a drain.
+
+`RowRemappingWatcher` turns `Device::remapped_rows()` readings into a
+`RowRemappingAlert` whenever a remapping becomes pending or a failure is first
+seen. It can be polled, or driven by the double bit ECC and row remapping XID
+events that accompany remappings through `EventCallbacks::on_row_remapping()`:
+
+```no_run
+use nvml_wrapper::high_level::event_thread::{BackgroundEventLoop, EventCallbacks};
+use nvml_wrapper::Nvml;
+use std::sync::mpsc;
+
+# fn main() -> Result<(), nvml_wrapper::error::NvmlErrorWithSource> {
+let nvml: &'static Nvml = Box::leak(Box::new(Nvml::init()?));
+let devices = vec![nvml.device_by_index(0)?];
+let (tx, rx) = mpsc::channel();
+
+let callbacks = EventCallbacks::new().on_row_remapping(move |device, alert| {
+    let _ = tx.send((device.index(), alert));
+});
+
+let _event_loop = BackgroundEventLoop::for_devices(nvml, &devices, callbacks)?;
+
+for (index, alert) in rx {
+    println!("{:?}: {:?}, drain the node", index, alert);
+}
+# Ok(())
+# }
+```
+*/
+
+use crate::bitmasks::event::EventTypes;
+use crate::enums::event::XidError;
+use crate::error::NvmlError;
+use crate::struct_wrappers::event::EventData;
+use crate::structs::device::RemappedRows;
+use crate::Device;
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+
+/// The event types that accompany row remapping.
+pub const ROW_REMAPPING_EVENTS: EventTypes =
+    EventTypes::DOUBLE_BIT_ECC_ERROR.union(EventTypes::CRITICAL_XID_ERROR);
+
+/// XID 63 (row remapping event) and XID 64 (row remapping failure).
+const ROW_REMAPPING_XIDS: [u64; 2] = [63, 64];
+
+/// A change in row remapping worth acting on.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RowRemappingAlert {
+    /// Remappings are pending; reset the GPU for them to take effect.
+    Pending(RemappedRows),
+    /// A remapping failed; the GPU should be taken out of service.
+    Failed(RemappedRows),
+}
+
+/// Reports when row remappings become pending or fail.
+#[derive(Debug, Clone, Default)]
+pub struct RowRemappingWatcher {
+    previous: Option<RemappedRows>,
+}
+
+impl RowRemappingWatcher {
+    /// Create a watcher that hasn't seen any readings yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+    Feed a reading through the watcher.
+
+    Returns an alert when a failure is seen for the first time, or when
+    remappings become pending, including in the first reading. A failure
+    takes precedence over pending remappings seen at the same time.
+    */
+    pub fn update(&mut self, rows: RemappedRows) -> Option<RowRemappingAlert> {
+        let (was_pending, had_failed) = self
+            .previous
+            .as_ref()
+            .map_or((false, false), |p| (p.pending, p.failure_occurred));
+
+        let alert = if rows.failure_occurred && !had_failed {
+            Some(RowRemappingAlert::Failed(rows.clone()))
+        } else if rows.pending && !was_pending {
+            Some(RowRemappingAlert::Pending(rows.clone()))
+        } else {
+            None
+        };
+
+        self.previous = Some(rows);
+
+        alert
+    }
+
+    /**
+    Read the remapped rows of `device` now and feed them through the watcher.
+
+    # Errors
+
+    Any error returned by `Device::remapped_rows()`.
+    */
+    pub fn poll(&mut self, device: &Device) -> Result<Option<RowRemappingAlert>, NvmlError> {
+        Ok(self.update(device.remapped_rows()?))
+    }
+
+    /// Whether `event` may come with a change in row remapping: a double bit
+    /// ECC error or a row remapping XID.
+    pub fn is_relevant(event: &EventData) -> bool {
+        event.event_type.contains(EventTypes::DOUBLE_BIT_ECC_ERROR)
+            || matches!(event.event_data, Some(XidError::Value(xid)) if ROW_REMAPPING_XIDS.contains(&xid))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::*;
+
+    fn rows(pending: bool, failure_occurred: bool) -> RemappedRows {
+        RemappedRows {
+            correctable: 0,
+            uncorrectable: 1,
+            pending,
+            failure_occurred,
+        }
+    }
+
+    #[test]
+    fn alerts_on_changes() {
+        let mut watcher = RowRemappingWatcher::new();
+
+        assert_eq!(watcher.update(rows(false, false)), None);
+        assert_eq!(
+            watcher.update(rows(true, false)),
+            Some(RowRemappingAlert::Pending(rows(true, false)))
+        );
+        // Still pending
+        assert_eq!(watcher.update(rows(true, false)), None);
+        assert_eq!(
+            watcher.update(rows(true, true)),
+            Some(RowRemappingAlert::Failed(rows(true, true)))
+        );
+        assert_eq!(watcher.update(rows(false, true)), None);
+
+        // Already pending or failed when first seen
+        assert_eq!(
+            RowRemappingWatcher::new().update(rows(true, true)),
+            Some(RowRemappingAlert::Failed(rows(true, true)))
+        );
+    }
+
+    #[test]
+    fn poll() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| RowRemappingWatcher::new().poll(device))
+    }
+}
@@ -0,0 +1,253 @@
+/*!
+Sparse field value subscriptions.
+
+A `FieldSubscription` polls a set of `FieldId`s via `Device::field_values_for()`
+and only reports a field when its value has moved by more than a configured
+delta since the last time it was reported. This keeps downstream processing
+low for metrics that are mostly static, such as clocks on an idle GPU.
+
+```no_run
+use nvml_wrapper::Nvml;
+use nvml_wrapper::high_level::subscription::FieldSubscription;
+use nvml_wrapper::structs::device::FieldId;
+use nvml_wrapper::sys_exports::field_id::*;
+# use std::{thread, time::Duration};
+
+# fn main() -> Result<(), nvml_wrapper::error::NvmlError> {
+let nvml = Nvml::init()?;
+let device = nvml.device_by_index(0)?;
+
+let mut subscription = FieldSubscription::new();
+subscription
+    .subscribe(FieldId(NVML_FI_DEV_POWER_INSTANT), 5_000.0)
+    .subscribe(FieldId(NVML_FI_DEV_TOTAL_ENERGY_CONSUMPTION), 0.0);
+
+loop {
+    for update in subscription.poll(&device)? {
+        let update = update?;
+        println!("{:?} changed to {:?}", update.field, update.value);
+    }
+#   break;
+    thread::sleep(Duration::from_secs(1));
+}
+# Ok(())
+# }
+```
+*/
+
+use crate::enums::device::SampleValue;
+use crate::error::NvmlError;
+use crate::struct_wrappers::device::FieldValueSample;
+use crate::structs::device::FieldId;
+use crate::Device;
+use std::collections::HashMap;
+
+/// A field whose value changed by more than its subscribed delta.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldUpdate {
+    /// The field that changed.
+    pub field: FieldId,
+    /// The sample's CPU timestamp in μs (Unix time).
+    pub timestamp: i64,
+    /// The previously reported value, or `None` if this is the first report.
+    pub previous: Option<SampleValue>,
+    /// The new value.
+    pub value: SampleValue,
+}
+
+#[derive(Debug, Clone)]
+struct Subscribed {
+    min_delta: f64,
+    last_reported: Option<SampleValue>,
+}
+
+/// Tracks a set of field IDs and reports only meaningful changes.
+///
+/// A subscription holds the last reported value of each field, so use one
+/// subscription per `Device`.
+#[derive(Debug, Clone, Default)]
+pub struct FieldSubscription {
+    ids: Vec<FieldId>,
+    fields: HashMap<u32, Subscribed>,
+}
+
+impl FieldSubscription {
+    /// Create an empty subscription.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+    Subscribe to `field`, reporting it whenever its value differs from the last
+    reported value by more than `min_delta`.
+
+    A `min_delta` of `0.0` reports every change. Subscribing to a field that is
+    already subscribed updates its delta.
+    */
+    pub fn subscribe(&mut self, field: FieldId, min_delta: f64) -> &mut Self {
+        match self.fields.get_mut(&field.0) {
+            Some(existing) => existing.min_delta = min_delta,
+            None => {
+                self.fields.insert(
+                    field.0,
+                    Subscribed {
+                        min_delta,
+                        last_reported: None,
+                    },
+                );
+                self.ids.push(field);
+            }
+        }
+
+        self
+    }
+
+    /// Stop reporting `field`.
+    pub fn unsubscribe(&mut self, field: &FieldId) -> &mut Self {
+        if self.fields.remove(&field.0).is_some() {
+            self.ids.retain(|id| id != field);
+        }
+
+        self
+    }
+
+    /// The field IDs that are currently subscribed to, in subscription order.
+    pub fn fields(&self) -> &[FieldId] {
+        &self.ids
+    }
+
+    /// Forget every last reported value, so that the next poll reports all fields.
+    pub fn reset(&mut self) {
+        for subscribed in self.fields.values_mut() {
+            subscribed.last_reported = None;
+        }
+    }
+
+    /**
+    Feed samples obtained elsewhere (e.g. from `Device::field_values_for()`)
+    through the subscription.
+
+    Samples for fields that are not subscribed to are ignored. Samples whose
+    value could not be retrieved are passed through as errors.
+    */
+    pub fn update<I>(&mut self, samples: I) -> Vec<Result<FieldUpdate, NvmlError>>
+    where
+        I: IntoIterator<Item = Result<FieldValueSample, NvmlError>>,
+    {
+        let mut updates = vec![];
+
+        for sample in samples {
+            let sample = match sample {
+                Ok(s) => s,
+                Err(e) => {
+                    updates.push(Err(e));
+                    continue;
+                }
+            };
+
+            let subscribed = match self.fields.get_mut(&sample.field.0) {
+                Some(s) => s,
+                None => continue,
+            };
+
+            let value = match sample.value {
+                Ok(v) => v,
+                Err(e) => {
+                    updates.push(Err(e));
+                    continue;
+                }
+            };
+
+            let changed = match subscribed.last_reported {
+                Some(ref last) => (value.as_f64() - last.as_f64()).abs() > subscribed.min_delta,
+                None => true,
+            };
+
+            if changed {
+                let previous = subscribed.last_reported.replace(value.clone());
+
+                updates.push(Ok(FieldUpdate {
+                    field: sample.field,
+                    timestamp: sample.timestamp,
+                    previous,
+                    value,
+                }));
+            }
+        }
+
+        updates
+    }
+
+    /**
+    Read every subscribed field from `device` and return the ones that changed.
+
+    Returns an empty `Vec` without calling into NVML if nothing is subscribed.
+
+    # Errors
+
+    ## Outer `Result`
+
+    Any error returned by `Device::field_values_for()`.
+
+    ## Inner `Result`
+
+    Errors for individual fields, as returned by `Device::field_values_for()`.
+    */
+    pub fn poll(
+        &mut self,
+        device: &Device,
+    ) -> Result<Vec<Result<FieldUpdate, NvmlError>>, NvmlError> {
+        if self.ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let samples = device.field_values_for(&self.ids)?;
+
+        Ok(self.update(samples))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sample(field: u32, value: u32) -> Result<FieldValueSample, NvmlError> {
+        Ok(FieldValueSample {
+            field: FieldId(field),
+            timestamp: 0,
+            latency: 0,
+            value: Ok(SampleValue::U32(value)),
+        })
+    }
+
+    #[test]
+    fn reports_only_changes_beyond_delta() {
+        let mut sub = FieldSubscription::new();
+        sub.subscribe(FieldId(1), 10.0).subscribe(FieldId(2), 0.0);
+
+        let updates = sub.update(vec![sample(1, 100), sample(2, 5), sample(3, 7)]);
+        assert_eq!(updates.len(), 2);
+
+        let updates = sub.update(vec![sample(1, 105), sample(2, 5)]);
+        assert!(updates.is_empty());
+
+        let updates = sub.update(vec![sample(1, 111), sample(2, 6)]);
+        let updates: Vec<_> = updates.into_iter().map(Result::unwrap).collect();
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[0].previous, Some(SampleValue::U32(100)));
+        assert_eq!(updates[0].value, SampleValue::U32(111));
+    }
+
+    #[test]
+    fn unsubscribe_and_reset() {
+        let mut sub = FieldSubscription::new();
+        sub.subscribe(FieldId(1), 0.0).subscribe(FieldId(2), 0.0);
+        sub.unsubscribe(&FieldId(2));
+
+        assert_eq!(sub.fields(), &[FieldId(1)]);
+        assert_eq!(sub.update(vec![sample(1, 1), sample(2, 1)]).len(), 1);
+
+        sub.reset();
+        assert_eq!(sub.update(vec![sample(1, 1)]).len(), 1);
+    }
+}
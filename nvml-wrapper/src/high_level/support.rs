@@ -0,0 +1,219 @@
+/*!
+Support matrices: which wrapped calls work on a device.
+
+`Device::support_matrix()` probes a fixed list of read-only calls and records,
+for each, whether it succeeded, is not supported, or needs more permissions.
+The result can be attached to bug reports or kept around as a capability
+cache so that unsupported calls are not attempted again.
+*/
+
+use crate::enum_wrappers::device::{
+    Clock, EccCounter, MemoryError, PcieUtilCounter, TemperatureSensor,
+};
+use crate::error::NvmlError;
+use crate::Device;
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The outcome of probing one call.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Support {
+    Supported,
+    NotSupported,
+    /// The call needs more permissions than the process has.
+    PermissionDenied,
+    /// The call failed for another reason, described by the error message.
+    Failed(String),
+}
+
+impl Support {
+    /// Classify the result of a call.
+    pub fn of<T>(res: &Result<T, NvmlError>) -> Self {
+        match res {
+            Ok(_) => Support::Supported,
+            Err(NvmlError::NotSupported) => Support::NotSupported,
+            Err(NvmlError::NoPermission) => Support::PermissionDenied,
+            Err(e) => Support::Failed(e.to_string()),
+        }
+    }
+}
+
+type Probe = fn(&Device) -> Support;
+
+/// The calls probed by `SupportMatrix::probe()`, by `Device` method name.
+static PROBES: &[(&str, Probe)] = &[
+    ("architecture", |d| Support::of(&d.architecture())),
+    ("vbios_version", |d| Support::of(&d.vbios_version())),
+    ("serial", |d| Support::of(&d.serial())),
+    ("board_part_number", |d| Support::of(&d.board_part_number())),
+    ("cuda_compute_capability", |d| {
+        Support::of(&d.cuda_compute_capability())
+    }),
+    ("memory_info", |d| Support::of(&d.memory_info())),
+    ("bar1_memory_info", |d| Support::of(&d.bar1_memory_info())),
+    ("utilization_rates", |d| Support::of(&d.utilization_rates())),
+    ("encoder_utilization", |d| {
+        Support::of(&d.encoder_utilization())
+    }),
+    ("decoder_utilization", |d| {
+        Support::of(&d.decoder_utilization())
+    }),
+    ("encoder_stats", |d| Support::of(&d.encoder_stats())),
+    ("fbc_stats", |d| Support::of(&d.fbc_stats())),
+    ("temperature", |d| {
+        Support::of(&d.temperature(TemperatureSensor::Gpu))
+    }),
+    ("num_fans", |d| Support::of(&d.num_fans())),
+    ("fan_speed", |d| Support::of(&d.fan_speed(0))),
+    ("power_usage", |d| Support::of(&d.power_usage())),
+    ("total_energy_consumption", |d| {
+        Support::of(&d.total_energy_consumption())
+    }),
+    ("power_management_limit", |d| {
+        Support::of(&d.power_management_limit())
+    }),
+    ("enforced_power_limit", |d| {
+        Support::of(&d.enforced_power_limit())
+    }),
+    ("performance_state", |d| Support::of(&d.performance_state())),
+    ("current_clocks_event_reasons", |d| {
+        Support::of(&d.current_clocks_event_reasons())
+    }),
+    ("clock_info", |d| {
+        Support::of(&d.clock_info(Clock::Graphics))
+    }),
+    ("max_clock_info", |d| {
+        Support::of(&d.max_clock_info(Clock::Graphics))
+    }),
+    ("applications_clock", |d| {
+        Support::of(&d.applications_clock(Clock::Graphics))
+    }),
+    ("supported_memory_clocks", |d| {
+        Support::of(&d.supported_memory_clocks())
+    }),
+    ("current_pcie_link_gen", |d| {
+        Support::of(&d.current_pcie_link_gen())
+    }),
+    ("current_pcie_link_width", |d| {
+        Support::of(&d.current_pcie_link_width())
+    }),
+    ("pcie_throughput", |d| {
+        Support::of(&d.pcie_throughput(PcieUtilCounter::Send))
+    }),
+    ("pcie_replay_counter", |d| {
+        Support::of(&d.pcie_replay_counter())
+    }),
+    ("is_ecc_enabled", |d| Support::of(&d.is_ecc_enabled())),
+    ("total_ecc_errors", |d| {
+        Support::of(&d.total_ecc_errors(MemoryError::Corrected, EccCounter::Volatile))
+    }),
+    ("compute_mode", |d| Support::of(&d.compute_mode())),
+    // Only available on Linux
+    #[cfg(target_os = "linux")]
+    ("is_in_persistent_mode", |d| {
+        Support::of(&d.is_in_persistent_mode())
+    }),
+    ("is_accounting_enabled", |d| {
+        Support::of(&d.is_accounting_enabled())
+    }),
+    ("mig_mode", |d| Support::of(&d.mig_mode())),
+    ("running_compute_processes", |d| {
+        Support::of(&d.running_compute_processes())
+    }),
+    ("nvlink", |d| {
+        Support::of(&d.link_wrapper_for(0).is_active())
+    }),
+    // Only available on Linux
+    #[cfg(target_os = "linux")]
+    ("virtualization_mode", |d| {
+        Support::of(&d.virtualization_mode())
+    }),
+    ("supported_vgpus", |d| Support::of(&d.supported_vgpus())),
+    ("vgpu_scheduler_capabilities", |d| {
+        Support::of(&d.vgpu_scheduler_capabilities())
+    }),
+];
+
+/// Whether each of a fixed list of calls works on a device.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SupportMatrix {
+    /// Probe outcomes by `Device` method name.
+    pub calls: BTreeMap<String, Support>,
+}
+
+impl SupportMatrix {
+    /// Probe every call on `device`.
+    pub fn probe(device: &Device) -> Self {
+        Self {
+            calls: PROBES
+                .iter()
+                .map(|(name, probe)| (name.to_string(), probe(device)))
+                .collect(),
+        }
+    }
+
+    /// The names of the calls that get probed.
+    pub fn probed_calls() -> impl Iterator<Item = &'static str> {
+        PROBES.iter().map(|(name, _)| *name)
+    }
+
+    /// The outcome of probing the `Device` method `name`, if it was probed.
+    pub fn get(&self, name: &str) -> Option<&Support> {
+        self.calls.get(name)
+    }
+
+    /**
+    Whether the `Device` method `name` is supported.
+
+    Returns `None` if the method was not probed or failed for a reason other
+    than a lack of support, in which case the answer is unknown.
+    */
+    pub fn is_supported(&self, name: &str) -> Option<bool> {
+        match self.get(name)? {
+            Support::Supported | Support::PermissionDenied => Some(true),
+            Support::NotSupported => Some(false),
+            Support::Failed(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn probe_names_are_unique() {
+        let mut names: Vec<_> = SupportMatrix::probed_calls().collect();
+        let count = names.len();
+
+        names.sort_unstable();
+        names.dedup();
+
+        assert_eq!(names.len(), count);
+    }
+
+    #[test]
+    fn support_of_results() {
+        let mut matrix = SupportMatrix::default();
+        let results: [(&str, Result<(), NvmlError>); 4] = [
+            ("a", Ok(())),
+            ("b", Err(NvmlError::NotSupported)),
+            ("c", Err(NvmlError::NoPermission)),
+            ("d", Err(NvmlError::GpuLost)),
+        ];
+
+        for (name, res) in &results {
+            matrix.calls.insert(name.to_string(), Support::of(res));
+        }
+
+        assert_eq!(matrix.is_supported("a"), Some(true));
+        assert_eq!(matrix.is_supported("b"), Some(false));
+        assert_eq!(matrix.get("c"), Some(&Support::PermissionDenied));
+        assert_eq!(matrix.is_supported("c"), Some(true));
+        assert_eq!(matrix.is_supported("d"), None);
+        assert_eq!(matrix.is_supported("e"), None);
+    }
+}
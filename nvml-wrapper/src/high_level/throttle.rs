@@ -0,0 +1,345 @@
+/*!
+Throttling analysis over a sliding window.
+
+NVML reports throttling in two ways: per-policy violation counters, which
+accumulate the time clocks were held back by e.g. power or thermal limits,
+and clocks event reasons, which say what is holding clocks back right now.
+`ThrottleAnalyzer` samples both periodically and reports, for a sliding
+window, the share of time clocks were throttled for each policy and reason.
+
+```no_run
+use nvml_wrapper::Nvml;
+use nvml_wrapper::high_level::throttle::ThrottleAnalyzer;
+# use std::{thread, time::Duration};
+
+# fn main() -> Result<(), nvml_wrapper::error::NvmlError> {
+let nvml = Nvml::init()?;
+let device = nvml.device_by_index(0)?;
+
+let mut analyzer = ThrottleAnalyzer::new(Duration::from_secs(60));
+
+loop {
+    if let Some(report) = analyzer.poll(&device)? {
+        for (policy, percent) in &report.policies {
+            println!("{:?}: {:.1}%", policy, percent);
+        }
+    }
+#   break;
+    thread::sleep(Duration::from_secs(1));
+}
+# Ok(())
+# }
+```
+*/
+
+use crate::bitmasks::device::ClocksEventReasons;
+use crate::enum_wrappers::device::PerformancePolicy;
+use crate::error::NvmlError;
+use crate::struct_wrappers::device::ViolationTime;
+use crate::Device;
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Every performance policy violation counters can be read for.
+pub const PERFORMANCE_POLICIES: [PerformancePolicy; 8] = [
+    PerformancePolicy::Power,
+    PerformancePolicy::Thermal,
+    PerformancePolicy::SyncBoost,
+    PerformancePolicy::BoardLimit,
+    PerformancePolicy::LowUtilization,
+    PerformancePolicy::Reliability,
+    PerformancePolicy::TotalAppClocks,
+    PerformancePolicy::TotalBaseClocks,
+];
+
+/// The raw throttling readings taken at one point in time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ThrottleReadings {
+    /// Violation counters of every supported performance policy.
+    pub violations: Vec<(PerformancePolicy, ViolationTime)>,
+    /// The current clocks event reasons, empty if not supported.
+    pub reasons: ClocksEventReasons,
+}
+
+impl ThrottleReadings {
+    /**
+    Read the violation counters of every performance policy and the current
+    clocks event reasons from `device`.
+
+    Policies and reasons the device doesn't support are left out.
+
+    # Errors
+
+    Any error other than `NotSupported` returned by
+    `Device::violation_status()` or `Device::current_clocks_event_reasons()`.
+    */
+    pub fn read(device: &Device) -> Result<Self, NvmlError> {
+        let mut violations = Vec::with_capacity(PERFORMANCE_POLICIES.len());
+
+        for policy in PERFORMANCE_POLICIES {
+            match device.violation_status(policy) {
+                Ok(time) => violations.push((policy, time)),
+                Err(NvmlError::NotSupported) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        let reasons = match device.current_clocks_event_reasons() {
+            Ok(reasons) => reasons,
+            Err(NvmlError::NotSupported) => ClocksEventReasons::empty(),
+            Err(e) => return Err(e),
+        };
+
+        Ok(Self {
+            violations,
+            reasons,
+        })
+    }
+}
+
+/// Throttled time over a window, as percentages.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ThrottleReport {
+    /// The time covered by the report, which can be shorter than the window
+    /// until enough samples have been taken.
+    pub interval: Duration,
+    /// The percentage of time each supported policy was violated, according
+    /// to its violation counter.
+    pub policies: Vec<(PerformancePolicy, f64)>,
+    /// The percentage of time each clocks event reason was active, estimated
+    /// from the samples. Reasons that were never seen are left out.
+    pub reasons: Vec<(ClocksEventReasons, f64)>,
+}
+
+impl ThrottleReport {
+    /// The percentage of time `policy` was violated, if it is supported.
+    pub fn policy(&self, policy: PerformancePolicy) -> Option<f64> {
+        self.policies
+            .iter()
+            .find(|(p, _)| *p == policy)
+            .map(|&(_, percent)| percent)
+    }
+
+    /// The percentage of time `reason` was active.
+    pub fn reason(&self, reason: ClocksEventReasons) -> f64 {
+        self.reasons
+            .iter()
+            .find(|(r, _)| *r == reason)
+            .map_or(0.0, |&(_, percent)| percent)
+    }
+}
+
+/// Reports per-policy and per-reason throttled time of a `Device` over a
+/// sliding window.
+#[derive(Debug, Clone)]
+pub struct ThrottleAnalyzer {
+    window: Duration,
+    samples: VecDeque<(Instant, ThrottleReadings)>,
+}
+
+impl ThrottleAnalyzer {
+    /// Create an analyzer reporting over the last `window` of samples.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// The window reports cover.
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Forget every sample taken so far.
+    pub fn reset(&mut self) {
+        self.samples.clear();
+    }
+
+    /**
+    Feed readings taken at `at` through the analyzer and report over the
+    window ending at `at`.
+
+    Returns `None` for the first readings, which only set the baseline.
+    Clocks event reasons are assumed to have held since the previous sample,
+    so they are only as accurate as the sampling is frequent. Violation
+    counters that went backwards (e.g. after a driver reload) count as no
+    throttling.
+    */
+    pub fn update(&mut self, at: Instant, readings: ThrottleReadings) -> Option<ThrottleReport> {
+        self.samples.push_back((at, readings));
+
+        // Keep the newest sample at or before the start of the window as the
+        // baseline
+        let start = at.checked_sub(self.window);
+
+        while let (Some(start), Some((second, _))) = (start, self.samples.get(1)) {
+            if *second > start {
+                break;
+            }
+
+            self.samples.pop_front();
+        }
+
+        if self.samples.len() < 2 {
+            return None;
+        }
+
+        let ((first_at, first), (last_at, last)) = (self.samples.front()?, self.samples.back()?);
+
+        let policies = last
+            .violations
+            .iter()
+            .filter_map(|(policy, cur)| {
+                let (_, prev) = first.violations.iter().find(|(p, _)| p == policy)?;
+                let elapsed_ns =
+                    cur.reference_time.saturating_sub(prev.reference_time) as f64 * 1000.0;
+                let violated_ns = cur.violation_time.saturating_sub(prev.violation_time) as f64;
+
+                let percent = match elapsed_ns {
+                    ns if ns > 0.0 => (violated_ns / ns * 100.0).min(100.0),
+                    _ => 0.0,
+                };
+
+                Some((*policy, percent))
+            })
+            .collect();
+
+        let interval = last_at.saturating_duration_since(*first_at);
+        let mut reasons = vec![];
+
+        if !interval.is_zero() {
+            for reason in ClocksEventReasons::all().iter() {
+                let active: Duration = self
+                    .samples
+                    .iter()
+                    .zip(self.samples.iter().skip(1))
+                    .filter(|(_, (_, cur))| cur.reasons.contains(reason))
+                    .map(|((prev_at, _), (cur_at, _))| cur_at.saturating_duration_since(*prev_at))
+                    .sum();
+
+                if !active.is_zero() {
+                    reasons.push((
+                        reason,
+                        active.as_secs_f64() / interval.as_secs_f64() * 100.0,
+                    ));
+                }
+            }
+        }
+
+        Some(ThrottleReport {
+            interval,
+            policies,
+            reasons,
+        })
+    }
+
+    /**
+    Read the throttling state of `device` now and feed it through the
+    analyzer.
+
+    # Errors
+
+    Any error returned by `ThrottleReadings::read()`.
+    */
+    pub fn poll(&mut self, device: &Device) -> Result<Option<ThrottleReport>, NvmlError> {
+        let readings = ThrottleReadings::read(device)?;
+
+        Ok(self.update(Instant::now(), readings))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::*;
+
+    fn readings(power_ns: u64, at_us: u64, reasons: ClocksEventReasons) -> ThrottleReadings {
+        ThrottleReadings {
+            violations: vec![(
+                PerformancePolicy::Power,
+                ViolationTime {
+                    reference_time: at_us,
+                    violation_time: power_ns,
+                },
+            )],
+            reasons,
+        }
+    }
+
+    #[test]
+    fn report_over_window() {
+        let start = Instant::now();
+        let secs = |s: u64| start + Duration::from_secs(s);
+        let mut analyzer = ThrottleAnalyzer::new(Duration::from_secs(4));
+
+        assert_eq!(
+            analyzer.update(secs(0), readings(0, 0, ClocksEventReasons::empty())),
+            None
+        );
+
+        // Power capped for half of the first two seconds
+        let report = analyzer
+            .update(
+                secs(2),
+                readings(1_000_000_000, 2_000_000, ClocksEventReasons::SW_POWER_CAP),
+            )
+            .unwrap();
+
+        assert_eq!(report.interval, Duration::from_secs(2));
+        assert_eq!(report.policy(PerformancePolicy::Power), Some(50.0));
+        assert_eq!(report.policy(PerformancePolicy::Thermal), None);
+        assert_eq!(report.reason(ClocksEventReasons::SW_POWER_CAP), 100.0);
+        assert_eq!(report.reason(ClocksEventReasons::GPU_IDLE), 0.0);
+
+        analyzer.update(
+            secs(4),
+            readings(1_000_000_000, 4_000_000, ClocksEventReasons::GPU_IDLE),
+        );
+
+        // The first sample falls out of the window
+        let report = analyzer
+            .update(
+                secs(6),
+                readings(1_000_000_000, 6_000_000, ClocksEventReasons::GPU_IDLE),
+            )
+            .unwrap();
+
+        assert_eq!(report.interval, Duration::from_secs(4));
+        assert_eq!(report.policy(PerformancePolicy::Power), Some(0.0));
+        assert_eq!(report.reason(ClocksEventReasons::GPU_IDLE), 100.0);
+        assert_eq!(report.reason(ClocksEventReasons::SW_POWER_CAP), 0.0);
+    }
+
+    #[test]
+    fn counters_going_backwards() {
+        let start = Instant::now();
+        let mut analyzer = ThrottleAnalyzer::new(Duration::from_secs(60));
+
+        analyzer.update(start, readings(5_000, 1_000, ClocksEventReasons::empty()));
+
+        let report = analyzer
+            .update(
+                start + Duration::from_secs(1),
+                readings(0, 1_000_001, ClocksEventReasons::empty()),
+            )
+            .unwrap();
+
+        assert_eq!(report.policy(PerformancePolicy::Power), Some(0.0));
+        assert!(report.reasons.is_empty());
+    }
+
+    #[test]
+    fn poll() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let mut analyzer = ThrottleAnalyzer::new(Duration::from_secs(1));
+            analyzer.poll(device)?;
+            analyzer.poll(device)
+        })
+    }
+}
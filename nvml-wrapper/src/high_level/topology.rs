@@ -0,0 +1,285 @@
+/*!
+The GPU topology matrix, as printed by `nvidia-smi topo -m`.
+
+`TopologyMatrix::read()` records, for every pair of GPUs in the system, the
+number of NvLink links directly connecting them and their common ancestor in
+the PCIe topology, along with the CPUs each GPU has affinity to. The matrix
+can be serialized and rendered as a table via its `Display` implementation.
+
+```no_run
+use nvml_wrapper::Nvml;
+
+# fn main() -> Result<(), nvml_wrapper::error::NvmlError> {
+let nvml = Nvml::init()?;
+let matrix = nvml.topology_matrix()?;
+
+print!("{}", matrix);
+# Ok(())
+# }
+```
+*/
+
+use crate::enum_wrappers::device::TopologyLevel;
+use crate::error::NvmlError;
+use crate::high_level::{active_nvlinks, supported};
+use crate::{Device, Nvml};
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+use std::fmt;
+use std::os::raw::c_ulong;
+
+/// The number of CPUs `Device::cpu_affinity()` is asked about.
+const MAX_CPUS: usize = 1024;
+
+const BITS_PER_WORD: usize = std::mem::size_of::<c_ulong>() * 8;
+
+/// How two GPUs in a `TopologyMatrix` are connected.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TopologyEntry {
+    /// The common ancestor of the two GPUs, or `None` for a GPU paired with
+    /// itself or if the topology could not be determined.
+    pub level: Option<TopologyLevel>,
+    /// The number of active NvLink links directly connecting the two GPUs.
+    pub nvlinks: u32,
+}
+
+impl TopologyEntry {
+    /**
+    The label `nvidia-smi topo -m` uses for this connection.
+
+    NvLink connections take precedence over the PCIe topology (`NV4` for
+    four links), and a GPU paired with itself is `X`. GPUs on the same board
+    are labelled `PIX`.
+    */
+    pub fn label(&self) -> String {
+        if self.nvlinks > 0 {
+            return format!("NV{}", self.nvlinks);
+        }
+
+        match self.level {
+            None => "X",
+            Some(TopologyLevel::Internal) | Some(TopologyLevel::Single) => "PIX",
+            Some(TopologyLevel::Multiple) => "PXB",
+            Some(TopologyLevel::HostBridge) => "PHB",
+            Some(TopologyLevel::Node) => "NODE",
+            Some(TopologyLevel::System) => "SYS",
+        }
+        .into()
+    }
+}
+
+/// A GPU in a `TopologyMatrix`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TopologyGpu {
+    /// The NVML index of the GPU.
+    pub index: u32,
+    /// The PCI bus ID of the GPU.
+    pub bus_id: String,
+    /// The CPUs ideal for the GPU, in ascending order, or `None` if not
+    /// supported.
+    pub cpu_affinity: Option<Vec<u32>>,
+}
+
+/// How every pair of GPUs in the system is connected.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TopologyMatrix {
+    pub gpus: Vec<TopologyGpu>,
+    /// `entries[i][j]` describes the connection between `gpus[i]` and
+    /// `gpus[j]`.
+    pub entries: Vec<Vec<TopologyEntry>>,
+}
+
+/// Turn a CPU bitmask as returned by `Device::cpu_affinity()` into CPU
+/// numbers.
+fn cpus_from_mask(mask: &[c_ulong]) -> Vec<u32> {
+    mask.iter()
+        .enumerate()
+        .flat_map(|(word, &bits)| {
+            (0..BITS_PER_WORD)
+                .filter(move |bit| bits & (1 << bit) != 0)
+                .map(move |bit| (word * BITS_PER_WORD + bit) as u32)
+        })
+        .collect()
+}
+
+/// Format CPU numbers in ascending order as ranges, e.g. `0-15,32-47`.
+fn cpu_ranges(cpus: &[u32]) -> String {
+    let mut ranges: Vec<(u32, u32)> = vec![];
+
+    for &cpu in cpus {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == cpu => *end = cpu,
+            _ => ranges.push((cpu, cpu)),
+        }
+    }
+
+    ranges
+        .iter()
+        .map(|&(start, end)| match start == end {
+            true => start.to_string(),
+            false => format!("{}-{}", start, end),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// The PCI bus IDs of the devices on the other end of `device`'s active
+/// NvLink links, one per link.
+fn nvlink_peers(device: &Device) -> Result<Vec<String>, NvmlError> {
+    active_nvlinks(device)?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|link| Ok(device.link_wrapper_for(link).remote_pci_info()?.bus_id))
+        .collect()
+}
+
+impl TopologyMatrix {
+    /**
+    Read the topology of every GPU in the system.
+
+    # Errors
+
+    * Any error returned by `Nvml::device_count()`, `Nvml::device_by_index()`
+      or `Device::pci_info()`
+    * Any error other than `NotSupported` returned by
+      `Device::cpu_affinity()` or `Device::topology_common_ancestor()`
+    * Any error other than `NotSupported` and `InvalidArg` returned by
+      `NvLink::is_active()`, and any error returned by
+      `NvLink::remote_pci_info()` for an active link
+
+    # Platform Support
+
+    Only supports Linux.
+    */
+    pub fn read(nvml: &Nvml) -> Result<Self, NvmlError> {
+        let devices = (0..nvml.device_count()?)
+            .map(|index| nvml.device_by_index(index))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut gpus = Vec::with_capacity(devices.len());
+        let mut peers = Vec::with_capacity(devices.len());
+
+        for (index, device) in devices.iter().enumerate() {
+            let mask = supported(device.cpu_affinity(MAX_CPUS / BITS_PER_WORD))?;
+
+            gpus.push(TopologyGpu {
+                index: index as u32,
+                bus_id: device.pci_info()?.bus_id,
+                cpu_affinity: mask.map(|m| cpus_from_mask(&m)),
+            });
+            peers.push(nvlink_peers(device)?);
+        }
+
+        let mut entries = Vec::with_capacity(devices.len());
+
+        for (i, device) in devices.iter().enumerate() {
+            let mut row = Vec::with_capacity(devices.len());
+
+            for (j, (other, gpu)) in devices.iter().zip(&gpus).enumerate() {
+                // A second `Device` for the same handle, as
+                // `topology_common_ancestor()` takes the other device by value
+                let other = unsafe { Device::new(other.handle(), nvml) };
+
+                let level = match i == j {
+                    true => None,
+                    false => supported(device.topology_common_ancestor(other))?,
+                };
+
+                let nvlinks = peers[i]
+                    .iter()
+                    .filter(|peer| i != j && peer.eq_ignore_ascii_case(&gpu.bus_id))
+                    .count() as u32;
+
+                row.push(TopologyEntry { level, nvlinks });
+            }
+
+            entries.push(row);
+        }
+
+        Ok(Self { gpus, entries })
+    }
+
+    /// The connection between the GPUs at positions `a` and `b`, if both
+    /// exist.
+    pub fn get(&self, a: usize, b: usize) -> Option<&TopologyEntry> {
+        self.entries.get(a)?.get(b)
+    }
+}
+
+impl fmt::Display for TopologyMatrix {
+    /// Render the matrix as a tab-separated table like `nvidia-smi topo -m`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for gpu in &self.gpus {
+            write!(f, "\tGPU{}", gpu.index)?;
+        }
+        writeln!(f, "\tCPU Affinity")?;
+
+        for (gpu, row) in self.gpus.iter().zip(&self.entries) {
+            write!(f, "GPU{}", gpu.index)?;
+
+            for entry in row {
+                write!(f, "\t{}", entry.label())?;
+            }
+
+            match gpu.cpu_affinity {
+                Some(ref cpus) => writeln!(f, "\t{}", cpu_ranges(cpus))?,
+                None => writeln!(f, "\tN/A")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::*;
+
+    #[test]
+    fn cpus_from_affinity_mask() {
+        let mut mask: Vec<c_ulong> = vec![0; 2];
+        mask[0] = 0b1011;
+        mask[1] = 0b1;
+
+        let cpus = cpus_from_mask(&mask);
+        assert_eq!(cpus, vec![0, 1, 3, BITS_PER_WORD as u32]);
+        assert_eq!(cpu_ranges(&cpus), format!("0-1,3,{}", BITS_PER_WORD));
+        assert_eq!(cpu_ranges(&[]), "");
+    }
+
+    #[test]
+    fn render() {
+        let gpu = |index| TopologyGpu {
+            index,
+            bus_id: format!("00000000:0{}:00.0", index),
+            cpu_affinity: Some(vec![0, 1, 2, 3]),
+        };
+        let entry = |level, nvlinks| TopologyEntry { level, nvlinks };
+
+        let matrix = TopologyMatrix {
+            gpus: vec![gpu(0), gpu(1)],
+            entries: vec![
+                vec![entry(None, 0), entry(Some(TopologyLevel::System), 4)],
+                vec![entry(Some(TopologyLevel::System), 4), entry(None, 0)],
+            ],
+        };
+
+        assert_eq!(matrix.get(0, 1).map(|e| e.label()), Some("NV4".into()));
+        assert_eq!(entry(Some(TopologyLevel::Node), 0).label(), "NODE");
+        assert_eq!(matrix.get(2, 0), None);
+        assert_eq!(
+            matrix.to_string(),
+            "\tGPU0\tGPU1\tCPU Affinity\nGPU0\tX\tNV4\t0-3\nGPU1\tNV4\tX\t0-3\n"
+        );
+    }
+
+    #[test]
+    fn read() {
+        let nvml = nvml();
+        test(3, || TopologyMatrix::read(&nvml))
+    }
+}
@@ -0,0 +1,424 @@
+/*!
+Temporary vGPU scheduler state changes and continuous scheduler logs.
+
+`VgpuSchedulerGuard` reads the vGPU scheduler state of a `Device`, applies a
+new one and puts the original back when it is dropped, so that experimenting
+with timeslice and ARR settings cannot leave a host in an unexpected
+scheduling mode, even after a panic. NVML doesn't report the ARR frequency,
+so if ARR is enabled, the state to put back has to be given with
+`VgpuSchedulerGuard::apply_restoring()`.
+
+```no_run
+use nvml_wrapper::Nvml;
+use nvml_wrapper::enums::device::{VgpuSchedulerArrMode, VgpuSchedulerPolicy};
+use nvml_wrapper::high_level::vgpu_scheduler::VgpuSchedulerGuard;
+use nvml_wrapper::struct_wrappers::device::{VgpuSchedulerSetParams, VgpuSchedulerSetState};
+
+# fn main() -> Result<(), nvml_wrapper::error::NvmlError> {
+let nvml = Nvml::init()?;
+let device = nvml.device_by_index(0)?;
+
+let guard = VgpuSchedulerGuard::apply(
+    &device,
+    VgpuSchedulerSetState {
+        scheduler_policy: VgpuSchedulerPolicy::EqualShare,
+        enable_arr_mode: VgpuSchedulerArrMode::Disabled,
+        scheduler_params: VgpuSchedulerSetParams {
+            avg_factor: None,
+            frequency_or_timeslice: 2_000_000,
+        },
+    },
+)?;
+
+// Run the experiment...
+
+guard.restore()?;
+# Ok(())
+# }
+```
+
+`Device::vgpu_scheduler_log()` only returns the latest entries the driver
+kept. `VgpuSchedulerLogPoller` fetches the log repeatedly and hands out each
+entry once, so that long-running analysis sees every entry the driver still
+had when it was polled:
+
+```no_run
+use nvml_wrapper::Nvml;
+use nvml_wrapper::high_level::vgpu_scheduler::VgpuSchedulerLogPoller;
+use std::time::Duration;
+
+# fn main() -> Result<(), nvml_wrapper::error::NvmlError> {
+let nvml = Nvml::init()?;
+let device = nvml.device_by_index(0)?;
+
+let mut poller = VgpuSchedulerLogPoller::new(Duration::from_millis(100));
+
+for entry in poller.entries(&device) {
+    let entry = entry?;
+    println!("runlist {} ran for {}ns", entry.sw_runlist_id, entry.time_run);
+#   break;
+}
+# Ok(())
+# }
+```
+*/
+
+use crate::enums::device::VgpuSchedulerArrMode;
+use crate::error::NvmlError;
+use crate::ffi::bindings::NVML_SCHEDULER_SW_MAX_LOG_ENTRIES;
+use crate::high_level::RestoreGuard;
+use crate::struct_wrappers::device::{
+    VgpuSchedulerGetState, VgpuSchedulerLog, VgpuSchedulerLogEntry, VgpuSchedulerSetParams,
+    VgpuSchedulerSetState,
+};
+use crate::Device;
+use std::collections::VecDeque;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/**
+Returns the state that, when set, puts the scheduler back in `state`.
+
+Returns `None` with ARR enabled: NVML then reports a timeslice, but setting
+the state takes the ARR frequency, which NVML doesn't report.
+*/
+fn restoring_state(state: &VgpuSchedulerGetState) -> Option<VgpuSchedulerSetState> {
+    if state.arr_mode == VgpuSchedulerArrMode::Enabled {
+        return None;
+    }
+
+    Some(VgpuSchedulerSetState {
+        scheduler_policy: state.scheduler_policy,
+        enable_arr_mode: state.arr_mode,
+        scheduler_params: VgpuSchedulerSetParams {
+            avg_factor: None,
+            frequency_or_timeslice: state.scheduler_params.timeslice,
+        },
+    })
+}
+
+/// Restores the vGPU scheduler state of a `Device` when dropped.
+#[derive(Debug)]
+pub struct VgpuSchedulerGuard<'dev, 'nvml> {
+    inner: RestoreGuard<&'dev Device<'nvml>, VgpuSchedulerSetState>,
+}
+
+impl<'dev, 'nvml> VgpuSchedulerGuard<'dev, 'nvml> {
+    /**
+    Record the current vGPU scheduler state of `device`, then apply `state`.
+
+    # Errors
+
+    * `NotSupported`, if ARR is currently enabled. NVML doesn't report the
+      ARR frequency, so the current state can't be recorded; use
+      `apply_restoring()` with the state to restore instead
+    * Any error returned by `Device::vgpu_scheduler_state()` or
+      `Device::set_vgpu_scheduler_state()`
+
+    Nothing is changed if recording the current state fails.
+    */
+    pub fn apply(
+        device: &'dev Device<'nvml>,
+        state: VgpuSchedulerSetState,
+    ) -> Result<Self, NvmlError> {
+        let previous =
+            restoring_state(&device.vgpu_scheduler_state()?).ok_or(NvmlError::NotSupported)?;
+
+        Self::apply_restoring(device, state, previous)
+    }
+
+    /**
+    Apply `state` to `device`, restoring `previous` rather than the recorded
+    current state.
+
+    # Errors
+
+    Any error returned by `Device::set_vgpu_scheduler_state()`.
+    */
+    pub fn apply_restoring(
+        device: &'dev Device<'nvml>,
+        state: VgpuSchedulerSetState,
+        previous: VgpuSchedulerSetState,
+    ) -> Result<Self, NvmlError> {
+        device.set_vgpu_scheduler_state(state)?;
+
+        Ok(Self {
+            inner: RestoreGuard::new(device, previous, |device, previous| {
+                device.set_vgpu_scheduler_state(previous)
+            }),
+        })
+    }
+
+    /// The device whose scheduler state is changed.
+    pub fn device(&self) -> &'dev Device<'nvml> {
+        self.inner.device()
+    }
+
+    /// The state that will be restored.
+    pub fn previous(&self) -> &VgpuSchedulerSetState {
+        self.inner.state()
+    }
+
+    /**
+    Restore the recorded state now, reporting whether that worked.
+
+    # Errors
+
+    Any error returned by `Device::set_vgpu_scheduler_state()`.
+    */
+    pub fn restore(self) -> Result<(), NvmlError> {
+        self.inner.restore()
+    }
+
+    /// Keep the applied state: drop the guard without restoring anything.
+    pub fn keep(self) {
+        self.inner.keep()
+    }
+}
+
+/// Fetches the vGPU scheduler log of a `Device` repeatedly, handing out each
+/// entry once.
+#[derive(Debug, Clone)]
+pub struct VgpuSchedulerLogPoller {
+    interval: Duration,
+    last_poll: Option<Instant>,
+    last_timestamp: Option<u64>,
+    // Entries with `last_timestamp`, which a later log may contain again
+    seen_at_last_timestamp: Vec<VgpuSchedulerLogEntry>,
+    pending: VecDeque<VgpuSchedulerLogEntry>,
+    possible_gaps: u64,
+}
+
+impl VgpuSchedulerLogPoller {
+    /// Create a poller that fetches the log at most once every `interval`.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_poll: None,
+            last_timestamp: None,
+            seen_at_last_timestamp: vec![],
+            pending: VecDeque::new(),
+            possible_gaps: 0,
+        }
+    }
+
+    /**
+    The number of fetched logs that may have missed entries.
+
+    A full log none of whose entries were seen before means that more entries
+    were logged between two polls than the driver keeps; poll more often if
+    this keeps growing.
+    */
+    pub fn possible_gaps(&self) -> u64 {
+        self.possible_gaps
+    }
+
+    /// Return the entries of `log` not seen in a previous log, oldest first.
+    pub fn update(&mut self, log: VgpuSchedulerLog) -> Vec<VgpuSchedulerLogEntry> {
+        let mut entries = log.entries;
+        entries.sort_by_key(|e| e.timestamp);
+
+        let total = entries.len();
+
+        let new: Vec<_> = entries
+            .into_iter()
+            .filter(|e| match self.last_timestamp {
+                Some(last) if e.timestamp == last => !self.seen_at_last_timestamp.contains(e),
+                Some(last) => e.timestamp > last,
+                None => true,
+            })
+            .collect();
+
+        if self.last_timestamp.is_some()
+            && total >= NVML_SCHEDULER_SW_MAX_LOG_ENTRIES as usize
+            && new.len() == total
+        {
+            self.possible_gaps += 1;
+        }
+
+        if let Some(newest) = new.last().map(|e| e.timestamp) {
+            if self.last_timestamp != Some(newest) {
+                self.seen_at_last_timestamp.clear();
+            }
+
+            self.last_timestamp = Some(newest);
+            self.seen_at_last_timestamp
+                .extend(new.iter().filter(|e| e.timestamp == newest).cloned());
+        }
+
+        new
+    }
+
+    /**
+    Fetch the log from `device` now and return the entries not seen before,
+    oldest first.
+
+    # Errors
+
+    Any error returned by `Device::vgpu_scheduler_log()`.
+    */
+    pub fn poll(&mut self, device: &Device) -> Result<Vec<VgpuSchedulerLogEntry>, NvmlError> {
+        self.last_poll = Some(Instant::now());
+
+        Ok(self.update(device.vgpu_scheduler_log()?))
+    }
+
+    /**
+    Iterate over the log entries of `device` as they get logged.
+
+    The iterator never ends. It blocks until the next poll when it runs out
+    of entries, and yields the error if a poll fails.
+    */
+    pub fn entries<'a, 'nvml>(
+        &'a mut self,
+        device: &'a Device<'nvml>,
+    ) -> VgpuSchedulerLogEntries<'a, 'nvml> {
+        VgpuSchedulerLogEntries {
+            poller: self,
+            device,
+        }
+    }
+}
+
+/// Returned from `VgpuSchedulerLogPoller::entries()`.
+#[derive(Debug)]
+pub struct VgpuSchedulerLogEntries<'a, 'nvml> {
+    poller: &'a mut VgpuSchedulerLogPoller,
+    device: &'a Device<'nvml>,
+}
+
+impl<'a, 'nvml> Iterator for VgpuSchedulerLogEntries<'a, 'nvml> {
+    type Item = Result<VgpuSchedulerLogEntry, NvmlError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(entry) = self.poller.pending.pop_front() {
+                return Some(Ok(entry));
+            }
+
+            if let Some(at) = self.poller.last_poll {
+                let elapsed = at.elapsed();
+
+                if elapsed < self.poller.interval {
+                    thread::sleep(self.poller.interval - elapsed);
+                }
+            }
+
+            match self.poller.poll(self.device) {
+                Ok(entries) => self.poller.pending.extend(entries),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::enums::device::VgpuSchedulerPolicy;
+    use crate::struct_wrappers::device::VgpuSchedulerParams;
+    use crate::test_utils::*;
+
+    fn entry(timestamp: u64, sw_runlist_id: u32) -> VgpuSchedulerLogEntry {
+        VgpuSchedulerLogEntry {
+            timestamp,
+            time_run_total: 0,
+            time_run: 0,
+            sw_runlist_id,
+            target_time_slice: 0,
+            cumulative_preemption_time: 0,
+        }
+    }
+
+    fn log(entries: Vec<VgpuSchedulerLogEntry>) -> VgpuSchedulerLog {
+        VgpuSchedulerLog {
+            engine_id: 0,
+            scheduler_policy: VgpuSchedulerPolicy::BestEffort,
+            arr_mode: VgpuSchedulerArrMode::Disabled,
+            scheduler_params: VgpuSchedulerParams {
+                avg_factor: None,
+                timeslice: 0,
+            },
+            entries_count: entries.len() as u32,
+            entries,
+        }
+    }
+
+    #[test]
+    fn poller_deduplicates_entries() {
+        let mut poller = VgpuSchedulerLogPoller::new(Duration::from_secs(1));
+        let ids = |entries: Vec<VgpuSchedulerLogEntry>| {
+            entries.iter().map(|e| e.sw_runlist_id).collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            ids(poller.update(log(vec![entry(20, 2), entry(10, 1), entry(20, 3)]))),
+            vec![1, 2, 3]
+        );
+
+        // Entries at the newest timestamp seen so far may be logged again
+        assert_eq!(
+            ids(poller.update(log(vec![
+                entry(20, 2),
+                entry(20, 3),
+                entry(20, 4),
+                entry(30, 5)
+            ]))),
+            vec![4, 5]
+        );
+        assert!(poller.update(log(vec![entry(30, 5)])).is_empty());
+        assert_eq!(poller.possible_gaps(), 0);
+
+        let full = (0..NVML_SCHEDULER_SW_MAX_LOG_ENTRIES)
+            .map(|i| entry(100 + i as u64, i))
+            .collect();
+        assert_eq!(
+            poller.update(log(full)).len(),
+            NVML_SCHEDULER_SW_MAX_LOG_ENTRIES as usize
+        );
+        assert_eq!(poller.possible_gaps(), 1);
+    }
+
+    #[test]
+    fn poll_scheduler_log() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            VgpuSchedulerLogPoller::new(Duration::from_millis(50)).poll(device)
+        })
+    }
+
+    #[test]
+    fn restoring_state_from_get_state() {
+        let restoring = restoring_state(&VgpuSchedulerGetState {
+            arr_mode: VgpuSchedulerArrMode::Disabled,
+            scheduler_policy: VgpuSchedulerPolicy::FixedShare,
+            scheduler_params: VgpuSchedulerParams {
+                avg_factor: None,
+                timeslice: 2_000_000,
+            },
+        })
+        .unwrap();
+
+        assert_eq!(restoring.scheduler_policy, VgpuSchedulerPolicy::FixedShare);
+        assert_eq!(restoring.enable_arr_mode, VgpuSchedulerArrMode::Disabled);
+        assert_eq!(
+            restoring.scheduler_params,
+            VgpuSchedulerSetParams {
+                avg_factor: None,
+                frequency_or_timeslice: 2_000_000,
+            }
+        );
+
+        // The ARR frequency isn't reported
+        let restoring = restoring_state(&VgpuSchedulerGetState {
+            arr_mode: VgpuSchedulerArrMode::Enabled,
+            scheduler_policy: VgpuSchedulerPolicy::BestEffort,
+            scheduler_params: VgpuSchedulerParams {
+                avg_factor: Some(33),
+                timeslice: 2_000_000,
+            },
+        });
+
+        assert_eq!(restoring, None);
+    }
+}
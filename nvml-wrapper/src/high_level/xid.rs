@@ -0,0 +1,212 @@
+/*!
+A lookup table of XID errors.
+
+Critical XID events (see `EventKind::XidCriticalError`) only carry the XID
+number. `XidInfo::lookup()` (or `XidError::info()`) maps it to a name, a
+description and the usual cause, following NVIDIA's XID documentation, so
+that alerts can be triaged without a copy of that table.
+
+```
+use nvml_wrapper::enums::event::XidError;
+use nvml_wrapper::high_level::xid::XidCategory;
+
+let info = XidError::Value(79).info().unwrap();
+
+assert_eq!(info.name, "GPU has fallen off the bus");
+assert_eq!(info.category, XidCategory::Hardware);
+```
+*/
+
+use crate::enums::event::XidError;
+use crate::high_level::health::RecommendedAction;
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+
+/// The usual cause of an XID error.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum XidCategory {
+    /// Faulty or failing hardware, including memory, power, PCIe and NvLink.
+    Hardware,
+    /// A driver or firmware error (or a hardware error the driver surfaces).
+    Driver,
+    /// A bug in, or misbehaviour of, the application running on the GPU.
+    Application,
+}
+
+/// What is known about an XID error.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct XidInfo {
+    pub xid: u64,
+    /// The name NVIDIA documents the XID under.
+    pub name: &'static str,
+    pub description: &'static str,
+    pub category: XidCategory,
+}
+
+impl XidInfo {
+    /// Look up an XID number. Returns `None` for XIDs not in the table.
+    pub fn lookup(xid: u64) -> Option<&'static XidInfo> {
+        XIDS.binary_search_by_key(&xid, |info| info.xid)
+            .ok()
+            .map(|i| &XIDS[i])
+    }
+
+    /// Every known XID, in ascending order.
+    pub fn all() -> &'static [XidInfo] {
+        XIDS
+    }
+
+    /// The action recommended for this XID; see
+    /// `RecommendedAction::for_xid()`.
+    pub fn recommended_action(&self) -> RecommendedAction {
+        RecommendedAction::for_xid(&XidError::Value(self.xid))
+    }
+}
+
+impl XidError {
+    /// Look up this XID error. Returns `None` for `Unknown` and for XIDs not
+    /// in the table.
+    pub fn info(&self) -> Option<&'static XidInfo> {
+        match *self {
+            XidError::Value(v) => XidInfo::lookup(v),
+            XidError::Unknown => None,
+        }
+    }
+}
+
+macro_rules! xids {
+    ($($xid:literal, $category:ident, $name:literal, $description:literal;)*) => {
+        &[$(XidInfo {
+            xid: $xid,
+            name: $name,
+            description: $description,
+            category: XidCategory::$category,
+        }),*]
+    };
+}
+
+// Sorted by XID so that lookups can binary search
+static XIDS: &[XidInfo] = xids![
+    1, Driver, "Invalid or corrupted push buffer stream",
+        "The driver sent the GPU a command stream it could not decode.";
+    2, Driver, "Invalid or corrupted push buffer stream",
+        "The driver sent the GPU a command stream it could not decode.";
+    3, Driver, "Invalid or corrupted push buffer stream",
+        "The driver sent the GPU a command stream it could not decode.";
+    4, Driver, "Invalid or corrupted push buffer stream",
+        "The driver sent the GPU a command stream it could not decode, or a GPU semaphore timed out.";
+    6, Driver, "Invalid or corrupted push buffer stream",
+        "The driver sent the GPU a command stream it could not decode.";
+    7, Driver, "Invalid or corrupted push buffer address",
+        "The GPU was given a command stream address it could not access.";
+    8, Driver, "GPU stopped processing",
+        "The GPU stopped making progress on submitted work; usually a driver or application hang.";
+    9, Driver, "Driver error programming GPU",
+        "The driver failed to program the GPU.";
+    11, Driver, "Invalid or corrupted push buffer stream",
+        "The driver sent the GPU a command stream it could not decode.";
+    12, Driver, "Driver error handling GPU exception",
+        "The driver failed while handling an exception raised by the GPU.";
+    13, Application, "Graphics Engine Exception",
+        "An application triggered an exception on the GPU, e.g. an out of bounds array access or an illegal instruction.";
+    16, Driver, "Display engine hung",
+        "The display engine stopped responding.";
+    18, Driver, "Bus mastering disabled in PCI Config Space",
+        "The GPU cannot access system memory because bus mastering is disabled.";
+    24, Application, "GPU semaphore timeout",
+        "A GPU semaphore was not released in time, usually because of an application hang.";
+    26, Driver, "Framebuffer timeout",
+        "An access to GPU memory timed out.";
+    31, Application, "GPU memory page fault",
+        "An application accessed GPU memory it had not mapped, or with the wrong permissions.";
+    32, Hardware, "Invalid or corrupted push buffer stream",
+        "A command stream was corrupted on its way to the GPU, usually because of a PCIe problem.";
+    38, Driver, "Driver firmware error",
+        "The firmware running on the GPU reported an error.";
+    43, Application, "GPU stopped processing",
+        "The GPU stopped the application's work after a fault the application caused; the GPU is healthy.";
+    44, Driver, "Graphics Engine fault during context switch",
+        "An uncorrectable error occurred while switching contexts on the GPU.";
+    45, Application, "Preemptive cleanup, due to previous errors",
+        "The application's work was stopped because of earlier errors or because the application was killed.";
+    48, Hardware, "Double Bit ECC Error",
+        "An uncorrectable ECC error was detected in GPU memory.";
+    54, Hardware, "Auxiliary power is not connected to the GPU board",
+        "The GPU is missing power from its auxiliary power connectors.";
+    56, Driver, "Display Engine error",
+        "The display engine reported an error.";
+    57, Hardware, "Error programming video memory interface",
+        "The driver could not bring up the interface to GPU memory.";
+    58, Hardware, "Unstable video memory interface detected",
+        "The interface to GPU memory is unstable.";
+    61, Driver, "Internal micro-controller breakpoint/warning",
+        "An internal micro-controller on the GPU hit a breakpoint or reported a warning.";
+    62, Driver, "Internal micro-controller halt",
+        "An internal micro-controller on the GPU halted.";
+    63, Hardware, "ECC page retirement or row remapping recording event",
+        "A page of GPU memory was retired (or a row remapped) after ECC errors.";
+    64, Hardware, "ECC page retirement or row remapper recording failure",
+        "A page of GPU memory with ECC errors could not be retired (or a row remapped).";
+    68, Driver, "NVDEC0 Exception",
+        "The video decoder raised an exception.";
+    69, Driver, "Graphics Engine class error",
+        "The graphics engine was given an invalid command.";
+    74, Hardware, "NVLink Error",
+        "An NvLink link reported an error, which may be caused by the link, a peer GPU or an NvSwitch.";
+    79, Hardware, "GPU has fallen off the bus",
+        "The GPU stopped responding on the PCIe bus.";
+    80, Hardware, "Corrupted data sent to GPU",
+        "Data sent to the GPU over PCIe was corrupted.";
+    81, Hardware, "VGA Subsystem Error",
+        "The VGA subsystem of the GPU reported an error.";
+    92, Hardware, "High single-bit ECC error rate",
+        "GPU memory is reporting correctable ECC errors at a high rate.";
+    93, Driver, "Non-fatal violation of provisioned InfoROM wear limit",
+        "The InfoROM has been written to more than it was provisioned for.";
+    94, Hardware, "Contained ECC error",
+        "An uncorrectable ECC error was detected and contained to the applications using the affected memory.";
+    95, Hardware, "Uncontained ECC error",
+        "An uncorrectable ECC error was detected and could not be contained; every application on the GPU is affected.";
+    109, Application, "Context Switch Timeout Error",
+        "The GPU could not switch away from an application's context in time.";
+    110, Hardware, "Security Fault Error",
+        "The GPU detected a security fault.";
+    119, Driver, "GSP RPC Timeout",
+        "A call to the GPU System Processor timed out.";
+    120, Driver, "GSP Error",
+        "The GPU System Processor reported an error.";
+    121, Hardware, "C2C Link Error",
+        "The chip-to-chip link between the GPU and the CPU reported an error.";
+    140, Hardware, "Unrecovered ECC Error",
+        "An uncorrectable ECC error was detected in GPU memory at a point where it could not be recovered from.";
+    143, Hardware, "GPU Initialization Failure",
+        "The GPU failed to initialize.";
+    154, Driver, "GPU Recovery Action Changed",
+        "The driver changed the action it recommends to recover the GPU; see `health::driver_recommended_action()`.";
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn table_is_sorted() {
+        assert!(XidInfo::all().windows(2).all(|w| w[0].xid < w[1].xid));
+    }
+
+    #[test]
+    fn lookup() {
+        let info = XidInfo::lookup(13).unwrap();
+        assert_eq!(info.xid, 13);
+        assert_eq!(info.category, XidCategory::Application);
+        assert_eq!(info.recommended_action(), RecommendedAction::Retry);
+
+        assert_eq!(
+            XidError::Value(48).info().map(|i| i.category),
+            Some(XidCategory::Hardware)
+        );
+        assert_eq!(XidInfo::lookup(5), None);
+        assert_eq!(XidError::Unknown.info(), None);
+    }
+}
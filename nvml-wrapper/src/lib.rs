@@ -85,6 +85,9 @@ way to avoid bumping this.
 The `serde` feature can be toggled on in order to `#[derive(Serialize, Deserialize)]`
 for every NVML data structure.
 
+The `tokio` feature can be toggled on in order to get `EventSet::into_stream()`,
+which delivers NVML events as a `futures_core::Stream` for use in async code.
+
 [nvml]: https://developer.nvidia.com/nvidia-management-library-nvml
 [libloading]: https://github.com/nagisa/rust_libloading
 [once_cell]: https://docs.rs/once_cell/latest/once_cell/sync/struct.Lazy.html
@@ -140,17 +143,31 @@ use static_assertions::assert_impl_all;
 
 #[cfg(target_os = "linux")]
 use crate::enum_wrappers::device::TopologyLevel;
+use crate::enum_wrappers::device::VgpuDriverCapability;
 
-use crate::error::{nvml_sym, nvml_try, NvmlError};
+#[cfg(target_os = "linux")]
+use crate::bitmasks::event::EventTypes;
+#[cfg(target_os = "linux")]
+use crate::error::NvmlErrorWithSource;
+use crate::error::{nvml_sym, nvml_try, with_blob, NvmlError};
 use crate::ffi::bindings::*;
 
 use crate::struct_wrappers::ExcludedDeviceInfo;
 
+use crate::high_level::multi::MultiResult;
+#[cfg(target_os = "linux")]
+use crate::high_level::topology::TopologyMatrix;
 #[cfg(target_os = "linux")]
 use crate::struct_wrappers::device::PciInfo;
 use crate::struct_wrappers::device::VgpuVersion;
+use crate::struct_wrappers::device::{PgpuMetadata, VgpuCompatibility, VgpuMetadata};
 use crate::struct_wrappers::unit::HwbcEntry;
 
+use crate::structs::device::{
+    ConfidentialComputeCapabilities, ConfidentialComputeSettings, ConfidentialComputeState,
+    KeyRotationThreshold,
+};
+
 use crate::bitmasks::InitFlags;
 
 #[cfg(not(target_os = "linux"))]
@@ -511,6 +528,25 @@ impl Nvml {
         }
     }
 
+    /**
+    Run `f` on every device, collecting the result for each device instead of
+    stopping at the first failure.
+
+    Failing to get a device's handle is recorded as that device's result.
+
+    # Errors
+
+    Any error returned by `.device_count()`.
+    */
+    pub fn map_devices<T, F>(&self, mut f: F) -> Result<MultiResult<T>, NvmlError>
+    where
+        F: FnMut(&Device) -> Result<T, NvmlError>,
+    {
+        Ok((0..self.device_count()?)
+            .map(|index| (index, self.device_by_index(index).and_then(|d| f(&d))))
+            .collect())
+    }
+
     /**
     Acquire the handle for a particular device based on its PCI bus ID.
 
@@ -763,6 +799,25 @@ impl Nvml {
         }
     }
 
+    /**
+    Gets how every pair of GPUs in the system is connected, along with the CPUs
+    each GPU has affinity to; the same data `nvidia-smi topo -m` prints.
+
+    See `high_level::topology` for details.
+
+    # Errors
+
+    Any error returned by `TopologyMatrix::read()`.
+
+    # Platform Support
+
+    Only supports Linux.
+    */
+    #[cfg(target_os = "linux")]
+    pub fn topology_matrix(&self) -> Result<TopologyMatrix, NvmlError> {
+        TopologyMatrix::read(self)
+    }
+
     /**
     Gets the IDs and firmware versions for any Host Interface Cards in the system.
 
@@ -888,6 +943,43 @@ impl Nvml {
         }
     }
 
+    /**
+    Create a set of events and register `events` with every device, restricted
+    to the event types each device supports.
+
+    Devices that support none of `events` are skipped. Use `EventData.source()`
+    to find out which device an event occurred on.
+
+    # Errors
+
+    * Any error returned by `.device_count()`, `.device_by_index()` or
+      `.create_event_set()`
+    * Any error returned by `Device.supported_event_types()` or
+      `Device.register_events()`
+
+    # Platform Support
+
+    Only supports Linux.
+    */
+    #[cfg(target_os = "linux")]
+    pub fn create_event_set_for_all_devices(
+        &self,
+        events: EventTypes,
+    ) -> Result<EventSet<'_>, NvmlErrorWithSource> {
+        let mut set = self.create_event_set()?;
+
+        for index in 0..self.device_count()? {
+            let device = self.device_by_index(index)?;
+            let types = events & device.supported_event_types()?;
+
+            if !types.is_empty() {
+                set = device.register_events(types, set)?;
+            }
+        }
+
+        Ok(set)
+    }
+
     /**
     Request the OS and the NVIDIA kernel driver to rediscover a portion of the PCI
     subsystem in search of GPUs that were previously removed.
@@ -926,6 +1018,36 @@ impl Nvml {
     # Platform Support
 
     Only supports Linux.
+
+    # Examples
+
+    Draining a failing GPU, removing it from the driver and bringing it back
+    after it has been serviced or hot-plugged:
+
+    ```no_run
+    # use nvml_wrapper::Nvml;
+    # use nvml_wrapper::error::*;
+    # use nvml_wrapper::enum_wrappers::device::{DetachGpuState, PcieLinkState};
+    # fn test() -> Result<(), NvmlErrorWithSource> {
+    # let nvml = Nvml::init()?;
+    let mut device = nvml.device_by_index(0)?;
+    // Keep the `PciInfo` around; the `Device` goes away when it is removed
+    let pci_info = device.pci_info()?;
+
+    // Stop new work from being scheduled on the GPU
+    device.set_drain(true, pci_info.clone())?;
+    assert!(device.is_drain_enabled(pci_info.clone())?);
+
+    // Once it is idle, detach it from the driver
+    let (res, _) = device.remove(pci_info.clone(), DetachGpuState::Remove, PcieLinkState::ShutDown);
+    res?;
+
+    // ... service the GPU ...
+
+    nvml.discover_gpus(pci_info)?;
+    # Ok(())
+    # }
+    ```
     */
     // TODO: constructor for default pci_infos ^
     // Checked against local
@@ -982,11 +1104,37 @@ impl Nvml {
     }
 
     /**
-    Gets the loaded vGPU list of capabilities
+    Gets information for every device excluded by the driver.
+
+    Excluded devices don't show up in `.device_count()` or
+    `.device_by_index()`, so this is the only way to see them.
+
+    # Errors
+
+    Any error returned by `.excluded_device_count()` or
+    `.excluded_device_info()`.
+
+    # Device Support
+
+    Supports all devices.
+    */
+    pub fn excluded_devices(&self) -> Result<Vec<ExcludedDeviceInfo>, NvmlError> {
+        (0..self.excluded_device_count()?)
+            .map(|index| self.excluded_device_info(index))
+            .collect()
+    }
+
+    /**
+    Check whether the loaded host driver supports the given vGPU capability.
+
+    Use this before looking at per-device capabilities with
+    `Device.vgpu_capabilities()`.
 
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if `capability` is invalid
+    * `NotSupported`, if the driver does not support vGPU
     * `Unknown`, on any unexpected error
 
     # Device Support
@@ -996,15 +1144,191 @@ impl Nvml {
     #[doc(alias = "nvmlGetVgpuDriverCapabilities")]
     pub fn vgpu_driver_capabilities(
         &self,
-        capability: nvmlVgpuDriverCapability_t,
-    ) -> Result<u32, NvmlError> {
+        capability: VgpuDriverCapability,
+    ) -> Result<bool, NvmlError> {
         let sym = nvml_sym(self.lib.nvmlGetVgpuDriverCapabilities.as_ref())?;
 
         unsafe {
-            let mut mask: u32 = mem::zeroed();
+            let mut result: c_uint = 0;
+
+            nvml_try(sym(capability.as_c(), &mut result))?;
+            Ok(result != 0)
+        }
+    }
+
+    /**
+    Gets the confidential compute capabilities of the system: which
+    confidential computing technology the CPU supports, and whether the GPUs
+    are capable of confidential compute.
+
+    Check `ConfidentialComputeCapabilities.is_supported()` before attempting
+    attestation (e.g. with `Device.confidential_compute_gpu_attestation_report()`).
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `NotSupported`, if this query is not supported by the system
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Hopper and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlSystemGetConfComputeCapabilities")]
+    pub fn confidential_compute_capabilities(
+        &self,
+    ) -> Result<ConfidentialComputeCapabilities, NvmlError> {
+        let sym = nvml_sym(self.lib.nvmlSystemGetConfComputeCapabilities.as_ref())?;
+
+        unsafe {
+            let mut capabilities: nvmlConfComputeSystemCaps_t = mem::zeroed();
+            nvml_try(sym(&mut capabilities))?;
 
-            nvml_try(sym(capability, &mut mask))?;
-            Ok(mask)
+            ConfidentialComputeCapabilities::try_from(capabilities)
+        }
+    }
+
+    /**
+    Gets the confidential compute state of the system: its environment, and
+    whether confidential compute and dev-tools mode are enabled.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `NotSupported`, if this query is not supported by the system
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Hopper and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlSystemGetConfComputeState")]
+    pub fn confidential_compute_state(&self) -> Result<ConfidentialComputeState, NvmlError> {
+        let sym = nvml_sym(self.lib.nvmlSystemGetConfComputeState.as_ref())?;
+
+        unsafe {
+            let mut state: nvmlConfComputeSystemState_t = mem::zeroed();
+            nvml_try(sym(&mut state))?;
+
+            ConfidentialComputeState::try_from(state)
+        }
+    }
+
+    /**
+    Gets the confidential compute settings of the system. These are the
+    fields of `.confidential_compute_state()` plus the multi-GPU protected
+    PCIe mode.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `NotSupported`, if this query is not supported by the system
+    * `UnexpectedVariant`, for which you can read the docs for (this includes
+      `NVML_ERROR_ARGUMENT_VERSION_MISMATCH`, if the driver does not support the
+      version of the settings struct this crate uses)
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Hopper and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlSystemGetConfComputeSettings")]
+    pub fn confidential_compute_settings(&self) -> Result<ConfidentialComputeSettings, NvmlError> {
+        let sym = nvml_sym(self.lib.nvmlSystemGetConfComputeSettings.as_ref())?;
+
+        unsafe {
+            let mut settings: nvmlSystemConfComputeSettings_t = mem::zeroed();
+            // Implements NVML_STRUCT_VERSION(SystemConfComputeSettings, 1), as detailed in nvml.h
+            settings.version = (mem::size_of::<nvmlSystemConfComputeSettings_v1_t>()
+                | (1_usize << 24_usize)) as u32;
+            nvml_try(sym(&mut settings))?;
+
+            ConfidentialComputeSettings::try_from(settings)
+        }
+    }
+
+    /**
+    Gets the confidential compute key rotation threshold.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `NotSupported`, if this query is not supported by the system
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Hopper and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlSystemGetConfComputeKeyRotationThresholdInfo")]
+    pub fn confidential_compute_key_rotation_threshold(
+        &self,
+    ) -> Result<KeyRotationThreshold, NvmlError> {
+        let sym = nvml_sym(
+            self.lib
+                .nvmlSystemGetConfComputeKeyRotationThresholdInfo
+                .as_ref(),
+        )?;
+
+        unsafe {
+            let mut info: nvmlConfComputeGetKeyRotationThresholdInfo_t = mem::zeroed();
+            // Implements NVML_STRUCT_VERSION(ConfComputeGetKeyRotationThresholdInfo, 1), as detailed in nvml.h
+            info.version = (mem::size_of::<nvmlConfComputeGetKeyRotationThresholdInfo_v1_t>()
+                | (1_usize << 24_usize)) as u32;
+            nvml_try(sym(&mut info))?;
+
+            Ok(KeyRotationThreshold {
+                max_attacker_advantage: info.attackerAdvantage,
+            })
+        }
+    }
+
+    /**
+    Sets the confidential compute key rotation threshold.
+
+    This can only be done while the GPUs are not accepting work; see
+    `Device.set_confidential_compute_state()`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if `threshold` is not between `KeyRotationThreshold::MIN`
+      and `KeyRotationThreshold::MAX`
+    * `UnexpectedVariant`, for which you can read the docs for (this includes
+      `NVML_ERROR_INVALID_STATE`, if the GPUs are accepting work)
+    * `NotSupported`, if this query is not supported by the system
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Hopper and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlSystemSetConfComputeKeyRotationThresholdInfo")]
+    pub fn set_confidential_compute_key_rotation_threshold(
+        &self,
+        threshold: KeyRotationThreshold,
+    ) -> Result<(), NvmlError> {
+        if !threshold.is_valid() {
+            return Err(NvmlError::InvalidArg);
+        }
+
+        let sym = nvml_sym(
+            self.lib
+                .nvmlSystemSetConfComputeKeyRotationThresholdInfo
+                .as_ref(),
+        )?;
+
+        unsafe {
+            let mut info: nvmlConfComputeSetKeyRotationThresholdInfo_t = mem::zeroed();
+            // Implements NVML_STRUCT_VERSION(ConfComputeSetKeyRotationThresholdInfo, 1), as detailed in nvml.h
+            info.version = (mem::size_of::<nvmlConfComputeSetKeyRotationThresholdInfo_v1_t>()
+                | (1_usize << 24_usize)) as u32;
+            info.maxAttackerAdvantage = threshold.max_attacker_advantage;
+
+            nvml_try(sym(&mut info))
         }
     }
 
@@ -1037,6 +1361,40 @@ impl Nvml {
 
         unsafe { nvml_try(sym(&mut version.as_c())) }
     }
+
+    /**
+    Check whether a vGPU instance is compatible with a physical GPU, e.g.
+    before live-migrating a VM to another host.
+
+    `vgpu` is obtained from `VgpuInstance.metadata()` (typically on the source
+    host) and `pgpu` from `Device.vgpu_metadata()` (typically on the
+    destination host).
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if either metadata is invalid
+    * `IncorrectBits`, if NVML returns compatibility flags that don't match the
+      flags in this wrapper
+    * `Unknown`, on any unexpected error
+    */
+    #[doc(alias = "nvmlGetVgpuCompatibility")]
+    pub fn vgpu_compatibility(
+        &self,
+        vgpu: &VgpuMetadata,
+        pgpu: &PgpuMetadata,
+    ) -> Result<VgpuCompatibility, NvmlError> {
+        let sym = nvml_sym(self.lib.nvmlGetVgpuCompatibility.as_ref())?;
+
+        let compatibility = with_blob(vgpu.as_bytes(), |vgpu| {
+            with_blob(pgpu.as_bytes(), |pgpu| unsafe {
+                let mut compatibility: nvmlVgpuPgpuCompatibility_t = mem::zeroed();
+                nvml_try(sym(vgpu, pgpu, &mut compatibility)).map(|_| compatibility)
+            })
+        })?;
+
+        VgpuCompatibility::try_from(compatibility)
+    }
 }
 
 /// This `Drop` implementation ignores errors! Use the `.shutdown()` method on
@@ -1137,6 +1495,31 @@ mod test {
         test(3, || nvml().device_count())
     }
 
+    #[test]
+    fn map_devices() {
+        let nvml = nvml();
+        test(3, || nvml.map_devices(|device| device.name()))
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn topology_matrix() {
+        let nvml = nvml();
+        test(3, || nvml.topology_matrix())
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn create_event_set_for_all_devices() {
+        use crate::bitmasks::event::EventTypes;
+
+        let nvml = nvml();
+        test(3, || {
+            nvml.create_event_set_for_all_devices(EventTypes::all())
+                .map_err(|e| e.error)
+        })
+    }
+
     #[test]
     fn sys_driver_version() {
         test(3, || nvml().sys_driver_version())
@@ -1304,10 +1687,51 @@ mod test {
         }
     }
 
+    #[test]
+    fn excluded_devices() {
+        let nvml = nvml();
+        test(3, || nvml.excluded_devices())
+    }
+
     #[test]
     fn vgpu_driver_capabilities() {
         let nvml = nvml();
-        test(3, || nvml.vgpu_driver_capabilities(1))
+        test(3, || {
+            nvml.vgpu_driver_capabilities(VgpuDriverCapability::HeterogeneousMultiVgpu)
+        })
+    }
+
+    #[test]
+    fn confidential_compute_capabilities() {
+        let nvml = nvml();
+        test(3, || nvml.confidential_compute_capabilities())
+    }
+
+    #[test]
+    fn confidential_compute_state() {
+        let nvml = nvml();
+        test(3, || nvml.confidential_compute_state())
+    }
+
+    #[test]
+    fn confidential_compute_settings() {
+        let nvml = nvml();
+        test(3, || nvml.confidential_compute_settings())
+    }
+
+    #[test]
+    fn confidential_compute_key_rotation_threshold() {
+        let nvml = nvml();
+        test(3, || nvml.confidential_compute_key_rotation_threshold())
+    }
+
+    // This modifies system state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn set_confidential_compute_key_rotation_threshold() {
+        let nvml = nvml();
+
+        nvml.set_confidential_compute_key_rotation_threshold(KeyRotationThreshold::DEFAULT)
+            .expect("set key rotation threshold")
     }
 
     #[test]
@@ -10,7 +10,7 @@ use crate::enums::nv_link::Counter;
 use crate::error::{nvml_sym, nvml_try, NvmlError};
 use crate::ffi::bindings::*;
 use crate::struct_wrappers::{device::PciInfo, nv_link::UtilizationControl};
-use crate::structs::nv_link::UtilizationCounter;
+use crate::structs::nv_link::{ErrorCounters, UtilizationCounter};
 
 use std::{
     convert::TryFrom,
@@ -247,6 +247,34 @@ impl<'device, 'nvml: 'device> NvLink<'device, 'nvml> {
         }
     }
 
+    /**
+    Gets the values of all error counters.
+
+    This is a shorthand for calling `.error_counter()` with every
+    `ErrorCounter`, e.g. once per scrape.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `link` or `Device` within this `NvLink` struct instance
+      is invalid
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Pascal or newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetNvLinkErrorCounter")]
+    pub fn all_error_counters(&self) -> Result<ErrorCounters, NvmlError> {
+        Ok(ErrorCounters {
+            dl_replay: self.error_counter(ErrorCounter::DlReplay)?,
+            dl_recovery: self.error_counter(ErrorCounter::DlRecovery)?,
+            dl_crc_flit: self.error_counter(ErrorCounter::DlCrcFlit)?,
+            dl_crc_data: self.error_counter(ErrorCounter::DlCrcData)?,
+        })
+    }
+
     /**
     Resets all error counters to zero.
 
@@ -572,6 +600,46 @@ impl<'device, 'nvml: 'device> NvLink<'device, 'nvml> {
         unsafe { nvml_try(sym(mode)) }
     }
 
+    /**
+    Sets the idle time after which NvLink links enter the low power state.
+
+    `threshold` must be between `NVML_NVLINK_LOW_POWER_THRESHOLD_MIN` and
+    `NVML_NVLINK_LOW_POWER_THRESHOLD_MAX`, in units of 100μs; pass `None` to
+    reset it to the driver default.
+
+    Note that the threshold applies to every link of the `Device` within this
+    `NvLink` struct instance, not only to this one.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `Device` within this `NvLink` struct instance is
+      invalid or `threshold` is out of range
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Hopper or newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceSetNvLinkDeviceLowPowerThreshold")]
+    pub fn set_low_power_threshold(&mut self, threshold: Option<u32>) -> Result<(), NvmlError> {
+        let sym = nvml_sym(
+            self.device
+                .nvml()
+                .lib
+                .nvmlDeviceSetNvLinkDeviceLowPowerThreshold
+                .as_ref(),
+        )?;
+
+        let mut info = nvmlNvLinkPowerThres_t {
+            lowPwrThreshold: threshold.unwrap_or(NVML_NVLINK_LOW_POWER_THRESHOLD_RESET),
+        };
+
+        unsafe { nvml_try(sym(self.device.handle(), &mut info)) }
+    }
+
     /**
      Get the NvLink device type for a given link index
 
@@ -650,6 +718,13 @@ mod test {
         })
     }
 
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn all_error_counters() {
+        let nvml = nvml();
+        test_with_link(3, &nvml, |link| link.all_error_counters())
+    }
+
     // This modifies link state, so we don't want to actually run the test
     #[allow(dead_code)]
     fn reset_error_counters() {
@@ -738,4 +813,14 @@ mod test {
         let mode = link.bw_mode().unwrap();
         link.set_bw_mode(mode).unwrap();
     }
+
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn set_low_power_threshold() {
+        let nvml = nvml();
+        let device = device(&nvml);
+        let mut link = device.link_wrapper_for(0);
+
+        link.set_low_power_threshold(None).unwrap();
+    }
 }
@@ -1,20 +1,31 @@
-use crate::bitmasks::device::FbcFlags;
+use crate::bitmasks::device::{
+    CoolerTarget, FbcFlags, VgpuCompatibilityLimit, VgpuVmCompatibility,
+};
 use crate::enum_wrappers::device::{
-    BridgeChip, Clock, EncoderType, FbcSessionType, PerformanceState, SampleValueType,
+    BridgeChip, Clock, CoolerControl, EncoderType, FbcSessionType, GridLicenseFeatureCode,
+    PerformanceState, PowerProfileType, SampleValueType, VgpuGuestInfoState,
+};
+use crate::enums::device::{
+    FirmwareVersion, GpuFabricState, LicenseExpiryStatus, SampleValue, ThermalController,
+    ThermalTarget, UsedGpuMemory, VgpuLicenseState, VgpuSchedulerArrMode, VgpuSchedulerPolicy,
 };
-use crate::enums::device::{FirmwareVersion, SampleValue, UsedGpuMemory};
 use crate::error::{nvml_try, Bits, NvmlError};
 use crate::ffi::bindings::*;
 use crate::structs::device::FieldId;
+use crate::vgpu::VgpuInstanceId;
 #[cfg(feature = "serde")]
 use serde_derive::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
+    collections::BTreeMap,
     ffi::{CStr, CString},
+    mem,
+    ops::Range,
 };
 use std::{
     convert::{TryFrom, TryInto},
-    os::raw::c_char,
+    os::raw::{c_char, c_uint},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 /// PCI information about a GPU device.
@@ -294,6 +305,8 @@ pub struct MemoryInfo {
     pub free: u64,
 
     /// Reserved FB memory.
+    ///
+    /// Always 0 when the driver only supports the v1 query; see `version`.
     pub reserved: u64,
 
     /// Total installed FB memory.
@@ -301,13 +314,53 @@ pub struct MemoryInfo {
     /// Allocated FB memory.
     ///
     /// Note that the driver/GPU always sets aside a small amount of memory for
-    /// bookkeeping.
+    /// bookkeeping. The v1 query (see `version`) counts that memory as used,
+    /// the v2 query reports it in `reserved` instead.
     pub used: u64,
 
     /// Struct version, must be set according to API specification before calling the API.
+    ///
+    /// 1 if `Device.memory_info()` had to fall back to the v1 query.
     pub version: u32,
 }
 
+impl MemoryInfo {
+    /**
+    Memory available to allocations: `total` minus `reserved`.
+
+    `total` includes memory the driver reserves for itself, which can never
+    be allocated, so `used + free` adds up to this rather than `total`.
+    */
+    pub fn usable(&self) -> u64 {
+        self.total.saturating_sub(self.reserved)
+    }
+
+    /// The percentage of `usable()` memory that is used, as reported by
+    /// nvidia-smi.
+    ///
+    /// With the v1 query, this includes the memory reserved by the driver.
+    pub fn used_percent(&self) -> f64 {
+        percent(self.used, self.usable())
+    }
+
+    /// The percentage of `usable()` memory that is free.
+    pub fn free_percent(&self) -> f64 {
+        percent(self.free, self.usable())
+    }
+
+    /// The percentage of `total` memory reserved by the driver.
+    pub fn reserved_percent(&self) -> f64 {
+        percent(self.reserved, self.total)
+    }
+}
+
+fn percent(part: u64, whole: u64) -> f64 {
+    match whole {
+        0 => 0.0,
+        whole => part as f64 / whole as f64 * 100.0,
+    }
+}
+
 impl From<nvmlMemory_v2_t> for MemoryInfo {
     fn from(struct_: nvmlMemory_v2_t) -> Self {
         Self {
@@ -320,6 +373,41 @@ impl From<nvmlMemory_v2_t> for MemoryInfo {
     }
 }
 
+impl From<nvmlMemory_t> for MemoryInfo {
+    fn from(struct_: nvmlMemory_t) -> Self {
+        Self {
+            free: struct_.free,
+            // Not reported by the v1 query
+            reserved: 0,
+            total: struct_.total,
+            used: struct_.used,
+            version: 1,
+        }
+    }
+}
+
+/// How a device's memory is split between protected and unprotected memory
+/// for confidential compute (in KiB).
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ConfComputeMemSizeInfo {
+    /// Memory only accessible from within the confidential compute
+    /// environment.
+    pub protected_mem_size_kib: u64,
+    /// Memory accessible from outside the confidential compute environment,
+    /// e.g. for bounce buffers.
+    pub unprotected_mem_size_kib: u64,
+}
+
+impl From<nvmlConfComputeMemSizeInfo_t> for ConfComputeMemSizeInfo {
+    fn from(struct_: nvmlConfComputeMemSizeInfo_t) -> Self {
+        Self {
+            protected_mem_size_kib: struct_.protectedMemSizeKib,
+            unprotected_mem_size_kib: struct_.unprotectedMemSizeKib,
+        }
+    }
+}
+
 /// Utilization information for a device. Each sample period may be between 1
 /// second and 1/6 second, depending on the product being queried.
 // Checked against local
@@ -363,6 +451,38 @@ impl From<nvmlViolationTime_t> for ViolationTime {
     }
 }
 
+/**
+Row remapper histogram, counting memory banks by how many rows they have left
+for remapping.
+*/
+// Checked against local
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RowRemapperHistogram {
+    /// Banks with every remapping row available.
+    pub max: u32,
+    /// Banks with most remapping rows available.
+    pub high: u32,
+    /// Banks with some remapping rows available.
+    pub partial: u32,
+    /// Banks with few remapping rows available.
+    pub low: u32,
+    /// Banks with no remapping rows available.
+    pub none: u32,
+}
+
+impl From<nvmlRowRemapperHistogramValues_t> for RowRemapperHistogram {
+    fn from(struct_: nvmlRowRemapperHistogramValues_t) -> Self {
+        Self {
+            max: struct_.max,
+            high: struct_.high,
+            partial: struct_.partial,
+            low: struct_.low,
+            none: struct_.none,
+        }
+    }
+}
+
 /**
 Accounting statistics for a process.
 
@@ -443,8 +563,7 @@ pub struct EncoderSessionInfo {
     /// The ID of the process that owns this session.
     pub pid: u32,
     /// The ID of the vGPU instance that owns this session (if applicable).
-    // TODO: Stronger typing if vgpu stuff gets wrapped
-    pub vgpu_instance: Option<u32>,
+    pub vgpu_instance: Option<VgpuInstanceId>,
     pub codec_type: EncoderType,
     /// Current horizontal encoding resolution.
     pub hres: u32,
@@ -472,7 +591,7 @@ impl TryFrom<nvmlEncoderSessionInfo_t> for EncoderSessionInfo {
             pid: value.pid,
             vgpu_instance: match value.vgpuInstance {
                 0 => None,
-                other => Some(other),
+                other => Some(VgpuInstanceId(other)),
             },
             codec_type: EncoderType::try_from(value.codecType)?,
             hres: value.hResolution,
@@ -533,6 +652,188 @@ impl From<nvmlProcessUtilizationSample_t> for ProcessUtilizationSample {
     }
 }
 
+/// Utilization of a vGPU instance, returned from
+/// `Device.vgpu_utilization_stats()`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuUtilizationSample {
+    pub vgpu_instance: VgpuInstanceId,
+    /// CPU timestamp in μs
+    pub timestamp: u64,
+    /// SM (3D / compute) utilization
+    pub sm_util: SampleValue,
+    /// Frame buffer memory utilization
+    pub mem_util: SampleValue,
+    /// Encoder utilization
+    pub enc_util: SampleValue,
+    /// Decoder utilization
+    pub dec_util: SampleValue,
+}
+
+impl VgpuUtilizationSample {
+    /// Given the tag NVML returned the utilization values with and the C
+    /// struct, returns the sample with the correct value variants.
+    pub fn from_tag_and_struct(
+        tag: &SampleValueType,
+        struct_: nvmlVgpuInstanceUtilizationSample_t,
+    ) -> Self {
+        Self {
+            vgpu_instance: VgpuInstanceId(struct_.vgpuInstance),
+            timestamp: struct_.timeStamp,
+            sm_util: SampleValue::from_tag_and_union(tag, struct_.smUtil),
+            mem_util: SampleValue::from_tag_and_union(tag, struct_.memUtil),
+            enc_util: SampleValue::from_tag_and_union(tag, struct_.encUtil),
+            dec_util: SampleValue::from_tag_and_union(tag, struct_.decUtil),
+        }
+    }
+}
+
+/// Utilization of a vGPU instance, returned from
+/// `Device.vgpu_instances_utilization_info()`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuUtilizationInfo {
+    pub vgpu_instance: VgpuInstanceId,
+    /// CPU timestamp in μs
+    pub timestamp: u64,
+    /// SM (3D / compute) utilization
+    pub sm_util: SampleValue,
+    /// Frame buffer memory utilization
+    pub mem_util: SampleValue,
+    /// Encoder utilization
+    pub enc_util: SampleValue,
+    /// Decoder utilization
+    pub dec_util: SampleValue,
+    /// JPEG engine utilization
+    pub jpg_util: SampleValue,
+    /// Optical flow accelerator utilization
+    pub ofa_util: SampleValue,
+}
+
+impl VgpuUtilizationInfo {
+    /// Given the tag NVML returned the utilization values with and the C
+    /// struct, returns the info with the correct value variants.
+    pub fn from_tag_and_struct(
+        tag: &SampleValueType,
+        struct_: nvmlVgpuInstanceUtilizationInfo_v1_t,
+    ) -> Self {
+        Self {
+            vgpu_instance: VgpuInstanceId(struct_.vgpuInstance),
+            timestamp: struct_.timeStamp,
+            sm_util: SampleValue::from_tag_and_union(tag, struct_.smUtil),
+            mem_util: SampleValue::from_tag_and_union(tag, struct_.memUtil),
+            enc_util: SampleValue::from_tag_and_union(tag, struct_.encUtil),
+            dec_util: SampleValue::from_tag_and_union(tag, struct_.decUtil),
+            jpg_util: SampleValue::from_tag_and_union(tag, struct_.jpgUtil),
+            ofa_util: SampleValue::from_tag_and_union(tag, struct_.ofaUtil),
+        }
+    }
+}
+
+/// Utilization of a process running in a vGPU guest, returned from
+/// `Device.vgpu_process_utilization_stats()`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuProcessUtilizationSample {
+    /// The vGPU instance the process runs in.
+    pub vgpu_instance: VgpuInstanceId,
+    /// The PID of the process in the guest.
+    pub pid: u32,
+    pub process_name: String,
+    /// CPU timestamp in μs
+    pub timestamp: u64,
+    /// SM (3D / compute) utilization
+    pub sm_util: u32,
+    /// Frame buffer memory utilization
+    pub mem_util: u32,
+    /// Encoder utilization
+    pub enc_util: u32,
+    /// Decoder utilization
+    pub dec_util: u32,
+}
+
+impl TryFrom<nvmlVgpuProcessUtilizationSample_t> for VgpuProcessUtilizationSample {
+    type Error = NvmlError;
+
+    /**
+    Construct `VgpuProcessUtilizationSample` from the corresponding C struct.
+
+    # Errors
+
+    * `Utf8Error`, if the process name obtained from the C function is not
+      valid Utf8
+    */
+    fn try_from(struct_: nvmlVgpuProcessUtilizationSample_t) -> Result<Self, Self::Error> {
+        let process_name = unsafe { CStr::from_ptr(struct_.processName.as_ptr()) };
+
+        Ok(Self {
+            vgpu_instance: VgpuInstanceId(struct_.vgpuInstance),
+            pid: struct_.pid,
+            process_name: process_name.to_str()?.into(),
+            timestamp: struct_.timeStamp,
+            sm_util: struct_.smUtil,
+            mem_util: struct_.memUtil,
+            enc_util: struct_.encUtil,
+            dec_util: struct_.decUtil,
+        })
+    }
+}
+
+/// Utilization of a process running in a vGPU guest, returned from
+/// `Device.vgpu_processes_utilization_info()`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuProcessUtilizationInfo {
+    /// The vGPU instance the process runs in.
+    pub vgpu_instance: VgpuInstanceId,
+    /// The PID of the process in the guest.
+    pub pid: u32,
+    pub process_name: String,
+    /// CPU timestamp in μs
+    pub timestamp: u64,
+    /// SM (3D / compute) utilization
+    pub sm_util: u32,
+    /// Frame buffer memory utilization
+    pub mem_util: u32,
+    /// Encoder utilization
+    pub enc_util: u32,
+    /// Decoder utilization
+    pub dec_util: u32,
+    /// JPEG engine utilization
+    pub jpg_util: u32,
+    /// Optical flow accelerator utilization
+    pub ofa_util: u32,
+}
+
+impl TryFrom<nvmlVgpuProcessUtilizationInfo_v1_t> for VgpuProcessUtilizationInfo {
+    type Error = NvmlError;
+
+    /**
+    Construct `VgpuProcessUtilizationInfo` from the corresponding C struct.
+
+    # Errors
+
+    * `Utf8Error`, if the process name obtained from the C function is not
+      valid Utf8
+    */
+    fn try_from(struct_: nvmlVgpuProcessUtilizationInfo_v1_t) -> Result<Self, Self::Error> {
+        let process_name = unsafe { CStr::from_ptr(struct_.processName.as_ptr()) };
+
+        Ok(Self {
+            vgpu_instance: VgpuInstanceId(struct_.vgpuInstance),
+            pid: struct_.pid,
+            process_name: process_name.to_str()?.into(),
+            timestamp: struct_.timeStamp,
+            sm_util: struct_.smUtil,
+            mem_util: struct_.memUtil,
+            enc_util: struct_.encUtil,
+            dec_util: struct_.decUtil,
+            jpg_util: struct_.jpgUtil,
+            ofa_util: struct_.ofaUtil,
+        })
+    }
+}
+
 /// Struct that stores information returned from `Device.field_values_for()`.
 // TODO: Missing a lot of derives because of the `Result`
 #[derive(Debug)]
@@ -611,8 +912,7 @@ pub struct FbcSessionInfo {
     /// The ID of the process that owns this session
     pub pid: u32,
     /// The ID of the vGPU instance that owns this session (if applicable).
-    // TODO: Stronger typing if vgpu stuff gets wrapped
-    pub vgpu_instance: Option<u32>,
+    pub vgpu_instance: Option<VgpuInstanceId>,
     /// The identifier of the display this session is running on
     pub display_ordinal: u32,
     /// The type of this session
@@ -651,7 +951,7 @@ impl TryFrom<nvmlFBCSessionInfo_t> for FbcSessionInfo {
             pid: value.pid,
             vgpu_instance: match value.vgpuInstance {
                 0 => None,
-                other => Some(other),
+                other => Some(VgpuInstanceId(other)),
             },
             display_ordinal: value.displayOrdinal,
             session_type: FbcSessionType::try_from(value.sessionType)?,
@@ -729,6 +1029,34 @@ impl From<nvmlFanSpeedInfo_t> for FanSpeedInfo {
     }
 }
 
+/// Cooler info, as returned by `Device.cooler_info()`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CoolerInfo {
+    /// The API version number
+    pub version: u32,
+    /// The index of the cooler
+    pub index: u32,
+    /// The kind of control signal the cooler takes
+    pub signal_type: CoolerControl,
+    /// What the cooler cools
+    pub target: CoolerTarget,
+}
+
+impl TryFrom<nvmlCoolerInfo_t> for CoolerInfo {
+    type Error = NvmlError;
+
+    fn try_from(value: nvmlCoolerInfo_t) -> Result<Self, Self::Error> {
+        Ok(Self {
+            version: value.version,
+            index: value.index,
+            signal_type: CoolerControl::try_from(value.signalType)?,
+            target: CoolerTarget::from_bits(value.target)
+                .ok_or(NvmlError::IncorrectBits(Bits::U32(value.target)))?,
+        })
+    }
+}
+
 /// Clock offset info.
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -757,6 +1085,106 @@ impl TryFrom<nvmlClockOffset_v1_t> for ClockOffset {
     }
 }
 
+impl ClockOffset {
+    /**
+    Obtain this struct's C counterpart.
+
+    `version` is passed on as-is, so it must be the one NVML expects for
+    `nvmlClockOffset_v1_t` (as in a `ClockOffset` returned by NVML).
+    */
+    pub fn as_c(&self) -> nvmlClockOffset_v1_t {
+        nvmlClockOffset_v1_t {
+            version: self.version,
+            type_: self.clock_type.as_c(),
+            pstate: self.state.as_c(),
+            clockOffsetMHz: self.clock_offset_mhz,
+            minClockOffsetMHz: self.min_clock_offset_mhz,
+            maxClockOffsetMHz: self.max_clock_offset_mhz,
+        }
+    }
+}
+
+/**
+A performance mode as reported by `Device.performance_modes()`.
+
+NVML describes each mode as a list of `key=value` pairs, such as
+`perf=0, nvclock=324, nvclockmin=324, nvclockmax=1530, memclock=405, ...`.
+*/
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PerformanceMode {
+    /// The index of this performance mode (its `perf` value)
+    pub perf: u32,
+    /// The remaining values of this mode in MHz, keyed by the name NVML gives
+    /// them (e.g. `nvclock`, `memclockmax` or `memTransferRate`)
+    pub clocks: BTreeMap<String, u32>,
+}
+
+impl PerformanceMode {
+    /**
+    Parse the `;`-separated list of modes returned by
+    `nvmlDeviceGetPerformanceModes`.
+
+    Modes without a `perf` value are skipped, as are pairs whose value is not
+    a number.
+    */
+    pub fn parse_list(modes: &str) -> Vec<Self> {
+        modes
+            .split(';')
+            .filter_map(|mode| {
+                let mut clocks = parse_clock_pairs(mode);
+                let perf = clocks.remove("perf")?;
+
+                Some(Self { perf, clocks })
+            })
+            .collect()
+    }
+}
+
+/// Parse a `,`-separated list of `key=value` pairs as returned by NVML's
+/// string-based clock queries, skipping anything that isn't one.
+pub(crate) fn parse_clock_pairs(pairs: &str) -> BTreeMap<String, u32> {
+    pairs
+        .split(',')
+        .filter_map(|pair| {
+            let (key, value) = pair.split_once('=')?;
+            let value = value.trim().parse().ok()?;
+
+            Some((key.trim().to_string(), value))
+        })
+        .collect()
+}
+
+/// A thermal sensor of a device, as returned by `Device.thermal_settings()`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ThermalSensor {
+    /// The controller reading this sensor
+    pub controller: ThermalController,
+    /// What this sensor measures the temperature of
+    pub target: ThermalTarget,
+    /// The current temperature, in °C
+    pub current_temp: i32,
+    /// The default minimum temperature, in °C
+    pub default_min_temp: i32,
+    /// The default maximum temperature, in °C
+    pub default_max_temp: i32,
+}
+
+impl TryFrom<nvmlGpuThermalSettings_t__bindgen_ty_1> for ThermalSensor {
+    type Error = NvmlError;
+
+    fn try_from(value: nvmlGpuThermalSettings_t__bindgen_ty_1) -> Result<Self, Self::Error> {
+        Ok(Self {
+            controller: ThermalController::try_from(value.controller)?,
+            target: ThermalTarget::try_from(value.target)?,
+            current_temp: value.currentTemp,
+            default_min_temp: value.defaultMinTemp,
+            default_max_temp: value.defaultMaxTemp,
+        })
+    }
+}
+
 /// MIG profile placements
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -767,6 +1195,26 @@ pub struct GpuInstancePlacement {
     pub start: u32,
 }
 
+/// The placements of a vGPU type on a physical GPU.
+///
+/// Returned from `VgpuType.supported_placements()` and
+/// `VgpuType.creatable_placements()`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuPlacementList {
+    /// The number of slots an instance of the vGPU type occupies.
+    pub placement_size: u32,
+    /// The IDs of the first slot of each placement.
+    pub placement_ids: Vec<u32>,
+}
+
+impl VgpuPlacementList {
+    /// The slots occupied by an instance placed at `placement_id`.
+    pub fn slots(&self, placement_id: u32) -> std::ops::Range<u32> {
+        placement_id..placement_id + self.placement_size
+    }
+}
+
 impl From<nvmlGpuInstancePlacement_t> for GpuInstancePlacement {
     fn from(value: nvmlGpuInstancePlacement_t) -> Self {
         Self {
@@ -776,6 +1224,46 @@ impl From<nvmlGpuInstancePlacement_t> for GpuInstancePlacement {
     }
 }
 
+/// Information about a MIG GPU instance profile.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GpuInstanceProfileInfo {
+    /// The profile ID.
+    pub id: u32,
+    /// Whether peer-to-peer is supported between instances of this profile.
+    pub is_p2p_supported: bool,
+    /// Number of GPU slices an instance of this profile uses.
+    pub slice_count: u32,
+    /// Maximum number of instances of this profile that fit on the device.
+    pub instance_count: u32,
+    pub multiprocessor_count: u32,
+    pub copy_engine_count: u32,
+    pub decoder_count: u32,
+    pub encoder_count: u32,
+    pub jpeg_count: u32,
+    pub ofa_count: u32,
+    /// Memory available to an instance of this profile, in MiB.
+    pub memory_size_mb: u64,
+}
+
+impl From<nvmlGpuInstanceProfileInfo_t> for GpuInstanceProfileInfo {
+    fn from(value: nvmlGpuInstanceProfileInfo_t) -> Self {
+        Self {
+            id: value.id,
+            is_p2p_supported: value.isP2pSupported != 0,
+            slice_count: value.sliceCount,
+            instance_count: value.instanceCount,
+            multiprocessor_count: value.multiprocessorCount,
+            copy_engine_count: value.copyEngineCount,
+            decoder_count: value.decoderCount,
+            encoder_count: value.encoderCount,
+            jpeg_count: value.jpegCount,
+            ofa_count: value.ofaCount,
+            memory_size_mb: value.memorySizeMB,
+        }
+    }
+}
+
 // Vgpu
 /// Vgpu scheduler capabilities
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -796,12 +1284,23 @@ pub struct VgpuSchedulerCapabilities {
     // Minimum timeslice value in ns
     pub min_time_slice: u32,
     // List of supported scheduler
-    pub supported_schedulers: Vec<u32>,
+    pub supported_schedulers: Vec<VgpuSchedulerPolicy>,
 }
 
 impl From<nvmlVgpuSchedulerCapabilities_t> for VgpuSchedulerCapabilities {
+    /**
+    Construct `VgpuSchedulerCapabilities` from the corresponding C struct.
+
+    Unused slots of the supported scheduler list are skipped.
+    */
     fn from(value: nvmlVgpuSchedulerCapabilities_t) -> Self {
-        let supported_schedulers = value.supportedSchedulers.to_vec();
+        let supported_schedulers = value
+            .supportedSchedulers
+            .iter()
+            .map(|p| VgpuSchedulerPolicy::from(*p))
+            .filter(|p| *p != VgpuSchedulerPolicy::Unknown)
+            .collect();
+
         Self {
             is_arr_mode_supported: value.isArrModeSupported > 0,
             max_avg_factor_for_arr: value.maxAvgFactorForARR,
@@ -851,6 +1350,32 @@ pub struct VgpuSchedulerParams {
     pub timeslice: u32,
 }
 
+impl VgpuSchedulerParams {
+    /// Given the ARR mode NVML returned the parameters with and the C union,
+    /// returns the parameters read from the correct union field.
+    pub fn from_arr_mode_and_union(
+        arr_mode: &VgpuSchedulerArrMode,
+        union: nvmlVgpuSchedulerParams_t,
+    ) -> Self {
+        match arr_mode {
+            VgpuSchedulerArrMode::Enabled => {
+                let data = unsafe { union.vgpuSchedDataWithARR };
+                Self {
+                    avg_factor: Some(data.avgFactor),
+                    timeslice: data.timeslice,
+                }
+            }
+            _ => {
+                let data = unsafe { union.vgpuSchedData };
+                Self {
+                    avg_factor: None,
+                    timeslice: data.timeslice,
+                }
+            }
+        }
+    }
+}
+
 /// Vgpu scheduler Log entry
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -889,9 +1414,9 @@ pub struct VgpuSchedulerLog {
     /// Engine id whose software runlist are fetched
     pub engine_id: u32,
     /// Scheduler policy
-    pub scheduler_policy: u32,
+    pub scheduler_policy: VgpuSchedulerPolicy,
     /// Scheduler Round Robin Mode
-    pub arr_mode: u32,
+    pub arr_mode: VgpuSchedulerArrMode,
     pub scheduler_params: VgpuSchedulerParams,
     /// Number of log entries fetched during the call
     pub entries_count: u32,
@@ -901,32 +1426,19 @@ pub struct VgpuSchedulerLog {
 
 impl From<nvmlVgpuSchedulerLog_t> for VgpuSchedulerLog {
     fn from(value: nvmlVgpuSchedulerLog_t) -> Self {
+        let arr_mode = VgpuSchedulerArrMode::from(value.arrMode);
         let entries = value
             .logEntries
             .iter()
+            .take(value.entriesCount as usize)
             .map(|e| VgpuSchedulerLogEntry::from(*e))
             .collect::<Vec<_>>();
-        let params = match value.arrMode {
-            2 => {
-                let data = unsafe { value.schedulerParams.vgpuSchedDataWithARR };
-                VgpuSchedulerParams {
-                    avg_factor: Some(data.avgFactor),
-                    timeslice: data.timeslice,
-                }
-            }
-            _ => {
-                let data = unsafe { value.schedulerParams.vgpuSchedData };
-                VgpuSchedulerParams {
-                    avg_factor: None,
-                    timeslice: data.timeslice,
-                }
-            }
-        };
+        let params = VgpuSchedulerParams::from_arr_mode_and_union(&arr_mode, value.schedulerParams);
 
         Self {
             engine_id: value.engineId,
-            scheduler_policy: value.schedulerPolicy,
-            arr_mode: value.arrMode,
+            scheduler_policy: VgpuSchedulerPolicy::from(value.schedulerPolicy),
+            arr_mode,
             scheduler_params: params,
             entries_count: entries.len() as u32,
             entries,
@@ -939,16 +1451,24 @@ impl From<nvmlVgpuSchedulerLog_t> for VgpuSchedulerLog {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct VgpuSchedulerGetState {
     /// Adaptative Round Robin scheduler mode
-    pub arr_mode: u32,
+    pub arr_mode: VgpuSchedulerArrMode,
     /// Scheduler policy
-    pub scheduler_policy: u32,
+    pub scheduler_policy: VgpuSchedulerPolicy,
+    /// Scheduler parameters
+    pub scheduler_params: VgpuSchedulerParams,
 }
 
 impl From<nvmlVgpuSchedulerGetState_t> for VgpuSchedulerGetState {
     fn from(value: nvmlVgpuSchedulerGetState_t) -> Self {
+        let arr_mode = VgpuSchedulerArrMode::from(value.arrMode);
+
         Self {
-            arr_mode: value.arrMode,
-            scheduler_policy: value.schedulerPolicy,
+            scheduler_params: VgpuSchedulerParams::from_arr_mode_and_union(
+                &arr_mode,
+                value.schedulerParams,
+            ),
+            arr_mode,
+            scheduler_policy: VgpuSchedulerPolicy::from(value.schedulerPolicy),
         }
     }
 }
@@ -983,29 +1503,801 @@ impl VgpuSchedulerSetParams {
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct VgpuSchedulerSetState {
-    pub scheduler_policy: u32,
-    pub enable_arr_mode: u32,
+    pub scheduler_policy: VgpuSchedulerPolicy,
+    pub enable_arr_mode: VgpuSchedulerArrMode,
     pub scheduler_params: VgpuSchedulerSetParams,
 }
 
 impl VgpuSchedulerSetState {
     pub fn as_c(&self) -> nvmlVgpuSchedulerSetState_t {
         nvmlVgpuSchedulerSetState_t {
-            enableARRMode: self.enable_arr_mode,
-            schedulerPolicy: self.scheduler_policy,
+            enableARRMode: self.enable_arr_mode.as_c(),
+            schedulerPolicy: self.scheduler_policy.as_c(),
             schedulerParams: self.scheduler_params.as_c(),
         }
     }
 }
 
-#[cfg(test)]
-#[allow(unused_variables, unused_imports)]
-mod tests {
-    use crate::error::*;
-    use crate::ffi::bindings::*;
-    use crate::test_utils::*;
-    use std::convert::TryInto;
-    use std::mem;
+/// Information about a GPU's membership of an NVLink fabric, returned from
+/// `Device.gpu_fabric_info()`.
+// TODO: Missing a lot of derives because of the `Result`
+#[derive(Debug)]
+pub struct GpuFabricInfo {
+    /// The UUID of the cluster the GPU belongs to.
+    pub cluster_uuid: [u8; 16],
+    /// The outcome of the fabric registration, once `state` is
+    /// `GpuFabricState::Completed`.
+    pub status: Result<(), NvmlError>,
+    /// The ID of the fabric clique the GPU belongs to.
+    pub clique_id: u32,
+    pub state: GpuFabricState,
+    /// Raw fabric health flags; see the `is_*()` methods.
+    pub health_mask: u32,
+}
+
+impl TryFrom<nvmlGpuFabricInfoV_t> for GpuFabricInfo {
+    type Error = NvmlError;
+
+    /**
+    Construct `GpuFabricInfo` from the corresponding C struct.
+
+    # Errors
+
+    * `UnexpectedVariant`, for which you can read the docs for
+    */
+    fn try_from(value: nvmlGpuFabricInfoV_t) -> Result<Self, Self::Error> {
+        Ok(Self {
+            cluster_uuid: value.clusterUuid,
+            status: nvml_try(value.status),
+            clique_id: value.cliqueId,
+            state: GpuFabricState::try_from(value.state as u32)?,
+            health_mask: value.healthMask,
+        })
+    }
+}
+
+impl GpuFabricInfo {
+    /// The cluster UUID formatted as `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx`.
+    pub fn cluster_uuid_string(&self) -> String {
+        let hex = |bytes: &[u8]| {
+            bytes
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect::<String>()
+        };
+        let u = &self.cluster_uuid;
+
+        format!(
+            "{}-{}-{}-{}-{}",
+            hex(&u[..4]),
+            hex(&u[4..6]),
+            hex(&u[6..8]),
+            hex(&u[8..10]),
+            hex(&u[10..])
+        )
+    }
+
+    // Each health field is a tri-state: not supported, true or false
+    fn health_field(&self, shift: u32, width: u32) -> Option<bool> {
+        match (self.health_mask >> shift) & width {
+            NVML_GPU_FABRIC_HEALTH_MASK_DEGRADED_BW_TRUE => Some(true),
+            NVML_GPU_FABRIC_HEALTH_MASK_DEGRADED_BW_FALSE => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Whether the GPU's fabric bandwidth is degraded, if reported.
+    pub fn is_bandwidth_degraded(&self) -> Option<bool> {
+        self.health_field(
+            NVML_GPU_FABRIC_HEALTH_MASK_SHIFT_DEGRADED_BW,
+            NVML_GPU_FABRIC_HEALTH_MASK_WIDTH_DEGRADED_BW,
+        )
+    }
+
+    /// Whether route recovery is in progress, if reported.
+    pub fn is_route_recovery_in_progress(&self) -> Option<bool> {
+        self.health_field(
+            NVML_GPU_FABRIC_HEALTH_MASK_SHIFT_ROUTE_RECOVERY,
+            NVML_GPU_FABRIC_HEALTH_MASK_WIDTH_ROUTE_RECOVERY,
+        )
+    }
+
+    /// Whether some fabric routes are unhealthy, if reported.
+    pub fn is_route_unhealthy(&self) -> Option<bool> {
+        self.health_field(
+            NVML_GPU_FABRIC_HEALTH_MASK_SHIFT_ROUTE_UNHEALTHY,
+            NVML_GPU_FABRIC_HEALTH_MASK_WIDTH_ROUTE_UNHEALTHY,
+        )
+    }
+
+    /// Whether recovery from an access timeout is in progress, if reported.
+    pub fn is_access_timeout_recovery_in_progress(&self) -> Option<bool> {
+        self.health_field(
+            NVML_GPU_FABRIC_HEALTH_MASK_SHIFT_ACCESS_TIMEOUT_RECOVERY,
+            NVML_GPU_FABRIC_HEALTH_MASK_WIDTH_ACCESS_TIMEOUT_RECOVERY,
+        )
+    }
+}
+
+/// The expiry date of a vGPU or GRID license.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuLicenseExpiry {
+    pub year: u32,
+    pub month: u16,
+    pub day: u16,
+    pub hour: u16,
+    pub min: u16,
+    pub sec: u16,
+    /// Whether the date fields hold a valid expiry date.
+    pub status: LicenseExpiryStatus,
+}
+
+impl TryFrom<nvmlVgpuLicenseExpiry_t> for VgpuLicenseExpiry {
+    type Error = NvmlError;
+
+    /**
+    Construct `VgpuLicenseExpiry` from the corresponding C struct.
+
+    # Errors
+
+    * `UnexpectedVariant`, if the expiry status is not known to this crate
+    */
+    fn try_from(value: nvmlVgpuLicenseExpiry_t) -> Result<Self, Self::Error> {
+        Ok(Self {
+            year: value.year,
+            month: value.month,
+            day: value.day,
+            hour: value.hour,
+            min: value.min,
+            sec: value.sec,
+            status: LicenseExpiryStatus::try_from(value.status as u32)?,
+        })
+    }
+}
+
+impl TryFrom<nvmlGridLicenseExpiry_t> for VgpuLicenseExpiry {
+    type Error = NvmlError;
+
+    /**
+    Construct `VgpuLicenseExpiry` from the corresponding GRID C struct.
+
+    # Errors
+
+    * `UnexpectedVariant`, if the expiry status is not known to this crate
+    */
+    fn try_from(value: nvmlGridLicenseExpiry_t) -> Result<Self, Self::Error> {
+        Ok(Self {
+            year: value.year,
+            month: value.month,
+            day: value.day,
+            hour: value.hour,
+            min: value.min,
+            sec: value.sec,
+            status: LicenseExpiryStatus::try_from(value.status as u32)?,
+        })
+    }
+}
+
+impl VgpuLicenseExpiry {
+    /**
+    The expiry date as a point in time, taking the date fields as UTC.
+
+    Returns `None` unless `status` is `LicenseExpiryStatus::Valid` and the date
+    fields hold a date no earlier than the Unix epoch.
+    */
+    pub fn timestamp(&self) -> Option<SystemTime> {
+        if self.status != LicenseExpiryStatus::Valid
+            || !(1..=12).contains(&self.month)
+            || !(1..=31).contains(&self.day)
+        {
+            return None;
+        }
+
+        // Days since the epoch of a proleptic Gregorian date
+        let (month, day) = (self.month as i64, self.day as i64);
+        let year = self.year as i64 - (month <= 2) as i64;
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        let days = era * 146_097 + day_of_era - 719_468;
+
+        let secs = days * 86_400 + self.hour as i64 * 3600 + self.min as i64 * 60 + self.sec as i64;
+
+        u64::try_from(secs)
+            .ok()
+            .map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+    }
+}
+
+/// Licensing information of a vGPU instance.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuLicenseInfo {
+    pub is_licensed: bool,
+    pub expiry: VgpuLicenseExpiry,
+    pub state: VgpuLicenseState,
+}
+
+impl TryFrom<nvmlVgpuLicenseInfo_t> for VgpuLicenseInfo {
+    type Error = NvmlError;
+
+    /**
+    Construct `VgpuLicenseInfo` from the corresponding C struct.
+
+    # Errors
+
+    * `UnexpectedVariant`, if the license state or expiry status is not known
+      to this crate
+    */
+    fn try_from(value: nvmlVgpuLicenseInfo_t) -> Result<Self, Self::Error> {
+        Ok(Self {
+            is_licensed: value.isLicensed != 0,
+            expiry: VgpuLicenseExpiry::try_from(value.licenseExpiry)?,
+            state: VgpuLicenseState::try_from(value.currentState)?,
+        })
+    }
+}
+
+/// A feature that can be licensed through GRID.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GridLicensableFeature {
+    pub feature_code: GridLicenseFeatureCode,
+    /// Whether the feature is currently licensed.
+    pub is_licensed: bool,
+    /// License information, e.g. the licensed edition.
+    pub license_info: String,
+    /// The name of the product the feature belongs to.
+    pub product_name: String,
+    /// Whether the feature is enabled.
+    pub is_enabled: bool,
+    pub license_expiry: VgpuLicenseExpiry,
+}
+
+impl TryFrom<nvmlGridLicensableFeature_t> for GridLicensableFeature {
+    type Error = NvmlError;
+
+    /**
+    Construct `GridLicensableFeature` from the corresponding C struct.
+
+    # Errors
+
+    * `UnexpectedVariant`, if the feature code or expiry status is not known
+      to this crate
+    * `Utf8Error`, if a string obtained from the C function is not valid Utf8
+    */
+    fn try_from(value: nvmlGridLicensableFeature_t) -> Result<Self, Self::Error> {
+        let license_info = unsafe { CStr::from_ptr(value.licenseInfo.as_ptr()) };
+        let product_name = unsafe { CStr::from_ptr(value.productName.as_ptr()) };
+
+        Ok(Self {
+            feature_code: GridLicenseFeatureCode::try_from(value.featureCode)?,
+            is_licensed: value.featureState != 0,
+            license_info: license_info.to_str()?.into(),
+            product_name: product_name.to_str()?.into(),
+            is_enabled: value.featureEnabled != 0,
+            license_expiry: VgpuLicenseExpiry::try_from(value.licenseExpiry)?,
+        })
+    }
+}
+
+/// The GRID licensable features of a device, returned from
+/// `Device.grid_licensable_features()`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GridLicensableFeatures {
+    pub is_grid_license_supported: bool,
+    /// The features the driver reported, in the order it reported them.
+    pub features: Vec<GridLicensableFeature>,
+}
+
+impl TryFrom<nvmlGridLicensableFeatures_t> for GridLicensableFeatures {
+    type Error = NvmlError;
+
+    /**
+    Construct `GridLicensableFeatures` from the corresponding C struct.
+
+    # Errors
+
+    * `UnexpectedVariant`, if a feature code or expiry status is not known to
+      this crate
+    * `Utf8Error`, if a string obtained from the C function is not valid Utf8
+    */
+    fn try_from(value: nvmlGridLicensableFeatures_t) -> Result<Self, Self::Error> {
+        let features = value
+            .gridLicensableFeatures
+            .iter()
+            .take(value.licensableFeaturesCount as usize)
+            .map(|f| GridLicensableFeature::try_from(*f))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            is_grid_license_supported: value.isGridLicenseSupported != 0,
+            features,
+        })
+    }
+}
+
+/// Read the fixed-size part of a metadata blob.
+fn blob_header<T: Copy>(bytes: &[u8]) -> T {
+    assert!(bytes.len() >= mem::size_of::<T>());
+
+    unsafe { std::ptr::read_unaligned(bytes.as_ptr() as *const T) }
+}
+
+fn blob_string(raw: &[c_char]) -> Result<String, NvmlError> {
+    let bytes: Vec<u8> = raw
+        .iter()
+        .map(|c| *c as u8)
+        .take_while(|c| *c != 0)
+        .collect();
+
+    Ok(std::str::from_utf8(&bytes)?.into())
+}
+
+/// The range of a metadata blob whose header `header` is covered by its
+/// opaque data.
+fn blob_opaque_range<T>(
+    header: &T,
+    opaque_data: &[c_char],
+    opaque_data_size: c_uint,
+) -> Range<usize> {
+    let start = opaque_data.as_ptr() as usize - header as *const T as usize;
+
+    start..start.saturating_add(opaque_data_size as usize)
+}
+
+/// The opaque data trailing a metadata blob whose header `header` is.
+fn blob_opaque_data<'a, T>(
+    bytes: &'a [u8],
+    header: &T,
+    opaque_data: &[c_char],
+    opaque_data_size: c_uint,
+) -> &'a [u8] {
+    let range = blob_opaque_range(header, opaque_data, opaque_data_size);
+    let end = range.end.min(bytes.len());
+
+    &bytes[range.start.min(end)..end]
+}
+
+/**
+Metadata of a vGPU instance, as needed to check whether it is compatible with
+a physical GPU.
+
+This holds the blob NVML returns as-is so that it can be sent elsewhere (e.g.
+to a live-migration destination host) with `as_bytes()` and restored with
+`from_bytes()`.
+*/
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "Vec<u8>", into = "Vec<u8>"))]
+pub struct VgpuMetadata {
+    bytes: Vec<u8>,
+}
+
+impl TryFrom<Vec<u8>> for VgpuMetadata {
+    type Error = NvmlError;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl From<VgpuMetadata> for Vec<u8> {
+    fn from(metadata: VgpuMetadata) -> Self {
+        metadata.bytes
+    }
+}
+
+impl VgpuMetadata {
+    /**
+    Restore metadata from the bytes returned by `as_bytes()`.
+
+    # Errors
+
+    * `InvalidArg`, if `bytes` is too short for the metadata or its opaque data
+    */
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, NvmlError> {
+        if bytes.len() < mem::size_of::<nvmlVgpuMetadata_t>() {
+            return Err(NvmlError::InvalidArg);
+        }
+
+        let header: nvmlVgpuMetadata_t = blob_header(&bytes);
+        let opaque = blob_opaque_range(&header, &header.opaqueData, header.opaqueDataSize);
+
+        if opaque.end > bytes.len() {
+            return Err(NvmlError::InvalidArg);
+        }
+
+        Ok(Self { bytes })
+    }
+
+    /// The raw metadata blob.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    fn header(&self) -> nvmlVgpuMetadata_t {
+        blob_header(&self.bytes)
+    }
+
+    /// The version of the metadata structure.
+    pub fn version(&self) -> u32 {
+        self.header().version
+    }
+
+    /// The revision of the metadata structure.
+    pub fn revision(&self) -> u32 {
+        self.header().revision
+    }
+
+    /**
+    Whether the guest driver has reported information.
+
+    # Errors
+
+    * `UnexpectedVariant`, for which you can read the docs for
+    */
+    pub fn guest_info_state(&self) -> Result<VgpuGuestInfoState, NvmlError> {
+        VgpuGuestInfoState::try_from(self.header().guestInfoState)
+    }
+
+    /**
+    The version of the guest driver.
+
+    # Errors
+
+    * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    */
+    pub fn guest_driver_version(&self) -> Result<String, NvmlError> {
+        blob_string(&self.header().guestDriverVersion)
+    }
+
+    /**
+    The version of the host driver.
+
+    # Errors
+
+    * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    */
+    pub fn host_driver_version(&self) -> Result<String, NvmlError> {
+        blob_string(&self.header().hostDriverVersion)
+    }
+
+    /// Whether the vGPU supports migration.
+    pub fn migration_supported(&self) -> bool {
+        self.header().vgpuVirtualizationCaps & NVML_VGPU_VIRTUALIZATION_CAP_MIGRATION_YES != 0
+    }
+
+    /// The vGPU version of the guest driver.
+    pub fn guest_vgpu_version(&self) -> u32 {
+        self.header().guestVgpuVersion
+    }
+
+    /// The driver-specific data trailing the metadata.
+    pub fn opaque_data(&self) -> &[u8] {
+        let header = self.header();
+
+        blob_opaque_data(
+            &self.bytes,
+            &header,
+            &header.opaqueData,
+            header.opaqueDataSize,
+        )
+    }
+}
+
+/**
+Metadata of a physical GPU, as needed to check whether a vGPU is compatible
+with it.
+
+Like `VgpuMetadata`, this holds the blob NVML returns as-is.
+*/
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "Vec<u8>", into = "Vec<u8>"))]
+pub struct PgpuMetadata {
+    bytes: Vec<u8>,
+}
+
+impl TryFrom<Vec<u8>> for PgpuMetadata {
+    type Error = NvmlError;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl From<PgpuMetadata> for Vec<u8> {
+    fn from(metadata: PgpuMetadata) -> Self {
+        metadata.bytes
+    }
+}
+
+impl PgpuMetadata {
+    /**
+    Restore metadata from the bytes returned by `as_bytes()`.
+
+    # Errors
+
+    * `InvalidArg`, if `bytes` is too short for the metadata or its opaque data
+    */
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, NvmlError> {
+        if bytes.len() < mem::size_of::<nvmlVgpuPgpuMetadata_t>() {
+            return Err(NvmlError::InvalidArg);
+        }
+
+        let header: nvmlVgpuPgpuMetadata_t = blob_header(&bytes);
+        let opaque = blob_opaque_range(&header, &header.opaqueData, header.opaqueDataSize);
+
+        if opaque.end > bytes.len() {
+            return Err(NvmlError::InvalidArg);
+        }
+
+        Ok(Self { bytes })
+    }
+
+    /// The raw metadata blob.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    fn header(&self) -> nvmlVgpuPgpuMetadata_t {
+        blob_header(&self.bytes)
+    }
+
+    /// The version of the metadata structure.
+    pub fn version(&self) -> u32 {
+        self.header().version
+    }
+
+    /// The revision of the metadata structure.
+    pub fn revision(&self) -> u32 {
+        self.header().revision
+    }
+
+    /**
+    The version of the host driver.
+
+    # Errors
+
+    * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    */
+    pub fn host_driver_version(&self) -> Result<String, NvmlError> {
+        blob_string(&self.header().hostDriverVersion)
+    }
+
+    /// Whether the physical GPU supports migration.
+    pub fn migration_supported(&self) -> bool {
+        self.header().pgpuVirtualizationCaps & NVML_VGPU_VIRTUALIZATION_CAP_MIGRATION_YES != 0
+    }
+
+    /// The range of vGPU versions the host driver supports.
+    pub fn host_supported_vgpu_range(&self) -> VgpuVersion {
+        self.header().hostSupportedVgpuRange.into()
+    }
+
+    /// The driver-specific data trailing the metadata.
+    pub fn opaque_data(&self) -> &[u8] {
+        let header = self.header();
+
+        blob_opaque_data(
+            &self.bytes,
+            &header,
+            &header.opaqueData,
+            header.opaqueDataSize,
+        )
+    }
+}
+
+/// Whether a vGPU is compatible with a physical GPU.
+///
+/// Returned from `Nvml.vgpu_compatibility()`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuCompatibility {
+    /// The VM states in which the vGPU can be migrated; empty if it cannot.
+    pub vm_compatibility: VgpuVmCompatibility,
+    /// What limits compatibility; empty if it is not limited.
+    pub limit: VgpuCompatibilityLimit,
+}
+
+impl TryFrom<nvmlVgpuPgpuCompatibility_t> for VgpuCompatibility {
+    type Error = NvmlError;
+
+    /**
+    Construct `VgpuCompatibility` from the corresponding C struct.
+
+    # Errors
+
+    * `IncorrectBits`, if bits are found in either field that don't match the
+      flags in this wrapper
+    */
+    fn try_from(value: nvmlVgpuPgpuCompatibility_t) -> Result<Self, Self::Error> {
+        Ok(Self {
+            vm_compatibility: VgpuVmCompatibility::from_bits(value.vgpuVmCompatibility).ok_or(
+                NvmlError::IncorrectBits(Bits::U32(value.vgpuVmCompatibility)),
+            )?,
+            limit: VgpuCompatibilityLimit::from_bits(value.compatibilityLimitCode).ok_or(
+                NvmlError::IncorrectBits(Bits::U32(value.compatibilityLimitCode)),
+            )?,
+        })
+    }
+}
+
+/**
+A set of workload power profiles, by profile ID.
+
+NVML identifies profiles by their index in a 255-bit mask; the IDs of the
+profiles this crate knows about are the values of `PowerProfileType`, but
+drivers may report others.
+*/
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PowerProfileMask {
+    mask: [u32; 8],
+}
+
+const MASK_BITS_PER_ELEM: u32 = 32;
+
+impl PowerProfileMask {
+    /// An empty set of profiles.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the profile with the given ID is in the set.
+    pub fn contains_id(&self, id: u32) -> bool {
+        id < NVML_WORKLOAD_POWER_MAX_PROFILES
+            && self.mask[(id / MASK_BITS_PER_ELEM) as usize] & (1 << (id % MASK_BITS_PER_ELEM)) != 0
+    }
+
+    /// Whether `profile` is in the set.
+    pub fn contains(&self, profile: PowerProfileType) -> bool {
+        self.contains_id(profile.as_c())
+    }
+
+    /**
+    Add the profile with the given ID to the set.
+
+    # Panics
+
+    Panics if `id` is not below
+    `ffi::bindings::NVML_WORKLOAD_POWER_MAX_PROFILES` (255).
+    */
+    pub fn insert_id(&mut self, id: u32) {
+        assert!(
+            id < NVML_WORKLOAD_POWER_MAX_PROFILES,
+            "power profile ID {} out of range",
+            id
+        );
+
+        self.mask[(id / MASK_BITS_PER_ELEM) as usize] |= 1 << (id % MASK_BITS_PER_ELEM);
+    }
+
+    /// Add `profile` to the set.
+    pub fn insert(&mut self, profile: PowerProfileType) {
+        self.insert_id(profile.as_c())
+    }
+
+    /// Remove the profile with the given ID from the set.
+    pub fn remove_id(&mut self, id: u32) {
+        if id < NVML_WORKLOAD_POWER_MAX_PROFILES {
+            self.mask[(id / MASK_BITS_PER_ELEM) as usize] &= !(1 << (id % MASK_BITS_PER_ELEM));
+        }
+    }
+
+    /// Remove `profile` from the set.
+    pub fn remove(&mut self, profile: PowerProfileType) {
+        self.remove_id(profile.as_c())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mask.iter().all(|&elem| elem == 0)
+    }
+
+    /// The IDs of the profiles in the set, in ascending order.
+    pub fn ids(&self) -> Vec<u32> {
+        (0..NVML_WORKLOAD_POWER_MAX_PROFILES)
+            .filter(|&id| self.contains_id(id))
+            .collect()
+    }
+
+    /// The profiles in the set, in ascending order of ID, skipping IDs this
+    /// crate does not know about.
+    pub fn profiles(&self) -> Vec<PowerProfileType> {
+        self.ids()
+            .into_iter()
+            .filter_map(|id| PowerProfileType::try_from(id).ok())
+            .collect()
+    }
+
+    /// Obtain this struct's C counterpart.
+    pub fn as_c(&self) -> nvmlMask255_t {
+        nvmlMask255_t { mask: self.mask }
+    }
+}
+
+impl From<nvmlMask255_t> for PowerProfileMask {
+    fn from(struct_: nvmlMask255_t) -> Self {
+        Self { mask: struct_.mask }
+    }
+}
+
+impl FromIterator<PowerProfileType> for PowerProfileMask {
+    fn from_iter<I: IntoIterator<Item = PowerProfileType>>(iter: I) -> Self {
+        let mut mask = Self::new();
+
+        for profile in iter {
+            mask.insert(profile);
+        }
+
+        mask
+    }
+}
+
+/// A workload power profile a device supports.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WorkloadPowerProfileInfo {
+    /// The ID of the profile; see `PowerProfileMask`.
+    pub profile_id: u32,
+    /// The priority of the profile. The lower the value, the higher the
+    /// priority.
+    pub priority: u32,
+    /// The profiles that cannot be requested at the same time as this one.
+    pub conflicting: PowerProfileMask,
+}
+
+impl WorkloadPowerProfileInfo {
+    /// The profile, if this crate knows about it.
+    pub fn profile(&self) -> Option<PowerProfileType> {
+        PowerProfileType::try_from(self.profile_id).ok()
+    }
+}
+
+impl From<nvmlWorkloadPowerProfileInfo_t> for WorkloadPowerProfileInfo {
+    fn from(struct_: nvmlWorkloadPowerProfileInfo_t) -> Self {
+        Self {
+            profile_id: struct_.profileId,
+            priority: struct_.priority,
+            conflicting: struct_.conflictingMask.into(),
+        }
+    }
+}
+
+/// The workload power profiles of a device.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WorkloadPowerProfileCurrentProfiles {
+    /// The profiles the device supports.
+    pub supported: PowerProfileMask,
+    /// The profiles that have been requested.
+    pub requested: PowerProfileMask,
+    /// The profiles that are currently in effect.
+    pub enforced: PowerProfileMask,
+}
+
+impl From<nvmlWorkloadPowerProfileCurrentProfiles_t> for WorkloadPowerProfileCurrentProfiles {
+    fn from(struct_: nvmlWorkloadPowerProfileCurrentProfiles_t) -> Self {
+        Self {
+            supported: struct_.perfProfilesMask.into(),
+            requested: struct_.requestedProfilesMask.into(),
+            enforced: struct_.enforcedProfilesMask.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_variables, unused_imports)]
+mod tests {
+    use crate::error::*;
+    use crate::ffi::bindings::*;
+    use crate::test_utils::*;
+    use std::convert::TryInto;
+    use std::mem;
 
     #[test]
     fn pci_info_from_to_c() {
@@ -1035,4 +2327,304 @@ mod tests {
             Ok(())
         })
     }
+
+    #[test]
+    fn license_expiry_timestamp() {
+        use super::VgpuLicenseExpiry;
+        use crate::enums::device::LicenseExpiryStatus;
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let mut expiry = VgpuLicenseExpiry {
+            year: 2024,
+            month: 2,
+            day: 29,
+            hour: 12,
+            min: 30,
+            sec: 15,
+            status: LicenseExpiryStatus::Valid,
+        };
+        assert_eq!(
+            expiry.timestamp(),
+            Some(UNIX_EPOCH + Duration::from_secs(1_709_209_815))
+        );
+
+        expiry.status = LicenseExpiryStatus::Permanent;
+        assert_eq!(expiry.timestamp(), None);
+    }
+
+    #[test]
+    fn vgpu_scheduler_capabilities_from_c() {
+        use super::VgpuSchedulerCapabilities;
+        use crate::enums::device::VgpuSchedulerPolicy;
+
+        let mut raw: nvmlVgpuSchedulerCapabilities_t = unsafe { mem::zeroed() };
+        raw.supportedSchedulers[0] = NVML_VGPU_SCHEDULER_POLICY_BEST_EFFORT;
+        raw.supportedSchedulers[1] = NVML_VGPU_SCHEDULER_POLICY_FIXED_SHARE;
+
+        raw.supportedSchedulers[2] = 42;
+
+        let capabilities = VgpuSchedulerCapabilities::from(raw);
+        assert_eq!(
+            capabilities.supported_schedulers,
+            vec![
+                VgpuSchedulerPolicy::BestEffort,
+                VgpuSchedulerPolicy::FixedShare,
+                VgpuSchedulerPolicy::Other(42)
+            ]
+        );
+    }
+
+    #[test]
+    fn gpu_fabric_info_from_c() {
+        use super::GpuFabricInfo;
+        use crate::enums::device::GpuFabricState;
+
+        let mut raw: nvmlGpuFabricInfoV_t = unsafe { mem::zeroed() };
+        raw.clusterUuid = [
+            0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0, 0x01, 0x23, 0x45, 0x67, 0x89, 0xab,
+            0xcd, 0xef,
+        ];
+        raw.state = NVML_GPU_FABRIC_STATE_COMPLETED as _;
+        raw.status = nvmlReturn_enum_NVML_SUCCESS;
+        // Bandwidth degraded, route unhealthy not, the rest not supported
+        raw.healthMask = NVML_GPU_FABRIC_HEALTH_MASK_DEGRADED_BW_TRUE
+            | (NVML_GPU_FABRIC_HEALTH_MASK_ROUTE_UNHEALTHY_FALSE
+                << NVML_GPU_FABRIC_HEALTH_MASK_SHIFT_ROUTE_UNHEALTHY);
+
+        let info = GpuFabricInfo::try_from(raw).expect("fabric info");
+        assert_eq!(info.state, GpuFabricState::Completed);
+        assert!(info.status.is_ok());
+        assert_eq!(
+            info.cluster_uuid_string(),
+            "12345678-9abc-def0-0123-456789abcdef"
+        );
+        assert_eq!(info.is_bandwidth_degraded(), Some(true));
+        assert_eq!(info.is_route_unhealthy(), Some(false));
+        assert_eq!(info.is_route_recovery_in_progress(), None);
+        assert_eq!(info.is_access_timeout_recovery_in_progress(), None);
+    }
+
+    #[test]
+    fn vgpu_metadata_blob() {
+        use super::VgpuMetadata;
+
+        assert!(VgpuMetadata::from_bytes(vec![0; 16]).is_err());
+
+        let mut raw: nvmlVgpuMetadata_t = unsafe { mem::zeroed() };
+        raw.version = 3;
+        raw.guestDriverVersion[..3].copy_from_slice(&[b'5' as _, b'5' as _, 0]);
+        raw.opaqueDataSize = 6;
+
+        let header_len = mem::size_of::<nvmlVgpuMetadata_t>();
+        let mut bytes = vec![0; header_len + 2];
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                &raw as *const _ as *const u8,
+                bytes.as_mut_ptr(),
+                header_len,
+            );
+        }
+        // The opaque data starts in the header and runs past it
+        bytes[header_len - 4..].copy_from_slice(&[1, 2, 3, 4, 5, 6]);
+
+        let metadata = VgpuMetadata::from_bytes(bytes).expect("metadata");
+        assert_eq!(metadata.version(), 3);
+        assert_eq!(metadata.guest_driver_version().expect("version"), "55");
+        assert!(!metadata.migration_supported());
+        assert_eq!(metadata.opaque_data(), &[1, 2, 3, 4, 5, 6]);
+
+        // Claiming more opaque data than the blob holds
+        let mut bytes = metadata.as_bytes().to_vec();
+        bytes.pop();
+        assert!(matches!(
+            VgpuMetadata::from_bytes(bytes),
+            Err(NvmlError::InvalidArg)
+        ));
+    }
+
+    #[test]
+    fn clock_offset_round_trip() {
+        use super::ClockOffset;
+        use crate::enum_wrappers::device::{Clock, PerformanceState};
+
+        let offset = ClockOffset {
+            // Implements NVML_STRUCT_VERSION(ClockOffset, 1), as detailed in nvml.h
+            version: (mem::size_of::<nvmlClockOffset_v1_t>() | (1_usize << 24_usize)) as u32,
+            clock_type: Clock::Memory,
+            state: PerformanceState::Two,
+            clock_offset_mhz: -200,
+            min_clock_offset_mhz: -1000,
+            max_clock_offset_mhz: 1500,
+        };
+
+        let c = offset.as_c();
+        assert_eq!(c.version >> 24, 1);
+
+        assert_eq!(ClockOffset::try_from(c).unwrap(), offset);
+    }
+
+    #[test]
+    fn performance_mode_parse_list() {
+        use super::PerformanceMode;
+
+        let modes = PerformanceMode::parse_list(
+            "perf=0, nvclock=324, nvclockmin=324, nvclockmax=1530, memclock=405 ; \
+             perf=1, nvclock=1530, memTransferRate=bogus; nvclock=100; ",
+        );
+
+        assert_eq!(modes.len(), 2);
+        assert_eq!(modes[0].perf, 0);
+        assert_eq!(modes[0].clocks.len(), 4);
+        assert_eq!(modes[0].clocks["nvclockmax"], 1530);
+        assert_eq!(modes[0].clocks["memclock"], 405);
+        assert_eq!(modes[1].perf, 1);
+        assert_eq!(modes[1].clocks.len(), 1);
+        assert_eq!(modes[1].clocks["nvclock"], 1530);
+    }
+
+    #[test]
+    fn parse_clock_pairs() {
+        let freqs =
+            super::parse_clock_pairs("nvclock=1530, memclock=5001,memTransferRate=10002, ,x=");
+
+        assert_eq!(freqs.len(), 3);
+        assert_eq!(freqs["nvclock"], 1530);
+        assert_eq!(freqs["memclock"], 5001);
+        assert_eq!(freqs["memTransferRate"], 10002);
+    }
+
+    #[test]
+    fn thermal_sensor_from_c() {
+        use super::ThermalSensor;
+        use crate::enums::device::{ThermalController, ThermalTarget};
+
+        let sensor = ThermalSensor::try_from(nvmlGpuThermalSettings_t__bindgen_ty_1 {
+            controller: nvmlThermalController_t_NVML_THERMAL_CONTROLLER_GPU_INTERNAL,
+            defaultMinTemp: -40,
+            defaultMaxTemp: 95,
+            currentTemp: 41,
+            target: nvmlThermalTarget_t_NVML_THERMAL_TARGET_MEMORY,
+        })
+        .unwrap();
+
+        assert_eq!(sensor.controller, ThermalController::GpuInternal);
+        assert_eq!(sensor.target, ThermalTarget::Memory);
+        assert_eq!(sensor.current_temp, 41);
+        assert_eq!(sensor.default_min_temp, -40);
+        assert_eq!(sensor.default_max_temp, 95);
+
+        let unknown = nvmlGpuThermalSettings_t__bindgen_ty_1 {
+            controller: 100,
+            defaultMinTemp: 0,
+            defaultMaxTemp: 0,
+            currentTemp: 0,
+            target: nvmlThermalTarget_t_NVML_THERMAL_TARGET_GPU,
+        };
+        assert!(matches!(
+            ThermalSensor::try_from(unknown),
+            Err(NvmlError::UnexpectedVariant(100))
+        ));
+    }
+
+    #[test]
+    fn cooler_info_from_c() {
+        use super::CoolerInfo;
+        use crate::bitmasks::device::CoolerTarget;
+        use crate::enum_wrappers::device::CoolerControl;
+
+        let raw = nvmlCoolerInfo_t {
+            version: 0,
+            index: 1,
+            signalType: nvmlCoolerControl_enum_NVML_THERMAL_COOLER_SIGNAL_VARIABLE,
+            target: nvmlCoolerTarget_enum_NVML_THERMAL_COOLER_TARGET_GPU_RELATED,
+        };
+
+        let info = CoolerInfo::try_from(raw).unwrap();
+        assert_eq!(info.index, 1);
+        assert_eq!(info.signal_type, CoolerControl::Variable);
+        assert!(info
+            .target
+            .contains(CoolerTarget::GPU | CoolerTarget::MEMORY));
+        assert!(info.target.contains(CoolerTarget::POWER_SUPPLY));
+
+        assert!(matches!(
+            CoolerInfo::try_from(nvmlCoolerInfo_t { target: 16, ..raw }),
+            Err(NvmlError::IncorrectBits(Bits::U32(16)))
+        ));
+    }
+
+    #[test]
+    fn power_profile_mask() {
+        use super::PowerProfileMask;
+        use crate::enum_wrappers::device::PowerProfileType;
+
+        let mut mask: PowerProfileMask = [PowerProfileType::MaxQ, PowerProfileType::Mig]
+            .iter()
+            .copied()
+            .collect();
+        mask.insert_id(200);
+
+        assert!(mask.contains(PowerProfileType::MaxQ));
+        assert!(!mask.contains(PowerProfileType::MaxP));
+        assert_eq!(mask.ids(), vec![1, 14, 200]);
+        assert_eq!(
+            mask.profiles(),
+            vec![PowerProfileType::MaxQ, PowerProfileType::Mig]
+        );
+
+        let raw = mask.as_c();
+        assert_eq!(raw.mask[0], 1 << 1 | 1 << 14);
+        assert_eq!(raw.mask[6], 1 << 8);
+        assert_eq!(PowerProfileMask::from(raw), mask);
+
+        mask.remove(PowerProfileType::MaxQ);
+        mask.remove(PowerProfileType::Mig);
+        mask.remove_id(200);
+        assert!(mask.is_empty());
+        assert!(!mask.contains_id(255));
+    }
+
+    #[test]
+    fn memory_info_from_v1() {
+        use super::MemoryInfo;
+        use crate::ffi::bindings::nvmlMemory_t;
+
+        let info = MemoryInfo::from(nvmlMemory_t {
+            total: 1000,
+            free: 600,
+            used: 400,
+        });
+
+        assert_eq!(info.reserved, 0);
+        assert_eq!(info.version, 1);
+        assert_eq!(info.used + info.free, info.total);
+    }
+
+    #[test]
+    fn memory_info_percentages() {
+        use super::MemoryInfo;
+
+        let info = MemoryInfo {
+            free: 600,
+            reserved: 200,
+            total: 1200,
+            used: 400,
+            version: 2,
+        };
+
+        assert_eq!(info.usable(), 1000);
+        assert_eq!(info.used_percent(), 40.0);
+        assert_eq!(info.free_percent(), 60.0);
+        assert_eq!(info.reserved_percent(), 200.0 / 1200.0 * 100.0);
+
+        let empty = MemoryInfo {
+            free: 0,
+            reserved: 0,
+            total: 0,
+            used: 0,
+            version: 2,
+        };
+
+        assert_eq!(empty.used_percent(), 0.0);
+    }
 }
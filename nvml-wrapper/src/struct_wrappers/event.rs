@@ -1,7 +1,14 @@
 use crate::device::Device;
-use crate::enums::event::XidError;
+use crate::enums::event::{EventKind, XidError};
+use crate::error::NvmlError;
 use crate::ffi::bindings::*;
 use crate::{bitmasks::event::EventTypes, Nvml};
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+
+/// The value NVML uses for MIG instance IDs that an event is not attributable
+/// to.
+const INVALID_INSTANCE_ID: u32 = 0xFFFFFFFF;
 
 /// Information about an event that has occurred.
 // Checked against local
@@ -23,6 +30,37 @@ pub struct EventData<'nvml> {
     `None` in the case of any other event type.
     */
     pub event_data: Option<XidError>,
+    /**
+    The GPU instance a critical XID error is attributable to, if MIG is
+    enabled.
+
+    `None` in the case of any other event type.
+    */
+    pub gpu_instance_id: Option<u32>,
+    /**
+    The compute instance a critical XID error is attributable to, if MIG is
+    enabled.
+
+    `None` in the case of any other event type.
+    */
+    pub compute_instance_id: Option<u32>,
+}
+
+/**
+Owned information identifying where an event occurred.
+
+Obtained from `EventData.source()`; unlike `EventData` it does not borrow the
+`Nvml` instance, so it can be stored or sent elsewhere.
+*/
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EventSource {
+    /// The NVML index of the device.
+    pub index: u32,
+    /// The UUID of the device.
+    pub uuid: String,
+    pub gpu_instance_id: Option<u32>,
+    pub compute_instance_id: Option<u32>,
 }
 
 impl<'nvml> EventData<'nvml> {
@@ -56,6 +94,47 @@ impl<'nvml> EventData<'nvml> {
             } else {
                 None
             },
+            gpu_instance_id: match event_data.gpuInstanceId {
+                INVALID_INSTANCE_ID => None,
+                id => Some(id),
+            },
+            compute_instance_id: match event_data.computeInstanceId {
+                INVALID_INSTANCE_ID => None,
+                id => Some(id),
+            },
         }
     }
+
+    /**
+    Read the index and UUID of the device this event occurred on.
+
+    # Errors
+
+    Any error returned by `Device.index()` or `Device.uuid()`.
+    */
+    pub fn source(&self) -> Result<EventSource, NvmlError> {
+        Ok(EventSource {
+            index: self.device.index()?,
+            uuid: self.device.uuid()?,
+            gpu_instance_id: self.gpu_instance_id,
+            compute_instance_id: self.compute_instance_id,
+        })
+    }
+
+    /**
+    Decode every event type in `event_type`, along with its data.
+
+    NVML delivers one event type per event, so this normally contains a
+    single element; see `.kind()`.
+    */
+    pub fn kinds(&self) -> Vec<EventKind> {
+        EventKind::decode(self.event_type, self.event_data.as_ref())
+    }
+
+    /// Decode the type of this event, along with its data.
+    ///
+    /// Returns `None` if `event_type` contains no known event type.
+    pub fn kind(&self) -> Option<EventKind> {
+        self.kinds().into_iter().next()
+    }
 }
@@ -14,8 +14,10 @@ use std::{convert::TryFrom, ffi::CStr};
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ExcludedDeviceInfo {
-    pci_info: PciInfo,
-    uuid: String,
+    /// PCI information of the excluded device.
+    pub pci_info: PciInfo,
+    /// The UUID of the excluded device.
+    pub uuid: String,
 }
 
 impl TryFrom<nvmlExcludedDeviceInfo_t> for ExcludedDeviceInfo {
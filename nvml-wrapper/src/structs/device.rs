@@ -1,10 +1,18 @@
 #[cfg(target_os = "windows")]
 use crate::enum_wrappers::device::DriverModel;
+use crate::enum_wrappers::device::MemoryLocation;
 use crate::enum_wrappers::device::OperationMode;
+use crate::enum_wrappers::device::Sampling;
+use crate::enums::device::PowerSmoothingProfileParam;
+use crate::error::NvmlError;
+use crate::ffi::bindings::*;
+use crate::struct_wrappers::device::Sample;
 #[cfg(feature = "serde")]
 use serde_derive::{Deserialize, Serialize};
+use std::time::Duration;
 
-/// Returned from `Device.get_confidential_compute_capabilities()`
+/// Returned from `Nvml.confidential_compute_capabilities()` and
+/// `Device.get_confidential_compute_capabilities()`
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ConfidentialComputeCapabilities {
@@ -14,7 +22,34 @@ pub struct ConfidentialComputeCapabilities {
     pub gpus_caps: ConfidentialComputeGpuCapabilities,
 }
 
-/// The possible CPU capabilities for confidential compute (either None, AMD SEV or Intel TDX)
+impl ConfidentialComputeCapabilities {
+    /// Whether both the CPU and the GPUs support confidential compute, i.e.
+    /// whether attestation can be attempted at all.
+    pub fn is_supported(&self) -> bool {
+        self.cpu_caps != ConfidentialComputeCpuCapabilities::None
+            && self.gpus_caps == ConfidentialComputeGpuCapabilities::Capable
+    }
+}
+
+impl TryFrom<nvmlConfComputeSystemCaps_t> for ConfidentialComputeCapabilities {
+    type Error = NvmlError;
+
+    /**
+    Construct `ConfidentialComputeCapabilities` from the corresponding C struct.
+
+    # Errors
+
+    * `UnexpectedVariant`, for which you can read the docs for
+    */
+    fn try_from(value: nvmlConfComputeSystemCaps_t) -> Result<Self, Self::Error> {
+        Ok(Self {
+            cpu_caps: ConfidentialComputeCpuCapabilities::try_from(value.cpuCaps)?,
+            gpus_caps: ConfidentialComputeGpuCapabilities::try_from(value.gpusCaps)?,
+        })
+    }
+}
+
+/// The possible CPU capabilities for confidential compute
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ConfidentialComputeCpuCapabilities {
@@ -24,6 +59,39 @@ pub enum ConfidentialComputeCpuCapabilities {
     AmdSev,
     /// Intel TDX confidential compute capabilities.
     IntelTdx,
+    /// AMD SEV-SNP confidential compute capabilities.
+    AmdSevSnp,
+    /// AMD SEV-SNP with a virtual Top of Memory (vTOM) confidential compute
+    /// capabilities.
+    AmdSnpVtom,
+}
+
+impl ConfidentialComputeCpuCapabilities {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> u32 {
+        match *self {
+            Self::None => NVML_CC_SYSTEM_CPU_CAPS_NONE,
+            Self::AmdSev => NVML_CC_SYSTEM_CPU_CAPS_AMD_SEV,
+            Self::IntelTdx => NVML_CC_SYSTEM_CPU_CAPS_INTEL_TDX,
+            Self::AmdSevSnp => NVML_CC_SYSTEM_CPU_CAPS_AMD_SEV_SNP,
+            Self::AmdSnpVtom => NVML_CC_SYSTEM_CPU_CAPS_AMD_SNP_VTOM,
+        }
+    }
+}
+
+impl TryFrom<u32> for ConfidentialComputeCpuCapabilities {
+    type Error = NvmlError;
+
+    fn try_from(data: u32) -> Result<Self, Self::Error> {
+        match data {
+            NVML_CC_SYSTEM_CPU_CAPS_NONE => Ok(Self::None),
+            NVML_CC_SYSTEM_CPU_CAPS_AMD_SEV => Ok(Self::AmdSev),
+            NVML_CC_SYSTEM_CPU_CAPS_INTEL_TDX => Ok(Self::IntelTdx),
+            NVML_CC_SYSTEM_CPU_CAPS_AMD_SEV_SNP => Ok(Self::AmdSevSnp),
+            NVML_CC_SYSTEM_CPU_CAPS_AMD_SNP_VTOM => Ok(Self::AmdSnpVtom),
+            _ => Err(NvmlError::UnexpectedVariant(data)),
+        }
+    }
 }
 
 /// The possible GPU capabilities for confidential compute (either not capable or capable)
@@ -36,24 +104,243 @@ pub enum ConfidentialComputeGpuCapabilities {
     NotCapable,
 }
 
-/// Returned from `Device.confidential_compute_gpu_attestation_report_bytes()`
+impl ConfidentialComputeGpuCapabilities {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> u32 {
+        match *self {
+            Self::Capable => NVML_CC_SYSTEM_GPUS_CC_CAPABLE,
+            Self::NotCapable => NVML_CC_SYSTEM_GPUS_CC_NOT_CAPABLE,
+        }
+    }
+}
+
+impl TryFrom<u32> for ConfidentialComputeGpuCapabilities {
+    type Error = NvmlError;
+
+    fn try_from(data: u32) -> Result<Self, Self::Error> {
+        match data {
+            NVML_CC_SYSTEM_GPUS_CC_CAPABLE => Ok(Self::Capable),
+            NVML_CC_SYSTEM_GPUS_CC_NOT_CAPABLE => Ok(Self::NotCapable),
+            _ => Err(NvmlError::UnexpectedVariant(data)),
+        }
+    }
+}
+
+/// The environment confidential compute is running in.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ConfidentialComputeEnvironment {
+    /// Confidential compute is not available.
+    Unavailable,
+    /// A simulated environment, for development.
+    Simulation,
+    /// A production environment.
+    Production,
+}
+
+impl ConfidentialComputeEnvironment {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> u32 {
+        match *self {
+            Self::Unavailable => NVML_CC_SYSTEM_ENVIRONMENT_UNAVAILABLE,
+            Self::Simulation => NVML_CC_SYSTEM_ENVIRONMENT_SIM,
+            Self::Production => NVML_CC_SYSTEM_ENVIRONMENT_PROD,
+        }
+    }
+}
+
+impl TryFrom<u32> for ConfidentialComputeEnvironment {
+    type Error = NvmlError;
+
+    fn try_from(data: u32) -> Result<Self, Self::Error> {
+        match data {
+            NVML_CC_SYSTEM_ENVIRONMENT_UNAVAILABLE => Ok(Self::Unavailable),
+            NVML_CC_SYSTEM_ENVIRONMENT_SIM => Ok(Self::Simulation),
+            NVML_CC_SYSTEM_ENVIRONMENT_PROD => Ok(Self::Production),
+            _ => Err(NvmlError::UnexpectedVariant(data)),
+        }
+    }
+}
+
+/// Returned from `Nvml.confidential_compute_state()`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ConfidentialComputeState {
+    pub environment: ConfidentialComputeEnvironment,
+    /// Whether confidential compute is enabled.
+    pub cc_enabled: bool,
+    /// Whether dev-tools mode, which allows debugging and profiling at the
+    /// cost of the confidentiality guarantees, is on.
+    pub dev_tools_mode: bool,
+}
+
+impl TryFrom<nvmlConfComputeSystemState_t> for ConfidentialComputeState {
+    type Error = NvmlError;
+
+    /**
+    Construct `ConfidentialComputeState` from the corresponding C struct.
+
+    # Errors
+
+    * `UnexpectedVariant`, for which you can read the docs for
+    */
+    fn try_from(value: nvmlConfComputeSystemState_t) -> Result<Self, Self::Error> {
+        Ok(Self {
+            environment: ConfidentialComputeEnvironment::try_from(value.environment)?,
+            cc_enabled: value.ccFeature == NVML_CC_SYSTEM_FEATURE_ENABLED,
+            dev_tools_mode: value.devToolsMode == NVML_CC_SYSTEM_DEVTOOLS_MODE_ON,
+        })
+    }
+}
+
+/// Returned from `Nvml.confidential_compute_settings()`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ConfidentialComputeSettings {
+    pub environment: ConfidentialComputeEnvironment,
+    /// Whether confidential compute is enabled.
+    pub cc_enabled: bool,
+    /// Whether dev-tools mode is on; see `ConfidentialComputeState`.
+    pub dev_tools_mode: bool,
+    /// Whether multi-GPU protected PCIe mode, which protects traffic between
+    /// GPUs, is on.
+    pub multi_gpu_protected_pcie: bool,
+}
+
+impl ConfidentialComputeSettings {
+    /// Whether confidential compute is enabled in production, with none of
+    /// its guarantees relaxed by dev-tools mode.
+    pub fn is_production_ready(&self) -> bool {
+        self.cc_enabled
+            && !self.dev_tools_mode
+            && self.environment == ConfidentialComputeEnvironment::Production
+    }
+}
+
+impl TryFrom<nvmlSystemConfComputeSettings_t> for ConfidentialComputeSettings {
+    type Error = NvmlError;
+
+    /**
+    Construct `ConfidentialComputeSettings` from the corresponding C struct.
+
+    # Errors
+
+    * `UnexpectedVariant`, for which you can read the docs for
+    */
+    fn try_from(value: nvmlSystemConfComputeSettings_t) -> Result<Self, Self::Error> {
+        Ok(Self {
+            environment: ConfidentialComputeEnvironment::try_from(value.environment)?,
+            cc_enabled: value.ccFeature == NVML_CC_SYSTEM_FEATURE_ENABLED,
+            dev_tools_mode: value.devToolsMode == NVML_CC_SYSTEM_DEVTOOLS_MODE_ON,
+            multi_gpu_protected_pcie: value.multiGpuMode == NVML_CC_SYSTEM_MULTIGPU_PROTECTED_PCIE,
+        })
+    }
+}
+
+/**
+The confidential compute key rotation threshold, returned from
+`Nvml.confidential_compute_key_rotation_threshold()` and passed to
+`Nvml.set_confidential_compute_key_rotation_threshold()`.
+
+The threshold is the maximum advantage an attacker may gain from observing
+ciphertext encrypted with the same key, as the negative base-2 logarithm of
+the probability (i.e. `60` is a 2^-60 advantage). A higher value means a
+smaller advantage, so channel keys are rotated more often.
+*/
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct KeyRotationThreshold {
+    pub max_attacker_advantage: u64,
+}
+
+impl KeyRotationThreshold {
+    /// The lowest threshold NVML accepts.
+    pub const MIN: Self = Self {
+        max_attacker_advantage: NVML_CC_KEY_ROTATION_THRESHOLD_ATTACKER_ADVANTAGE_MIN as u64,
+    };
+
+    /// The highest threshold NVML accepts.
+    pub const MAX: Self = Self {
+        max_attacker_advantage: NVML_CC_KEY_ROTATION_THRESHOLD_ATTACKER_ADVANTAGE_MAX as u64,
+    };
+
+    /// The threshold the driver uses by default.
+    pub const DEFAULT: Self = Self {
+        max_attacker_advantage: 60,
+    };
+
+    /// Whether NVML accepts this threshold, i.e. whether it is between
+    /// `MIN` and `MAX`.
+    pub fn is_valid(&self) -> bool {
+        (Self::MIN..=Self::MAX).contains(self)
+    }
+}
+
+/// A new value for a parameter of a power smoothing preset profile, passed to
+/// `Device.update_power_smoothing_preset_profile_param()`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PowerSmoothingProfile {
+    /// The preset profile to update, below
+    /// `ffi::bindings::NVML_POWER_SMOOTHING_MAX_NUM_PROFILES`.
+    pub profile_id: u32,
+    pub param: PowerSmoothingProfileParam,
+    /// The new value, in the unit documented on `param`.
+    pub value: f64,
+}
+
+impl PowerSmoothingProfile {
+    /// Obtain this struct's C counterpart.
+    pub fn as_c(&self) -> nvmlPowerSmoothingProfile_t {
+        nvmlPowerSmoothingProfile_t {
+            // Implements NVML_STRUCT_VERSION(PowerSmoothingProfile, 1), as detailed in nvml.h
+            version: (std::mem::size_of::<nvmlPowerSmoothingProfile_v1_t>() | (1_usize << 24_usize))
+                as u32,
+            profileId: self.profile_id,
+            paramId: self.param.as_c(),
+            value: self.value,
+        }
+    }
+}
+
+/// Returned from `Device.confidential_compute_gpu_attestation_report()`
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ConfidentialComputeGpuAttestationReport {
     /// The size of the attestation report.
     pub attestation_report_size: u32,
-    /// The attestation report, of size
-    /// `ffi::bindings::NVML_CC_GPU_ATTESTATION_REPORT_SIZE` == 8192 bytes.
+    /// The attestation report, `attestation_report_size` bytes long (at most
+    /// `ffi::bindings::NVML_CC_GPU_ATTESTATION_REPORT_SIZE` == 8192 bytes).
     pub attestation_report: Vec<u8>,
     /// Whether the CEC attestation report is present.
     pub is_cec_attestation_report_present: bool,
     /// The size of the CEC attestation report.
     pub cec_attestation_report_size: u32,
-    /// The CEC attestation report, of size
-    /// `ffi::bindings::NVML_CC_GPU_CEC_ATTESTATION_REPORT_SIZE` == 4096 bytes.
+    /// The CEC attestation report, `cec_attestation_report_size` bytes long
+    /// (at most `ffi::bindings::NVML_CC_GPU_CEC_ATTESTATION_REPORT_SIZE` ==
+    /// 4096 bytes), or empty if it is not present.
     pub cec_attestation_report: Vec<u8>,
 }
 
+impl From<nvmlConfComputeGpuAttestationReport_t> for ConfidentialComputeGpuAttestationReport {
+    /// Copy the reports out of the C struct, keeping only the bytes NVML
+    /// reports as filled in.
+    fn from(value: nvmlConfComputeGpuAttestationReport_t) -> Self {
+        let is_cec_attestation_report_present = value.isCecAttestationReportPresent == 1;
+
+        Self {
+            attestation_report_size: value.attestationReportSize,
+            attestation_report: truncated(&value.attestationReport, value.attestationReportSize),
+            is_cec_attestation_report_present,
+            cec_attestation_report_size: value.cecAttestationReportSize,
+            cec_attestation_report: match is_cec_attestation_report_present {
+                true => truncated(&value.cecAttestationReport, value.cecAttestationReportSize),
+                false => vec![],
+            },
+        }
+    }
+}
+
 /// Returned from `Device.confidential_compute_gpu_certificate()`
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -62,14 +349,36 @@ pub struct ConfidentialComputeGpuCertificate {
     pub cert_chain_size: u32,
     /// The size of the attestation certificate chain.
     pub attestation_cert_chain_size: u32,
-    /// The certificate chain, of size
-    /// `ffi::bindings::NVML_GPU_CERT_CHAIN_SIZE` == 4096 bytes.
+    /// The certificate chain, `cert_chain_size` bytes long (at most
+    /// `ffi::bindings::NVML_GPU_CERT_CHAIN_SIZE` == 4096 bytes).
     pub cert_chain: Vec<u8>,
-    /// The attestation certificate chain, of size
-    /// `ffi::bindings::NVML_GPU_ATTESTATION_CERT_CHAIN_SIZE` == 5120 bytes.
+    /// The attestation certificate chain, `attestation_cert_chain_size` bytes
+    /// long (at most `ffi::bindings::NVML_GPU_ATTESTATION_CERT_CHAIN_SIZE` ==
+    /// 5120 bytes).
     pub attestation_cert_chain: Vec<u8>,
 }
 
+/// The first `size` bytes of `buffer`, or all of it if `size` is larger.
+pub(crate) fn truncated(buffer: &[u8], size: u32) -> Vec<u8> {
+    buffer[..buffer.len().min(size as usize)].to_vec()
+}
+
+impl From<nvmlConfComputeGpuCertificate_t> for ConfidentialComputeGpuCertificate {
+    /// Copy the certificate chains out of the C struct, keeping only the
+    /// bytes NVML reports as filled in.
+    fn from(value: nvmlConfComputeGpuCertificate_t) -> Self {
+        Self {
+            cert_chain_size: value.certChainSize,
+            attestation_cert_chain_size: value.attestationCertChainSize,
+            cert_chain: truncated(&value.certChain, value.certChainSize),
+            attestation_cert_chain: truncated(
+                &value.attestationCertChain,
+                value.attestationCertChainSize,
+            ),
+        }
+    }
+}
+
 /// Returned from `Device.auto_boosted_clocks_enabled()`
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -110,6 +419,13 @@ pub struct EccModeState {
     pub pending_enabled: bool,
 }
 
+impl EccModeState {
+    /// Whether the ECC mode will change after the next reboot or GPU reset.
+    pub fn is_change_pending(&self) -> bool {
+        self.currently_enabled != self.pending_enabled
+    }
+}
+
 /// Returned from `Device.gpu_operation_mode()`
 #[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -118,6 +434,97 @@ pub struct OperationModeState {
     pub pending: OperationMode,
 }
 
+/// Memory error counts of one kind, per memory location. Each is `None` if
+/// the device doesn't report errors for that location.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MemoryLocationCounts {
+    pub l1_cache: Option<u64>,
+    pub l2_cache: Option<u64>,
+    pub device: Option<u64>,
+    pub register_file: Option<u64>,
+    pub texture: Option<u64>,
+    pub shared: Option<u64>,
+    pub cbu: Option<u64>,
+    pub sram: Option<u64>,
+}
+
+impl MemoryLocationCounts {
+    /// Every memory location, in the order of the fields.
+    pub const LOCATIONS: [MemoryLocation; 8] = [
+        MemoryLocation::L1Cache,
+        MemoryLocation::L2Cache,
+        MemoryLocation::Device,
+        MemoryLocation::RegisterFile,
+        MemoryLocation::Texture,
+        MemoryLocation::Shared,
+        MemoryLocation::Cbu,
+        MemoryLocation::SRAM,
+    ];
+
+    /// The count for `location`.
+    pub fn get(&self, location: MemoryLocation) -> Option<u64> {
+        *self.field(location)
+    }
+
+    /// Set the count for `location`.
+    pub fn set(&mut self, location: MemoryLocation, count: Option<u64>) {
+        *self.field_mut(location) = count;
+    }
+
+    /// The sum of the counts of every location that reported one.
+    pub fn total(&self) -> u64 {
+        Self::LOCATIONS
+            .iter()
+            .filter_map(|&location| self.get(location))
+            .sum()
+    }
+
+    fn field(&self, location: MemoryLocation) -> &Option<u64> {
+        match location {
+            MemoryLocation::L1Cache => &self.l1_cache,
+            MemoryLocation::L2Cache => &self.l2_cache,
+            MemoryLocation::Device => &self.device,
+            MemoryLocation::RegisterFile => &self.register_file,
+            MemoryLocation::Texture => &self.texture,
+            MemoryLocation::Shared => &self.shared,
+            MemoryLocation::Cbu => &self.cbu,
+            MemoryLocation::SRAM => &self.sram,
+        }
+    }
+
+    fn field_mut(&mut self, location: MemoryLocation) -> &mut Option<u64> {
+        match location {
+            MemoryLocation::L1Cache => &mut self.l1_cache,
+            MemoryLocation::L2Cache => &mut self.l2_cache,
+            MemoryLocation::Device => &mut self.device,
+            MemoryLocation::RegisterFile => &mut self.register_file,
+            MemoryLocation::Texture => &mut self.texture,
+            MemoryLocation::Shared => &mut self.shared,
+            MemoryLocation::Cbu => &mut self.cbu,
+            MemoryLocation::SRAM => &mut self.sram,
+        }
+    }
+}
+
+/// Volatile and aggregate memory error counts of one error type.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EccCounterCounts {
+    /// Counts since the driver was last loaded.
+    pub volatile: MemoryLocationCounts,
+    /// Counts over the lifetime of the device.
+    pub aggregate: MemoryLocationCounts,
+}
+
+/// Returned from `Device.all_memory_error_counters()`
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MemoryErrorCounters {
+    pub corrected: EccCounterCounts,
+    pub uncorrected: EccCounterCounts,
+}
+
 /// Returned from `Device.power_management_limit_constraints()`
 ///
 /// Values are in milliwatts.
@@ -157,10 +564,24 @@ pub struct RetiredPage {
     /// Note that this does not match the virtual address used in CUDA but does
     /// match the address information in XID 63.
     pub address: u64,
-    /// The retirement timestamp.
+    /// The retirement timestamp, as reported by `nvmlDeviceGetRetiredPages_v2`.
     pub timestamp: u64,
 }
 
+/// Returned from `Device.remapped_rows()`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RemappedRows {
+    /// The number of rows remapped due to correctable errors.
+    pub correctable: u32,
+    /// The number of rows remapped due to uncorrectable errors.
+    pub uncorrectable: u32,
+    /// Whether remappings are pending; they take effect after a GPU reset.
+    pub pending: bool,
+    /// Whether a remapping has failed in the past.
+    pub failure_occurred: bool,
+}
+
 /// Populate this newtype with the constants `nvml_wrapper::sys_exports::field_id::*`.
 ///
 /// Used in `FieldValue` and `Device.field_values_for()`.
@@ -177,3 +598,246 @@ pub struct MigMode {
     /// Mode set after reboot.
     pub pending: u32,
 }
+
+/// Returned from `Device.sampling_info()`
+///
+/// Describes the sample buffer NVML keeps for one `Sampling` type, as
+/// derived from the samples currently in it.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SamplingInfo {
+    pub sample_type: Sampling,
+    /// The number of samples currently in the buffer.
+    pub buffer_depth: u32,
+    /// The time between consecutive samples (the median of the observed
+    /// gaps), if there are at least two samples.
+    pub period: Option<Duration>,
+    /// The time between the oldest and the newest sample in the buffer.
+    pub span: Duration,
+    /// The CPU timestamp in μs of the newest sample, if any.
+    pub newest_timestamp: Option<u64>,
+}
+
+impl SamplingInfo {
+    /// Derive sampling info from the samples of `sample_type` in a buffer.
+    pub fn from_samples(sample_type: Sampling, samples: &[Sample]) -> Self {
+        let mut timestamps: Vec<u64> = samples.iter().map(|s| s.timestamp).collect();
+        timestamps.sort_unstable();
+
+        let mut gaps: Vec<u64> = timestamps
+            .windows(2)
+            .map(|w| w[1] - w[0])
+            .filter(|gap| *gap > 0)
+            .collect();
+        gaps.sort_unstable();
+
+        let span = match (timestamps.first(), timestamps.last()) {
+            (Some(oldest), Some(newest)) => newest - oldest,
+            _ => 0,
+        };
+
+        Self {
+            sample_type,
+            buffer_depth: samples.len() as u32,
+            period: gaps
+                .get(gaps.len() / 2)
+                .map(|us| Duration::from_micros(*us)),
+            span: Duration::from_micros(span),
+            newest_timestamp: timestamps.last().copied(),
+        }
+    }
+
+    /**
+    The longest interval between polls that does not miss samples.
+
+    The buffer only holds `span` worth of samples, so polling less often than
+    this loses the oldest ones. Polling more often than `period` returns no
+    new samples when passing the last seen timestamp to `Device.samples()`.
+
+    Returns `None` if the buffer does not hold enough samples to tell.
+    */
+    pub fn max_poll_interval(&self) -> Option<Duration> {
+        self.period.map(|_| self.span)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::enums::device::SampleValue;
+
+    #[test]
+    fn sampling_info_from_samples() {
+        let samples: Vec<Sample> = [3000, 1000, 2000, 2000, 4500]
+            .iter()
+            .map(|&timestamp| Sample {
+                timestamp,
+                value: SampleValue::U32(0),
+            })
+            .collect();
+
+        let info = SamplingInfo::from_samples(Sampling::Power, &samples);
+
+        assert_eq!(info.buffer_depth, 5);
+        assert_eq!(info.period, Some(Duration::from_millis(1)));
+        assert_eq!(info.span, Duration::from_micros(3500));
+        assert_eq!(info.newest_timestamp, Some(4500));
+        assert_eq!(info.max_poll_interval(), Some(Duration::from_micros(3500)));
+
+        let empty = SamplingInfo::from_samples(Sampling::Power, &[]);
+        assert_eq!(empty.period, None);
+        assert_eq!(empty.max_poll_interval(), None);
+    }
+
+    #[test]
+    fn confidential_compute_capabilities_from_c() {
+        let caps = ConfidentialComputeCapabilities::try_from(nvmlConfComputeSystemCaps_t {
+            cpuCaps: NVML_CC_SYSTEM_CPU_CAPS_AMD_SEV_SNP,
+            gpusCaps: NVML_CC_SYSTEM_GPUS_CC_CAPABLE,
+        })
+        .unwrap();
+
+        assert_eq!(caps.cpu_caps, ConfidentialComputeCpuCapabilities::AmdSevSnp);
+        assert_eq!(caps.cpu_caps.as_c(), NVML_CC_SYSTEM_CPU_CAPS_AMD_SEV_SNP);
+        assert!(caps.is_supported());
+
+        let no_cpu = ConfidentialComputeCapabilities {
+            cpu_caps: ConfidentialComputeCpuCapabilities::None,
+            gpus_caps: ConfidentialComputeGpuCapabilities::Capable,
+        };
+        assert!(!no_cpu.is_supported());
+
+        assert!(matches!(
+            ConfidentialComputeCpuCapabilities::try_from(42),
+            Err(NvmlError::UnexpectedVariant(42))
+        ));
+    }
+
+    #[test]
+    fn confidential_compute_settings_from_c() {
+        let settings = ConfidentialComputeSettings::try_from(nvmlSystemConfComputeSettings_t {
+            version: 0,
+            environment: NVML_CC_SYSTEM_ENVIRONMENT_PROD,
+            ccFeature: NVML_CC_SYSTEM_FEATURE_ENABLED,
+            devToolsMode: NVML_CC_SYSTEM_DEVTOOLS_MODE_OFF,
+            multiGpuMode: NVML_CC_SYSTEM_MULTIGPU_PROTECTED_PCIE,
+        })
+        .unwrap();
+
+        assert_eq!(
+            settings.environment,
+            ConfidentialComputeEnvironment::Production
+        );
+        assert!(settings.multi_gpu_protected_pcie);
+        assert!(settings.is_production_ready());
+
+        let dev_tools = ConfidentialComputeSettings {
+            dev_tools_mode: true,
+            ..settings
+        };
+        assert!(!dev_tools.is_production_ready());
+
+        assert!(
+            ConfidentialComputeState::try_from(nvmlConfComputeSystemState_t {
+                environment: 7,
+                ccFeature: NVML_CC_SYSTEM_FEATURE_DISABLED,
+                devToolsMode: NVML_CC_SYSTEM_DEVTOOLS_MODE_OFF,
+            })
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn gpu_certificate_is_truncated_to_reported_sizes() {
+        let mut raw: nvmlConfComputeGpuCertificate_t = unsafe { std::mem::zeroed() };
+        raw.certChainSize = 3;
+        raw.certChain[..4].copy_from_slice(&[1, 2, 3, 4]);
+        raw.attestationCertChainSize = u32::MAX;
+
+        let cert = ConfidentialComputeGpuCertificate::from(raw);
+
+        assert_eq!(cert.cert_chain, vec![1, 2, 3]);
+        assert_eq!(
+            cert.attestation_cert_chain.len(),
+            NVML_GPU_ATTESTATION_CERT_CHAIN_SIZE as usize
+        );
+    }
+
+    #[test]
+    fn gpu_attestation_report_is_truncated_to_reported_sizes() {
+        let mut raw: nvmlConfComputeGpuAttestationReport_t = unsafe { std::mem::zeroed() };
+        raw.attestationReportSize = 2;
+        raw.attestationReport[..3].copy_from_slice(&[7, 8, 9]);
+        raw.cecAttestationReportSize = 4;
+
+        let report = ConfidentialComputeGpuAttestationReport::from(raw);
+        assert_eq!(report.attestation_report, vec![7, 8]);
+        assert!(report.cec_attestation_report.is_empty());
+
+        raw.isCecAttestationReportPresent = 1;
+        let report = ConfidentialComputeGpuAttestationReport::from(raw);
+        assert_eq!(report.cec_attestation_report, vec![0; 4]);
+    }
+
+    #[test]
+    fn key_rotation_threshold_range() {
+        assert!(KeyRotationThreshold::DEFAULT.is_valid());
+        assert!(KeyRotationThreshold::MIN.is_valid());
+        assert!(KeyRotationThreshold::MAX.is_valid());
+        assert!(!KeyRotationThreshold {
+            max_attacker_advantage: 49
+        }
+        .is_valid());
+        assert!(!KeyRotationThreshold {
+            max_attacker_advantage: 66
+        }
+        .is_valid());
+    }
+
+    #[test]
+    fn power_smoothing_profile_as_c() {
+        let profile = PowerSmoothingProfile {
+            profile_id: 2,
+            param: PowerSmoothingProfileParam::RampDownRate,
+            value: 100.5,
+        };
+        let raw = profile.as_c();
+
+        assert_eq!(raw.version >> 24, 1);
+        assert_eq!(raw.profileId, 2);
+        assert_eq!(
+            PowerSmoothingProfileParam::try_from(raw.paramId).unwrap(),
+            PowerSmoothingProfileParam::RampDownRate
+        );
+        assert_eq!(raw.value, 100.5);
+    }
+
+    #[test]
+    fn ecc_mode_change_pending() {
+        let state = EccModeState {
+            currently_enabled: true,
+            pending_enabled: true,
+        };
+        assert!(!state.is_change_pending());
+
+        let state = EccModeState {
+            currently_enabled: true,
+            pending_enabled: false,
+        };
+        assert!(state.is_change_pending());
+    }
+
+    #[test]
+    fn memory_location_counts() {
+        let mut counts = MemoryLocationCounts::default();
+
+        for (i, &location) in MemoryLocationCounts::LOCATIONS.iter().enumerate() {
+            assert_eq!(counts.get(location), None);
+            counts.set(location, Some(i as u64));
+            assert_eq!(counts.get(location), Some(i as u64));
+        }
+
+        counts.set(MemoryLocation::SRAM, None);
+        assert_eq!(counts.total(), 1 + 2 + 3 + 4 + 5 + 6);
+    }
+}
@@ -10,3 +10,27 @@ pub struct UtilizationCounter {
     /// Send counter value
     pub send: u64,
 }
+
+/// Returned by `NvLink.all_error_counters()`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ErrorCounters {
+    /// Data link transmit replay error counter value
+    pub dl_replay: u64,
+    /// Data link transmit recovery error counter value
+    pub dl_recovery: u64,
+    /// Data link receive flow control digit CRC error counter value
+    pub dl_crc_flit: u64,
+    /// Data link receive data CRC error counter value
+    pub dl_crc_data: u64,
+}
+
+impl ErrorCounters {
+    /// The sum of all counters, saturating at `u64::MAX`.
+    pub fn total(&self) -> u64 {
+        self.dl_replay
+            .saturating_add(self.dl_recovery)
+            .saturating_add(self.dl_crc_flit)
+            .saturating_add(self.dl_crc_data)
+    }
+}
@@ -10,9 +10,26 @@ use crate::enums::device::BusType;
 use crate::enums::device::DeviceArchitecture;
 use crate::enums::device::PcieLinkMaxSpeed;
 use crate::enums::device::PowerSource;
+use crate::enums::nv_link::NvLinkBwMode;
 use crate::enums::unit::*;
 use crate::error::NvmlError;
 use crate::event::EventSet;
+use crate::high_level::attestation::AttestationEvidence;
+use crate::high_level::compare::DeviceComparison;
+use crate::high_level::health::{HealthReport, RecommendedAction};
+use crate::high_level::interconnect::InterconnectSample;
+use crate::high_level::mig::{MigDeviceStats, MigSliceIndex, Planner};
+use crate::high_level::multi::MultiResult;
+use crate::high_level::power::{EnergySample, PowerReadings};
+use crate::high_level::process::ProcessEngineUsage;
+use crate::high_level::query::{Metric, QueryValue};
+use crate::high_level::row_remap::RowRemappingAlert;
+use crate::high_level::support::SupportMatrix;
+use crate::high_level::throttle::ThrottleReport;
+#[cfg(target_os = "linux")]
+use crate::high_level::topology::TopologyMatrix;
+use crate::vgpu::{VgpuInstance, VgpuTypeId};
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 
 use crate::struct_wrappers::nv_link::*;
@@ -48,6 +65,24 @@ impl<'nvml> ShouldPrint for Unit<'nvml> {
     }
 }
 
+impl ShouldPrint for AttestationEvidence {
+    fn should_print(&self) -> bool {
+        false
+    }
+}
+
+impl ShouldPrint for ConfidentialComputeGpuAttestationReport {
+    fn should_print(&self) -> bool {
+        false
+    }
+}
+
+impl ShouldPrint for ConfidentialComputeGpuCertificate {
+    fn should_print(&self) -> bool {
+        false
+    }
+}
+
 impl<'nvml> ShouldPrint for EventSet<'nvml> {
     fn should_print(&self) -> bool {
         false
@@ -56,23 +91,49 @@ impl<'nvml> ShouldPrint for EventSet<'nvml> {
 
 impl ShouldPrint for bool {}
 impl ShouldPrint for u32 {}
+impl ShouldPrint for f64 {}
 impl ShouldPrint for i32 {}
 impl ShouldPrint for (u32, u32) {}
+impl ShouldPrint for (i32, i32) {}
 impl ShouldPrint for u64 {}
 impl ShouldPrint for String {}
 impl ShouldPrint for Brand {}
 impl ShouldPrint for [i8; 16] {}
 impl ShouldPrint for Vec<ProcessInfo> {}
+impl ShouldPrint for Vec<WorkloadPowerProfileInfo> {}
+impl ShouldPrint for WorkloadPowerProfileCurrentProfiles {}
 impl ShouldPrint for Vec<ProcessUtilizationSample> {}
 impl ShouldPrint for Vec<PerformanceState> {}
 impl<'nvml> ShouldPrint for Vec<Device<'nvml>> {}
 impl ShouldPrint for Vec<u32> {}
 impl ShouldPrint for Vec<u64> {}
 impl ShouldPrint for Vec<Sample> {}
+impl ShouldPrint for Vec<VgpuUtilizationSample> {}
+impl ShouldPrint for Vec<VgpuUtilizationInfo> {}
+impl ShouldPrint for GridLicensableFeatures {}
+impl ShouldPrint for ConfidentialComputeCapabilities {}
+impl ShouldPrint for ConfidentialComputeState {}
+impl ShouldPrint for ConfidentialComputeSettings {}
+impl ShouldPrint for KeyRotationThreshold {}
+impl ShouldPrint for PowerReadings {}
+impl ShouldPrint for Option<EnergySample> {}
+impl ShouldPrint for Option<ThrottleReport> {}
+impl ShouldPrint for Option<RowRemappingAlert> {}
+impl ShouldPrint for Vec<ThermalSensor> {}
+impl ShouldPrint for CoolerInfo {}
+impl ShouldPrint for GpuFabricInfo {}
+impl ShouldPrint for Vec<VgpuSchedulerLogEntry> {}
+impl ShouldPrint for NvLinkBwMode {}
+impl ShouldPrint for Vec<u8> {}
+impl ShouldPrint for Vec<VgpuProcessUtilizationInfo> {}
+impl ShouldPrint for Vec<VgpuProcessUtilizationSample> {}
 impl ShouldPrint for Vec<Result<FieldValueSample, NvmlError>> {}
 impl ShouldPrint for Vec<HwbcEntry> {}
 impl ShouldPrint for Utilization {}
 impl ShouldPrint for EncoderStats {}
+impl ShouldPrint for SamplingInfo {}
+impl ShouldPrint for PgpuMetadata {}
+impl<T: Debug> ShouldPrint for MultiResult<T> {}
 impl ShouldPrint for FbcStats {}
 impl ShouldPrint for Vec<FbcSessionInfo> {}
 impl ShouldPrint for Vec<EncoderSessionInfo> {}
@@ -86,12 +147,18 @@ impl ShouldPrint for OperationModeState {}
 impl ShouldPrint for InfoRom {}
 impl ShouldPrint for Vec<RetiredPage> {}
 impl ShouldPrint for ExcludedDeviceInfo {}
+impl ShouldPrint for Vec<ExcludedDeviceInfo> {}
 impl ShouldPrint for MemoryInfo {}
+impl ShouldPrint for ConfComputeMemSizeInfo {}
 impl ShouldPrint for PciInfo {}
 impl ShouldPrint for PerformanceState {}
 impl ShouldPrint for PowerManagementConstraints {}
 impl ShouldPrint for ThrottleReasons {}
+impl ShouldPrint for ClocksEventReasons {}
 impl ShouldPrint for ViolationTime {}
+impl ShouldPrint for RemappedRows {}
+impl ShouldPrint for MemoryErrorCounters {}
+impl ShouldPrint for RowRemapperHistogram {}
 impl ShouldPrint for AccountingStats {}
 impl ShouldPrint for EventTypes {}
 impl<'nvml> ShouldPrint for EventData<'nvml> {}
@@ -101,17 +168,36 @@ impl ShouldPrint for PsuInfo {}
 impl ShouldPrint for UnitInfo {}
 impl ShouldPrint for UtilizationControl {}
 impl ShouldPrint for UtilizationCounter {}
+impl ShouldPrint for ErrorCounters {}
 impl ShouldPrint for BusType {}
 impl ShouldPrint for PowerSource {}
 impl ShouldPrint for DeviceArchitecture {}
 impl ShouldPrint for PcieLinkMaxSpeed {}
 impl ShouldPrint for DeviceAttributes {}
-impl ShouldPrint for (Vec<String>, u32) {}
 impl ShouldPrint for GpuVirtualizationMode {}
+impl ShouldPrint for HostVgpuMode {}
+impl ShouldPrint for P2pStatus {}
 impl ShouldPrint for ClockOffset {}
+impl ShouldPrint for Vec<PerformanceMode> {}
+impl ShouldPrint for BTreeMap<String, u32> {}
 impl ShouldPrint for MigMode {}
 impl ShouldPrint for Vec<GpuInstancePlacement> {}
+impl ShouldPrint for GpuInstanceProfileInfo {}
 impl ShouldPrint for (VgpuVersion, VgpuVersion) {}
+impl ShouldPrint for Vec<(Metric, Option<QueryValue>)> {}
+impl ShouldPrint for Vec<MigDeviceStats> {}
+impl ShouldPrint for MigSliceIndex {}
+impl ShouldPrint for Planner {}
+impl ShouldPrint for DeviceComparison {}
+impl ShouldPrint for SupportMatrix {}
+#[cfg(target_os = "linux")]
+impl ShouldPrint for TopologyMatrix {}
+impl ShouldPrint for InterconnectSample {}
+impl ShouldPrint for Vec<ProcessEngineUsage> {}
+impl ShouldPrint for RecommendedAction {}
+impl ShouldPrint for HealthReport {}
+impl ShouldPrint for Vec<VgpuTypeId> {}
+impl<'dev> ShouldPrint for Vec<VgpuInstance<'dev>> {}
 
 #[cfg(target_os = "windows")]
 impl ShouldPrint for DriverModelState {}
@@ -1,15 +1,41 @@
-use std::{ffi::CStr, os::raw::c_uint};
+use std::{convert::TryFrom, ffi::CStr, mem, os::raw::c_uint};
 
 use ffi::bindings::{
-    nvmlVgpuCapability_t, nvmlVgpuTypeId_t, NVML_DEVICE_NAME_BUFFER_SIZE,
-    NVML_GRID_LICENSE_BUFFER_SIZE,
+    nvmlFBCStats_t, nvmlReturn_t, nvmlVgpuCapability_t, nvmlVgpuInstance_t, nvmlVgpuLicenseInfo_t,
+    nvmlVgpuPlacementList_t, nvmlVgpuPlacementList_v2_t, nvmlVgpuTypeId_t, nvmlVgpuVmIdType_t,
+    NVML_DEVICE_NAME_BUFFER_SIZE, NVML_DEVICE_UUID_BUFFER_SIZE, NVML_GRID_LICENSE_BUFFER_SIZE,
+    NVML_SYSTEM_DRIVER_VERSION_BUFFER_SIZE,
 };
 use static_assertions::assert_impl_all;
 
 use crate::{
-    error::{nvml_sym, nvml_try, NvmlError},
+    enum_wrappers::device::{VgpuTypeCapability, VgpuVmIdType},
+    enums::device::VgpuPlacementMode,
+    error::{nvml_sym, nvml_try, nvml_try_blob, nvml_try_list, NvmlError},
+    struct_wrappers::device::{
+        EncoderSessionInfo, FbcSessionInfo, FbcStats, VgpuLicenseInfo, VgpuMetadata,
+        VgpuPlacementList, VgpuUtilizationSample,
+    },
+    structs::device::EncoderStats,
     Device,
 };
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+
+/// The ID of a vGPU type (profile).
+///
+/// Obtained from [`Device::supported_vgpus`] and [`Device::creatable_vgpus`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuTypeId(pub nvmlVgpuTypeId_t);
+
+/// The ID of an active vGPU instance.
+///
+/// This is the value NVML reports in the `vgpu_instance` fields of session
+/// information structs; see also [`VgpuInstance::id`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuInstanceId(pub nvmlVgpuInstance_t);
 
 pub struct VgpuType<'dev> {
     id: nvmlVgpuTypeId_t,
@@ -33,6 +59,11 @@ impl<'dev> VgpuType<'dev> {
         self.device
     }
 
+    /// The ID of this vGPU type.
+    pub fn id(&self) -> VgpuTypeId {
+        VgpuTypeId(self.id)
+    }
+
     /// Retrieve the class of the vGPU type.
     ///
     /// # Errors
@@ -143,6 +174,22 @@ impl<'dev> VgpuType<'dev> {
         Ok(result != 0)
     }
 
+    /// Check whether this vGPU type has the given capability.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this `Device` is invalid
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuTypeGetCapabilities")]
+    pub fn has_capability(&self, capability: VgpuTypeCapability) -> Result<bool, NvmlError> {
+        self.capabilities(capability.as_c())
+    }
+
     /// Retrieve the device ID of the vGPU type.
     ///
     /// # Errors
@@ -194,6 +241,47 @@ impl<'dev> VgpuType<'dev> {
         Ok(limit)
     }
 
+    /// Retrieve the static GSP heap size of the vGPU type in bytes.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this `Device` is invalid
+    /// * `NotSupported`, if this feature is not supported
+    /// * `Unknown`, on any unexpected error
+    #[doc(alias = "nvmlVgpuTypeGetGspHeapSize")]
+    pub fn gsp_heap_size(&self) -> Result<u64, NvmlError> {
+        let sym = nvml_sym(self.device.nvml().lib.nvmlVgpuTypeGetGspHeapSize.as_ref())?;
+
+        let mut size = 0;
+        unsafe {
+            nvml_try(sym(self.id, &mut size))?;
+        }
+        Ok(size)
+    }
+
+    /// Retrieve the static framebuffer reservation of the vGPU type in bytes.
+    ///
+    /// Together with [`VgpuType::gsp_heap_size`], this is the framebuffer an
+    /// instance of this type takes up on top of [`VgpuType::framebuffer_size`].
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this `Device` is invalid
+    /// * `NotSupported`, if this feature is not supported
+    /// * `Unknown`, on any unexpected error
+    #[doc(alias = "nvmlVgpuTypeGetFbReservation")]
+    pub fn fb_reservation(&self) -> Result<u64, NvmlError> {
+        let sym = nvml_sym(self.device.nvml().lib.nvmlVgpuTypeGetFbReservation.as_ref())?;
+
+        let mut size = 0;
+        unsafe {
+            nvml_try(sym(self.id, &mut size))?;
+        }
+        Ok(size)
+    }
+
     /// Retrieve the vGPU framebuffer size in bytes.
     ///
     /// # Errors
@@ -354,4 +442,604 @@ impl<'dev> VgpuType<'dev> {
         }
         Ok((x, y))
     }
+
+    /// Retrieve the placements of this vGPU type that the physical GPU
+    /// supports in the given mode.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this `Device` or vGPU type is invalid
+    /// * `NotSupported`, if this `Device` does not support this feature
+    /// * `InsufficientSize`, if the list kept growing while it was being fetched
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Ada or newer fully supported devices.
+    #[doc(alias = "nvmlDeviceGetVgpuTypeSupportedPlacements")]
+    pub fn supported_placements(
+        &self,
+        mode: VgpuPlacementMode,
+    ) -> Result<VgpuPlacementList, NvmlError> {
+        let sym = nvml_sym(
+            self.device
+                .nvml()
+                .lib
+                .nvmlDeviceGetVgpuTypeSupportedPlacements
+                .as_ref(),
+        )?;
+
+        self.placements(mode, |list| unsafe {
+            sym(self.device.handle(), self.id, list)
+        })
+    }
+
+    /// Retrieve the placements at which an instance of this vGPU type can
+    /// currently be created on the physical GPU.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this `Device` or vGPU type is invalid
+    /// * `NotSupported`, if this `Device` does not support this feature
+    /// * `InsufficientSize`, if the list kept growing while it was being fetched
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Ada or newer fully supported devices.
+    #[doc(alias = "nvmlDeviceGetVgpuTypeCreatablePlacements")]
+    pub fn creatable_placements(&self) -> Result<VgpuPlacementList, NvmlError> {
+        let sym = nvml_sym(
+            self.device
+                .nvml()
+                .lib
+                .nvmlDeviceGetVgpuTypeCreatablePlacements
+                .as_ref(),
+        )?;
+
+        self.placements(VgpuPlacementMode::Heterogeneous, |list| unsafe {
+            sym(self.device.handle(), self.id, list)
+        })
+    }
+
+    fn placements<F>(
+        &self,
+        mode: VgpuPlacementMode,
+        mut query: F,
+    ) -> Result<VgpuPlacementList, NvmlError>
+    where
+        F: FnMut(*mut nvmlVgpuPlacementList_t) -> nvmlReturn_t,
+    {
+        let mut placement_size = 0;
+
+        let placement_ids = nvml_try_list(0, |count, ids| {
+            let mut list = nvmlVgpuPlacementList_t {
+                // Implements NVML_STRUCT_VERSION(VgpuPlacementList, 2), as detailed in nvml.h
+                version: (mem::size_of::<nvmlVgpuPlacementList_v2_t>() | (2_usize << 24_usize))
+                    as u32,
+                placementSize: 0,
+                count: *count,
+                placementIds: ids,
+                mode: mode.as_c(),
+            };
+
+            let res = query(&mut list);
+            *count = list.count;
+            placement_size = list.placementSize;
+
+            res
+        })?;
+
+        Ok(VgpuPlacementList {
+            placement_size,
+            placement_ids,
+        })
+    }
+}
+
+/// A handle to an active vGPU instance on a `Device`.
+///
+/// Obtained from [`Device::active_vgpus`].
+#[derive(Debug)]
+pub struct VgpuInstance<'dev> {
+    instance: nvmlVgpuInstance_t,
+    device: &'dev Device<'dev>,
+}
+
+assert_impl_all!(VgpuInstance: Send, Sync);
+
+impl<'dev> VgpuInstance<'dev> {
+    /// Create a new vGPU instance wrapper.
+    ///
+    /// You probably don't need to use this yourself, but rather through
+    /// [`Device::active_vgpus`].
+    pub fn new(device: &'dev Device, instance: nvmlVgpuInstance_t) -> Self {
+        Self { instance, device }
+    }
+
+    /// Access the `Device` this vGPU instance is running on.
+    pub fn device(&self) -> &'dev Device<'dev> {
+        self.device
+    }
+
+    /// Get the raw vGPU instance handle contained in this struct.
+    ///
+    /// This is the value NVML reports in the `vgpu_instance` fields of
+    /// session information structs.
+    pub fn handle(&self) -> nvmlVgpuInstance_t {
+        self.instance
+    }
+
+    /// The ID of this vGPU instance.
+    pub fn id(&self) -> VgpuInstanceId {
+        VgpuInstanceId(self.instance)
+    }
+
+    /// Retrieve the ID of the VM this vGPU instance is attached to, along with
+    /// the kind of ID it is.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance does not exist anymore
+    /// * `UnexpectedVariant`, for which you can read the docs for
+    /// * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Kepler or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetVmID")]
+    pub fn vm_id(&self) -> Result<(String, VgpuVmIdType), NvmlError> {
+        let sym = nvml_sym(self.device.nvml().lib.nvmlVgpuInstanceGetVmID.as_ref())?;
+
+        unsafe {
+            let mut buffer = vec![0; NVML_DEVICE_UUID_BUFFER_SIZE as usize];
+            let mut id_type: nvmlVgpuVmIdType_t = 0;
+
+            nvml_try(sym(
+                self.instance,
+                buffer.as_mut_ptr(),
+                buffer.len() as u32,
+                &mut id_type,
+            ))?;
+
+            let id_raw = CStr::from_ptr(buffer.as_ptr());
+            Ok((id_raw.to_str()?.into(), VgpuVmIdType::try_from(id_type)?))
+        }
+    }
+
+    /// Retrieve the UUID of this vGPU instance.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance does not exist anymore
+    /// * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Kepler or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetUUID")]
+    pub fn uuid(&self) -> Result<String, NvmlError> {
+        let sym = nvml_sym(self.device.nvml().lib.nvmlVgpuInstanceGetUUID.as_ref())?;
+
+        unsafe {
+            let mut buffer = vec![0; NVML_DEVICE_UUID_BUFFER_SIZE as usize];
+
+            nvml_try(sym(self.instance, buffer.as_mut_ptr(), buffer.len() as u32))?;
+
+            let uuid_raw = CStr::from_ptr(buffer.as_ptr());
+            Ok(uuid_raw.to_str()?.into())
+        }
+    }
+
+    /// Retrieve the mediated device (mdev) UUID of this vGPU instance.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance does not exist anymore
+    /// * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetMdevUUID")]
+    pub fn mdev_uuid(&self) -> Result<String, NvmlError> {
+        let sym = nvml_sym(self.device.nvml().lib.nvmlVgpuInstanceGetMdevUUID.as_ref())?;
+
+        unsafe {
+            let mut buffer = vec![0; NVML_DEVICE_UUID_BUFFER_SIZE as usize];
+
+            nvml_try(sym(self.instance, buffer.as_mut_ptr(), buffer.len() as u32))?;
+
+            let uuid_raw = CStr::from_ptr(buffer.as_ptr());
+            Ok(uuid_raw.to_str()?.into())
+        }
+    }
+
+    /// Retrieve the NVIDIA driver version installed in the VM this vGPU
+    /// instance is attached to.
+    ///
+    /// The version is reported as "Not Available" if no NVIDIA driver is
+    /// installed in the VM, or if the VM has not yet booted far enough to load
+    /// it.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance does not exist anymore
+    /// * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Kepler or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetVmDriverVersion")]
+    pub fn vm_driver_version(&self) -> Result<String, NvmlError> {
+        let sym = nvml_sym(
+            self.device
+                .nvml()
+                .lib
+                .nvmlVgpuInstanceGetVmDriverVersion
+                .as_ref(),
+        )?;
+
+        unsafe {
+            let mut buffer = vec![0; NVML_SYSTEM_DRIVER_VERSION_BUFFER_SIZE as usize];
+
+            nvml_try(sym(self.instance, buffer.as_mut_ptr(), buffer.len() as u32))?;
+
+            let version_raw = CStr::from_ptr(buffer.as_ptr());
+            Ok(version_raw.to_str()?.into())
+        }
+    }
+
+    /// Retrieve the framebuffer usage of this vGPU instance, in bytes.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance does not exist anymore
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Kepler or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetFbUsage")]
+    pub fn fb_usage(&self) -> Result<u64, NvmlError> {
+        let sym = nvml_sym(self.device.nvml().lib.nvmlVgpuInstanceGetFbUsage.as_ref())?;
+
+        let mut usage = 0;
+        unsafe {
+            nvml_try(sym(self.instance, &mut usage))?;
+        }
+        Ok(usage)
+    }
+
+    /// Retrieve the vGPU type (profile) of this vGPU instance.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance does not exist anymore
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Kepler or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetType")]
+    pub fn vgpu_type(&self) -> Result<VgpuType<'dev>, NvmlError> {
+        let sym = nvml_sym(self.device.nvml().lib.nvmlVgpuInstanceGetType.as_ref())?;
+
+        let mut id = 0;
+        unsafe {
+            nvml_try(sym(self.instance, &mut id))?;
+        }
+        Ok(VgpuType::new(self.device, id))
+    }
+
+    /// Retrieve the encoder statistics of this vGPU instance.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance does not exist anymore
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetEncoderStats")]
+    pub fn encoder_stats(&self) -> Result<EncoderStats, NvmlError> {
+        let sym = nvml_sym(
+            self.device
+                .nvml()
+                .lib
+                .nvmlVgpuInstanceGetEncoderStats
+                .as_ref(),
+        )?;
+
+        let (mut session_count, mut average_fps, mut average_latency) = (0, 0, 0);
+        unsafe {
+            nvml_try(sym(
+                self.instance,
+                &mut session_count,
+                &mut average_fps,
+                &mut average_latency,
+            ))?;
+        }
+        Ok(EncoderStats {
+            session_count,
+            average_fps,
+            average_latency,
+        })
+    }
+
+    /// Retrieve information about the active encoder sessions of this vGPU
+    /// instance.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance does not exist anymore
+    /// * `InsufficientSize`, if the list kept growing while it was being fetched
+    /// * `UnexpectedVariant`, if an enum variant not defined in this wrapper gets
+    ///   returned in a field of an `EncoderSessionInfo` struct
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetEncoderSessions")]
+    pub fn encoder_sessions(&self) -> Result<Vec<EncoderSessionInfo>, NvmlError> {
+        let sym = nvml_sym(
+            self.device
+                .nvml()
+                .lib
+                .nvmlVgpuInstanceGetEncoderSessions
+                .as_ref(),
+        )?;
+
+        let sessions = nvml_try_list(unsafe { mem::zeroed() }, |count, sessions| unsafe {
+            sym(self.instance, count, sessions)
+        })?;
+
+        sessions
+            .into_iter()
+            .map(EncoderSessionInfo::try_from)
+            .collect()
+    }
+
+    /// Retrieve the utilization samples of this vGPU instance.
+    ///
+    /// This filters the samples [`Device::vgpu_utilization_stats`] returns for
+    /// the whole device; see there for the meaning of `last_seen_timestamp`
+    /// and the possible errors.
+    pub fn utilization_stats<T>(
+        &self,
+        last_seen_timestamp: T,
+    ) -> Result<Vec<VgpuUtilizationSample>, NvmlError>
+    where
+        T: Into<Option<u64>>,
+    {
+        let id = self.id();
+
+        Ok(self
+            .device
+            .vgpu_utilization_stats(last_seen_timestamp)?
+            .into_iter()
+            .filter(|s| s.vgpu_instance == id)
+            .collect())
+    }
+
+    /// Retrieve the frame buffer capture statistics of this vGPU instance.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance does not exist anymore
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetFBCStats")]
+    pub fn fbc_stats(&self) -> Result<FbcStats, NvmlError> {
+        let sym = nvml_sym(self.device.nvml().lib.nvmlVgpuInstanceGetFBCStats.as_ref())?;
+
+        unsafe {
+            let mut fbc_stats: nvmlFBCStats_t = mem::zeroed();
+            nvml_try(sym(self.instance, &mut fbc_stats))?;
+
+            Ok(fbc_stats.into())
+        }
+    }
+
+    /// Retrieve information about the active frame buffer capture sessions of
+    /// this vGPU instance.
+    ///
+    /// Information such as the resolution, the average FPS and the average
+    /// latency will be zero if no frames have been captured since a session
+    /// was started.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance does not exist anymore
+    /// * `InsufficientSize`, if the list kept growing while it was being fetched
+    /// * `UnexpectedVariant`, for which you can read the docs for
+    /// * `IncorrectBits`, if bits are found in a session's info flags that don't
+    ///   match the flags in this wrapper
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetFBCSessions")]
+    pub fn fbc_sessions(&self) -> Result<Vec<FbcSessionInfo>, NvmlError> {
+        let sym = nvml_sym(
+            self.device
+                .nvml()
+                .lib
+                .nvmlVgpuInstanceGetFBCSessions
+                .as_ref(),
+        )?;
+
+        let sessions = nvml_try_list(unsafe { mem::zeroed() }, |count, sessions| unsafe {
+            sym(self.instance, count, sessions)
+        })?;
+
+        sessions.into_iter().map(FbcSessionInfo::try_from).collect()
+    }
+
+    /// Retrieve the licensing information of this vGPU instance.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance does not exist anymore
+    /// * `UnexpectedVariant`, for which you can read the docs for
+    /// * `Unknown`, on any unexpected error
+    #[doc(alias = "nvmlVgpuInstanceGetLicenseInfo_v2")]
+    pub fn license_info(&self) -> Result<VgpuLicenseInfo, NvmlError> {
+        let sym = nvml_sym(
+            self.device
+                .nvml()
+                .lib
+                .nvmlVgpuInstanceGetLicenseInfo_v2
+                .as_ref(),
+        )?;
+
+        unsafe {
+            let mut info: nvmlVgpuLicenseInfo_t = mem::zeroed();
+            nvml_try(sym(self.instance, &mut info))?;
+
+            VgpuLicenseInfo::try_from(info)
+        }
+    }
+
+    /// Retrieve the frame rate limit set for this vGPU instance.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance does not exist anymore
+    /// * `NotSupported`, if the frame rate limiter is turned off for this vGPU
+    ///   type
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Kepler or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetFrameRateLimit")]
+    pub fn frame_rate_limit(&self) -> Result<u32, NvmlError> {
+        let sym = nvml_sym(
+            self.device
+                .nvml()
+                .lib
+                .nvmlVgpuInstanceGetFrameRateLimit
+                .as_ref(),
+        )?;
+
+        let mut limit = 0;
+        unsafe {
+            nvml_try(sym(self.instance, &mut limit))?;
+        }
+        Ok(limit)
+    }
+
+    /// Retrieve the encoder capacity of this vGPU instance, as a percentage of
+    /// the maximum encoder capacity (0 to 100).
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance does not exist anymore
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetEncoderCapacity")]
+    pub fn encoder_capacity(&self) -> Result<u32, NvmlError> {
+        let sym = nvml_sym(
+            self.device
+                .nvml()
+                .lib
+                .nvmlVgpuInstanceGetEncoderCapacity
+                .as_ref(),
+        )?;
+
+        let mut capacity = 0;
+        unsafe {
+            nvml_try(sym(self.instance, &mut capacity))?;
+        }
+        Ok(capacity)
+    }
+
+    /// Set the encoder capacity of this vGPU instance, as a percentage of the
+    /// maximum encoder capacity (0 to 100).
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid or `capacity` is
+    ///   greater than 100
+    /// * `NotFound`, if this vGPU instance does not exist anymore
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceSetEncoderCapacity")]
+    pub fn set_encoder_capacity(&mut self, capacity: u32) -> Result<(), NvmlError> {
+        let sym = nvml_sym(
+            self.device
+                .nvml()
+                .lib
+                .nvmlVgpuInstanceSetEncoderCapacity
+                .as_ref(),
+        )?;
+
+        unsafe { nvml_try(sym(self.instance, capacity)) }
+    }
+
+    /// Retrieve the metadata of this vGPU instance, for checking its
+    /// compatibility with a physical GPU with [`crate::Nvml::vgpu_compatibility`].
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance does not exist anymore
+    /// * `InsufficientSize`, if the metadata kept growing while it was being
+    ///   fetched
+    /// * `Unknown`, on any unexpected error
+    #[doc(alias = "nvmlVgpuInstanceGetMetadata")]
+    pub fn metadata(&self) -> Result<VgpuMetadata, NvmlError> {
+        let sym = nvml_sym(self.device.nvml().lib.nvmlVgpuInstanceGetMetadata.as_ref())?;
+
+        let bytes = nvml_try_blob(|metadata, size| unsafe { sym(self.instance, metadata, size) })?;
+
+        VgpuMetadata::from_bytes(bytes)
+    }
 }